@@ -0,0 +1,9 @@
+-- Add up migration script here
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_constraint WHERE conname = 'users_pkey'
+    ) THEN
+        ALTER TABLE users ADD CONSTRAINT users_pkey PRIMARY KEY (id);
+    END IF;
+END$$;
@@ -0,0 +1,6 @@
+-- Add down migration script here
+ALTER TABLE refresh_tokens DROP COLUMN IF EXISTS authenticated_at;
+
+ALTER TABLE sessions DROP COLUMN IF EXISTS persistent;
+ALTER TABLE sessions DROP COLUMN IF EXISTS authenticated_at;
+ALTER TABLE sessions DROP COLUMN IF EXISTS absolute_expires_at;
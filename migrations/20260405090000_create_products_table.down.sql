@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS products;
+DROP TYPE IF EXISTS product_gender;
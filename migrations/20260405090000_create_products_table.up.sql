@@ -0,0 +1,28 @@
+-- Add up migration script here
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'product_gender') THEN
+        CREATE TYPE product_gender AS ENUM ('men', 'women', 'unisex', 'kids');
+    END IF;
+END$$;
+
+CREATE TABLE IF NOT EXISTS products (
+    id UUID NOT NULL PRIMARY KEY DEFAULT gen_random_uuid(),
+    slug TEXT NOT NULL UNIQUE,
+    sku TEXT NOT NULL UNIQUE,
+    brand TEXT NOT NULL,
+    model TEXT NOT NULL,
+    colorway TEXT NOT NULL,
+    description TEXT,
+    gender product_gender NOT NULL,
+    release_date DATE NOT NULL,
+    price_cents BIGINT NOT NULL CHECK (price_cents >= 0),
+    currency TEXT NOT NULL,
+    archived_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS products_release_date_idx
+    ON products (release_date, id)
+    WHERE archived_at IS NULL;
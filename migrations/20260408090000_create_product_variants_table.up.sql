@@ -0,0 +1,22 @@
+-- Add up migration script here
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'shoe_width') THEN
+        CREATE TYPE shoe_width AS ENUM ('narrow', 'standard', 'wide', 'extra_wide');
+    END IF;
+END$$;
+
+CREATE TABLE IF NOT EXISTS product_variants (
+    id UUID NOT NULL PRIMARY KEY DEFAULT gen_random_uuid(),
+    product_id UUID NOT NULL REFERENCES products (id) ON DELETE CASCADE,
+    sku TEXT NOT NULL UNIQUE,
+    size_us DOUBLE PRECISION NOT NULL CHECK (size_us * 2 = floor(size_us * 2)),
+    size_uk DOUBLE PRECISION NOT NULL CHECK (size_uk * 2 = floor(size_uk * 2)),
+    size_eu TEXT NOT NULL,
+    width shoe_width NOT NULL DEFAULT 'standard',
+    stock_quantity INTEGER NOT NULL DEFAULT 0 CHECK (stock_quantity >= 0),
+    price_cents_override BIGINT CHECK (price_cents_override >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (product_id, width, size_us)
+);
@@ -0,0 +1,2 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS webhook_deliveries_endpoint_event_idx;
@@ -0,0 +1,4 @@
+-- Add up migration script here
+CREATE UNIQUE INDEX IF NOT EXISTS webhook_deliveries_endpoint_event_idx
+    ON webhook_deliveries (endpoint_id, event_id)
+    WHERE replay_of IS NULL;
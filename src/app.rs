@@ -1,50 +1,99 @@
-use std::{net::SocketAddr, ops::Deref, sync::Arc};
+use std::{
+    net::SocketAddr,
+    ops::Deref,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 
 use axum::{
-    Json, Router,
+    Router,
     extract::FromRef,
     http::{HeaderName, StatusCode},
-    middleware::from_fn,
-    response::IntoResponse,
+    middleware::{from_fn, from_fn_with_state},
 };
 use axum_extra::extract::cookie::Key;
+use redis::aio::MultiplexedConnection;
 use sqlx::{PgPool, postgres::PgPoolOptions};
 use std::time::Duration;
 use tokio::{net::TcpListener, signal};
 use tokio_util::sync::CancellationToken;
 use tower::ServiceBuilder;
-use tower_governor::{GovernorLayer, governor::GovernorConfigBuilder};
-use tracing::info;
+use tracing::{info, warn};
 
 use axum::http::{HeaderValue, Method, header};
 
 use tower_http::{compression::CompressionLayer, cors::CorsLayer, timeout::TimeoutLayer};
 
 use crate::{
-    ErrorResponse, Result,
+    Error, Result,
     clients::{
-        email_client::build_email_client, http_client::build_http_client,
+        database_client::run_migrations,
+        email_client::build_email_client,
+        http_client::{build_http_client, build_webhook_client},
         redis_client::build_redis_client,
     },
-    configuration::{Configuration, app_config::ApplicationConfig},
-    features::auth::{AuthModule, AuthService},
-    middlewares::{error_logging, request_logging},
+    configuration::{
+        Configuration,
+        app_config::ApplicationConfig,
+        reload::{RuntimeSettings, SettingsHandle},
+    },
+    events::{AuditLog, DeliverEvent, Delivery, EventBus},
+    features::{
+        admin::{AdminModule, AdminService, UserBanned},
+        auth::{
+            AuthModule, AuthService, CleanupExpiredSessions, CleanupUnverifiedAccounts,
+            OnboardingPipeline, PasswordReset, UserSignedIn, UserSignedUp, UserVerified,
+        },
+        catalog::{
+            CatalogModule, CatalogService,
+            inventory::{InventoryModule, InventoryService},
+        },
+        docs::DocsModule,
+        health::{HealthModule, HealthService},
+        metrics::{MetricsModule, MetricsService},
+        webhooks::{DeliverWebhook, WebhookDispatcher, WebhookService, WebhooksModule},
+    },
+    jobs::{CronSchedule, JobQueue, JobRunner},
+    middlewares::{
+        CSRF_HEADER_NAME, IDEMPOTENCY_KEY_HEADER, RateLimitLayer, client_ip, csrf_protection,
+        error_logging, http_metrics, problem_details, request_logging, rotate_cookie_keys,
+    },
 };
 
 pub struct Application {
     port: u16,
     pool: PgPool,
+    ready: Arc<AtomicBool>,
+    drain: Duration,
     listener: TcpListener,
     router: Router,
+    metrics_port: u16,
+    metrics_listener: TcpListener,
+    metrics_router: Router,
+    settings: SettingsHandle,
+    jobs: JobRunner,
 }
 
 #[derive(Clone)]
 pub struct AppState(Arc<InnerState>);
 
 pub struct InnerState {
-    key: Key,
+    pub(crate) key: Key,
+    pub(crate) previous_keys: Vec<Key>,
     pub config: ApplicationConfig,
+    pub settings: SettingsHandle,
+    pub redis: MultiplexedConnection,
     pub auth_service: AuthService,
+    pub admin_service: AdminService,
+    pub jobs: JobQueue,
+    pub events: EventBus,
+    pub webhook_service: WebhookService,
+    pub catalog_service: CatalogService,
+    pub inventory_service: InventoryService,
+    pub health_service: HealthService,
+    pub metrics_service: MetricsService,
 }
 
 impl Deref for AppState {
@@ -68,42 +117,152 @@ impl Application {
             .unwrap();
         let listener = TcpListener::bind(addr).await?;
 
+        let metrics_addr: SocketAddr = format!("{}:{}", config.metrics.host, config.metrics.port)
+            .parse()
+            .unwrap();
+        let metrics_listener = TcpListener::bind(metrics_addr).await?;
+
         let database_pool =
             PgPoolOptions::new().connect_lazy_with(config.database.connect_options());
+
+        if config.database.migrate_on_startup {
+            run_migrations(&database_pool).await?;
+        }
+
         let redis_client = build_redis_client(&config.redis).await?;
         let email_client = build_email_client(&config.smtp, &config.application).await?;
         let http_client = build_http_client()?;
+        let settings = SettingsHandle::new(RuntimeSettings::from(config));
+        let job_queue = JobQueue::new(database_pool.clone());
+        let events = EventBus::new(job_queue.clone());
 
         let auth_module = AuthModule::new(
             config.application.clone(),
             config.oauth2.clone(),
+            settings.clone(),
             database_pool.clone(),
             redis_client.clone(),
-            email_client,
+            email_client.clone(),
             http_client.clone(),
+            events.clone(),
+        );
+
+        let admin_module = AdminModule::new(
+            &config.application,
+            database_pool.clone(),
+            redis_client.clone(),
+            events.clone(),
+        );
+
+        let webhooks_module = WebhooksModule::new(
+            database_pool.clone(),
+            build_webhook_client(config.application.allow_private_webhook_targets)?,
+            config.application.allow_private_webhook_targets,
+        );
+
+        let catalog_module = CatalogModule::new(database_pool.clone());
+        let inventory_module = InventoryModule::new(database_pool.clone());
+
+        let ready = Arc::new(AtomicBool::new(true));
+        let health_module = HealthModule::new(
+            database_pool.clone(),
+            redis_client.clone(),
+            email_client.clone(),
+            ready.clone(),
+            Duration::from_millis(config.application.health_check_timeout_ms),
+        );
+        let metrics_module = MetricsModule::new(
+            database_pool.clone(),
+            redis_client.clone(),
+            Duration::from_millis(config.application.health_check_timeout_ms),
         );
 
         let state = AppState(Arc::new(InnerState {
             key: Key::from(config.application.cookie_secret.as_bytes()),
+            previous_keys: config
+                .application
+                .previous_cookie_secrets
+                .iter()
+                .map(|secret| Key::from(secret.as_bytes()))
+                .collect(),
             config: config.application.clone(),
+            settings: settings.clone(),
+            redis: redis_client.clone(),
             auth_service: auth_module.auth_service,
+            admin_service: admin_module.admin_service,
+            jobs: job_queue.clone(),
+            events: events.clone(),
+            webhook_service: webhooks_module.webhook_service,
+            catalog_service: catalog_module.catalog_service,
+            inventory_service: inventory_module.inventory_service,
+            health_service: health_module.health_service,
+            metrics_service: metrics_module.metrics_service,
         }));
 
+        events
+            .subscribe::<UserSignedUp>("audit", Delivery::Immediate, AuditLog)
+            .subscribe::<UserVerified>("audit", Delivery::Immediate, AuditLog)
+            .subscribe::<PasswordReset>("audit", Delivery::Immediate, AuditLog)
+            .subscribe::<UserSignedIn>("audit", Delivery::Immediate, AuditLog)
+            .subscribe::<UserBanned>("audit", Delivery::Immediate, AuditLog);
+        WebhookDispatcher::new(database_pool.clone()).subscribe(&events);
+        OnboardingPipeline::new(database_pool.clone(), email_client).subscribe(&events);
+
+        let cleanup_schedule =
+            CronSchedule::parse(&config.jobs.cleanup_unverified_accounts_schedule)
+                .expect("validated with the configuration");
+        let session_cleanup_schedule =
+            CronSchedule::parse(&config.jobs.cleanup_expired_sessions_schedule)
+                .expect("validated with the configuration");
+        let jobs = JobRunner::new(state.clone(), config.jobs.clone())
+            .register::<DeliverEvent>()
+            .register::<DeliverWebhook>()
+            .recurring(
+                "cleanup_unverified_accounts",
+                cleanup_schedule,
+                CleanupUnverifiedAccounts,
+            )
+            .recurring(
+                "cleanup_expired_sessions",
+                session_cleanup_schedule,
+                CleanupExpiredSessions,
+            );
+
+        let health = Router::new()
+            .nest("/health", HealthModule::router())
+            .with_state(state.clone());
+
+        let docs =
+            DocsModule::router(&config.application.session_cookie_name).with_state(state.clone());
+
+        let metrics_router = MetricsModule::router().with_state(state.clone());
+
         let app = Router::new()
+            .nest("/api/v1/auth", AuthModule::v1(state.clone()))
+            .nest(
+                "/api/v1",
+                CatalogModule::v1(state.clone()).merge(InventoryModule::v1(state.clone())),
+            )
             .nest(
-                "/api/v1/auth",
-                AuthModule::v1(state.clone(), &config.ratelimit),
+                "/api/v1/admin",
+                AdminModule::v1(state.clone())
+                    .merge(WebhooksModule::v1(state.clone()))
+                    .merge(CatalogModule::admin_v1(state.clone()))
+                    .merge(InventoryModule::admin_v1(state.clone())),
             )
+            .route_layer(from_fn(http_metrics))
             .with_state(state.clone())
             .fallback(handler_404)
             .layer(
                 ServiceBuilder::new()
-                    .layer(GovernorLayer::new(
-                        GovernorConfigBuilder::default()
-                            .per_second(60)
-                            .burst_size(100)
-                            .finish()
-                            .unwrap(),
+                    .layer(from_fn_with_state(state.clone(), problem_details))
+                    .layer(from_fn_with_state(state.clone(), client_ip))
+                    .layer(from_fn_with_state(state.clone(), rotate_cookie_keys))
+                    .layer(RateLimitLayer::new(
+                        redis_client.clone(),
+                        settings.clone(),
+                        "global",
+                        |r| &r.global,
                     ))
                     .layer(from_fn(request_logging))
                     .layer(from_fn(error_logging))
@@ -125,39 +284,75 @@ impl Application {
                             )
                             .allow_headers([
                                 header::CONTENT_TYPE,
+                                header::AUTHORIZATION,
                                 HeaderName::from_static("content-type"),
+                                HeaderName::from_static(CSRF_HEADER_NAME),
+                                HeaderName::from_static(IDEMPOTENCY_KEY_HEADER),
                             ])
                             .allow_credentials(true),
                     )
+                    .layer(from_fn_with_state(state.clone(), csrf_protection))
                     .layer(CompressionLayer::new())
                     .layer(TimeoutLayer::with_status_code(
                         StatusCode::REQUEST_TIMEOUT,
                         Duration::from_secs(10),
                     )),
-            );
+            )
+            .merge(health)
+            .merge(docs);
 
         Ok(Self {
             pool: database_pool.clone(),
+            ready,
+            drain: Duration::from_secs(config.application.shutdown_drain_seconds),
             port: listener
                 .local_addr()
                 .expect("Failed to get tcp port")
                 .port(),
             listener,
             router: app,
+            metrics_port: metrics_listener
+                .local_addr()
+                .expect("Failed to get metrics tcp port")
+                .port(),
+            metrics_listener,
+            metrics_router,
+            settings,
+            jobs,
         })
     }
 
     pub async fn run(self, token: CancellationToken) -> Result<()> {
         info!("Running on port {}", self.port);
+        info!("Serving metrics on port {}", self.metrics_port);
+
+        // The metrics listener outlives the API drain so the shutdown stays observable.
+        let metrics_token = token.child_token();
+        let jobs_token = token.child_token();
+        let jobs = tokio::spawn(self.jobs.run(jobs_token.clone()));
+        let metrics_server = tokio::spawn(
+            axum::serve(self.metrics_listener, self.metrics_router)
+                .with_graceful_shutdown(metrics_token.clone().cancelled_owned())
+                .into_future(),
+        );
 
         axum::serve(
             self.listener,
             self.router
                 .into_make_service_with_connect_info::<SocketAddr>(),
         )
-        .with_graceful_shutdown(shutdown_signal(token))
+        .with_graceful_shutdown(shutdown_signal(token, self.ready, self.drain))
         .await?;
 
+        // Workers finish the job in hand, so stop them before closing the pool.
+        jobs_token.cancel();
+        jobs.await.map_err(|e| Error::Internal(e.to_string()))?;
+
+        metrics_token.cancel();
+        metrics_server
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))??;
+
         self.pool.close().await;
 
         Ok(())
@@ -166,9 +361,20 @@ impl Application {
     pub fn port(&self) -> u16 {
         self.port
     }
+
+    pub fn metrics_port(&self) -> u16 {
+        self.metrics_port
+    }
+
+    /// Handle for publishing reloaded settings to the running services.
+    pub fn settings(&self) -> SettingsHandle {
+        self.settings.clone()
+    }
 }
 
-async fn shutdown_signal(token: CancellationToken) {
+/// Waits for a shutdown request, then fails readiness and keeps serving for
+/// `drain` so the load balancer stops routing here before connections close.
+async fn shutdown_signal(token: CancellationToken, ready: Arc<AtomicBool>, drain: Duration) {
     let ctrl_c = async {
         signal::ctrl_c()
             .await
@@ -194,13 +400,18 @@ async fn shutdown_signal(token: CancellationToken) {
         _ = token.cancelled() => {
         }
     }
+
+    ready.store(false, Ordering::SeqCst);
+
+    if !drain.is_zero() {
+        warn!(
+            drain_seconds = drain.as_secs(),
+            "Shutdown requested, draining"
+        );
+        tokio::time::sleep(drain).await;
+    }
 }
 
-async fn handler_404() -> impl IntoResponse {
-    (
-        StatusCode::NOT_FOUND,
-        Json(ErrorResponse {
-            error: "Not found".to_string(),
-        }),
-    )
+async fn handler_404() -> Error {
+    Error::NotFound("Not found".into())
 }
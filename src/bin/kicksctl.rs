@@ -0,0 +1,199 @@
+use std::{path::PathBuf, process};
+
+use clap::{Parser, Subcommand};
+use kicksapi::{
+    self,
+    clients::{
+        database_client::run_migrations, email_client::build_email_client,
+        redis_client::build_redis_client,
+    },
+    configuration::{Configuration, config_dir, environment},
+    error::Result,
+    events::EventBus,
+    features::{
+        admin::{AdminModule, CreateAdminInput},
+        auth::{EmailAddress, Password},
+        webhooks::WebhookDispatcher,
+    },
+    jobs::JobQueue,
+};
+use sqlx::PgPool;
+
+/// Management commands for kicksapi, using the same configuration as the server.
+#[derive(Debug, Parser)]
+#[command(name = "kicksctl", version)]
+struct Cli {
+    /// Directory holding `<environment>.yaml`.
+    #[arg(long, global = true)]
+    config_dir: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Apply pending database migrations.
+    Migrate,
+    /// Create a verified admin account.
+    CreateAdmin {
+        #[arg(long)]
+        email: String,
+        /// Read from the environment so it stays out of shell history.
+        #[arg(long, env = "KICKSCTL_ADMIN_PASSWORD", hide_env_values = true)]
+        password: String,
+    },
+    /// Ban a user and revoke all of their sessions.
+    Ban {
+        #[arg(long)]
+        email: String,
+    },
+    /// Lift a ban.
+    Unban {
+        #[arg(long)]
+        email: String,
+    },
+    /// Sign a user out of every session and refresh token.
+    RevokeSessions {
+        #[arg(long)]
+        email: String,
+    },
+    /// Send a test message through the configured SMTP server.
+    SendTestEmail {
+        #[arg(long)]
+        to: String,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let config_dir = cli.config_dir.unwrap_or_else(config_dir);
+    let config = Configuration::load_from(&config_dir, &environment()).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1);
+    });
+
+    match cli.command {
+        Command::Migrate => {
+            run_migrations(&connect(&config).await?).await?;
+            println!("Migrations applied");
+        }
+        Command::CreateAdmin { email, password } => {
+            let admin = admin_module(&config).await?;
+            let user_id = admin
+                .admin_service
+                .create_admin(CreateAdminInput {
+                    email: EmailAddress::parse(email)?,
+                    password: Password::parse(password)?,
+                })
+                .await?;
+            println!("Created admin {user_id}");
+        }
+        Command::Ban { email } => {
+            let admin = admin_module(&config).await?;
+            let user_id = admin
+                .admin_service
+                .set_banned(&EmailAddress::parse(email)?, true)
+                .await?;
+            println!("Banned user {user_id}");
+        }
+        Command::Unban { email } => {
+            let admin = admin_module(&config).await?;
+            let user_id = admin
+                .admin_service
+                .set_banned(&EmailAddress::parse(email)?, false)
+                .await?;
+            println!("Unbanned user {user_id}");
+        }
+        Command::RevokeSessions { email } => {
+            let admin = admin_module(&config).await?;
+            let revoked = admin
+                .admin_service
+                .revoke_sessions(&EmailAddress::parse(email)?)
+                .await?;
+            println!(
+                "Revoked {} sessions and {} refresh tokens",
+                revoked.sessions, revoked.refresh_tokens
+            );
+        }
+        Command::SendTestEmail { to } => {
+            let email_client = build_email_client(&config.smtp, &config.application).await?;
+            email_client.send_test_email(&to).await?;
+            println!("Test email sent to {to}");
+        }
+    }
+
+    Ok(())
+}
+
+async fn connect(config: &Configuration) -> Result<PgPool> {
+    Ok(PgPool::connect_with(config.database.connect_options()).await?)
+}
+
+async fn admin_module(config: &Configuration) -> Result<AdminModule> {
+    let pool = connect(config).await?;
+    let redis = build_redis_client(&config.redis).await?;
+
+    // Subscribers here only queue deliveries; the server's workers run them.
+    let events = EventBus::new(JobQueue::new(pool.clone()));
+    WebhookDispatcher::new(pool.clone()).subscribe(&events);
+
+    Ok(AdminModule::new(&config.application, pool, redis, events))
+}
+
+#[cfg(test)]
+mod test {
+    use clap::{CommandFactory, error::ErrorKind};
+
+    use super::*;
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn subcommands_use_kebab_case() {
+        let cli = Cli::try_parse_from(["kicksctl", "revoke-sessions", "--email", "a@b.com"]);
+
+        assert!(matches!(
+            cli.unwrap().command,
+            Command::RevokeSessions { email } if email == "a@b.com"
+        ));
+    }
+
+    #[test]
+    fn create_admin_requires_password() {
+        // Read from a variable nothing sets, so the outcome doesn't depend on
+        // whether `KICKSCTL_ADMIN_PASSWORD` is exported.
+        let command = Cli::command().mut_subcommand("create-admin", |create_admin| {
+            create_admin.mut_arg("password", |password| {
+                password.env("KICKSCTL_TEST_PASSWORD_NEVER_SET")
+            })
+        });
+
+        let err = command
+            .try_get_matches_from(["kicksctl", "create-admin", "--email", "a@b.com"])
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn create_admin_takes_password_flag() {
+        let cli = Cli::try_parse_from([
+            "kicksctl",
+            "create-admin",
+            "--email",
+            "a@b.com",
+            "--password",
+            "hunter2hunter2",
+        ]);
+
+        assert!(matches!(
+            cli.unwrap().command,
+            Command::CreateAdmin { password, .. } if password == "hunter2hunter2"
+        ));
+    }
+}
@@ -0,0 +1,17 @@
+use sqlx::{PgPool, migrate::Migrator};
+use tracing::info;
+
+use crate::Result;
+
+/// Migrations from `migrations/`, embedded at compile time.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Applies pending migrations. Concurrent runners are serialized by an
+/// advisory lock, so replicas may call this at the same time.
+pub async fn run_migrations(pool: &PgPool) -> Result<()> {
+    MIGRATOR.run(pool).await?;
+
+    info!("Database migrations are up to date");
+
+    Ok(())
+}
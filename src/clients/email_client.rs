@@ -9,6 +9,7 @@ use lettre::{
 use crate::{
     Error, Result,
     configuration::{app_config::ApplicationConfig, smtp_config::SmtpConfig},
+    features::metrics::record_email,
 };
 
 #[derive(Debug)]
@@ -22,7 +23,7 @@ impl EmailClient {
     pub async fn send_account_verification_email(&self, to: &str, token: &str) -> Result<()> {
         let to: Mailbox = to
             .parse()
-            .map_err(|_| Error::Conflict("invalid email address".to_string()))?;
+            .map_err(|_| Error::Internal("invalid email address".to_string()))?;
 
         let url = format!(
             "{}{}?email={}&token={}",
@@ -55,18 +56,13 @@ impl EmailClient {
 
         let message = self.build_message(to, "Account Verification", text_part, html_part)?;
 
-        self.transport
-            .send(message)
-            .await
-            .map_err(|e| Error::Internal(format!("Failed to send email: {}", e)))?;
-
-        Ok(())
+        self.send(message, "account_verification").await
     }
 
     pub async fn send_reset_password_email(&self, to: &str, token: &str) -> Result<()> {
         let to: Mailbox = to
             .parse()
-            .map_err(|_| Error::Conflict("invalid email address".to_string()))?;
+            .map_err(|_| Error::Internal("invalid email address".to_string()))?;
 
         let url = format!(
             "{}{}?email={}&token={}",
@@ -99,10 +95,87 @@ impl EmailClient {
 
         let message = self.build_message(to, "Password Reset", text_part, html_part)?;
 
-        self.transport
-            .send(message)
-            .await
-            .map_err(|e| Error::Internal(format!("Failed to send email: {}", e)))?;
+        self.send(message, "password_reset").await
+    }
+
+    /// Greets a newly verified user and points them at their onboarding checklist.
+    pub async fn send_welcome_email(&self, to: &str, first_name: Option<&str>) -> Result<()> {
+        let to: Mailbox = to
+            .parse()
+            .map_err(|_| Error::Internal("invalid email address".to_string()))?;
+
+        let greeting = match first_name {
+            Some(name) => format!("Hello {name}"),
+            None => "Hello".to_owned(),
+        };
+        let url = &self.application_config.client_url;
+
+        let text_part = SinglePart::plain(format!(
+            "{greeting},\n\nWelcome aboard! Your account is ready. Finish setting up your profile, shoe size and preferences here:\n\n{url}"
+        ));
+
+        let html_part = SinglePart::html(format!(
+            r#"
+            <html>
+                <body>
+                    <h2>{greeting}!</h2>
+                    <p>Welcome aboard! Your account is ready.</p>
+                    <p>Finish setting up your profile, shoe size and preferences to get recommendations that fit:</p>
+                    <p><a href="{url}" style="background-color: #4CAF50; color: white; padding: 15px 32px; text-align: center; text-decoration: none; display: inline-block; font-size: 16px; border-radius: 5px;">Get Started</a></p>
+                    <br>
+                    <p>Best regards,<br>Your Support Team</p>
+                </body>
+            </html>
+            "#
+        ));
+
+        let message = self.build_message(to, "Welcome", text_part, html_part)?;
+
+        self.send(message, "welcome").await
+    }
+
+    /// Sends a plain message used by operators to check SMTP delivery end to end.
+    pub async fn send_test_email(&self, to: &str) -> Result<()> {
+        let to: Mailbox = to
+            .parse()
+            .map_err(|_| Error::Internal("invalid email address".to_string()))?;
+
+        let text_part = SinglePart::plain(
+            "Hello,\n\nThis is a test message. If you can read it, email delivery works."
+                .to_owned(),
+        );
+
+        let html_part = SinglePart::html(
+            r#"
+            <html>
+                <body>
+                    <h2>Hello!</h2>
+                    <p>This is a test message. If you can read it, email delivery works.</p>
+                </body>
+            </html>
+            "#
+            .to_owned(),
+        );
+
+        let message = self.build_message(to, "Test Email", text_part, html_part)?;
+
+        self.send(message, "test").await
+    }
+
+    /// Verifies the SMTP server accepts connections using `NOOP`.
+    pub async fn ping(&self) -> Result<()> {
+        match self.transport.test_connection().await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(Error::Internal("SMTP server rejected NOOP".into())),
+            Err(e) => Err(Error::Internal(e.to_string())),
+        }
+    }
+
+    async fn send(&self, message: Message, kind: &'static str) -> Result<()> {
+        let result = self.transport.send(message).await;
+        record_email(kind, result.is_ok());
+
+        result.map_err(|e| Error::Internal(format!("Failed to send email: {}", e)))?;
 
         Ok(())
     }
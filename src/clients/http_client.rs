@@ -1,6 +1,20 @@
-use std::time::Duration;
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
 
-use reqwest::Client;
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry_http::HeaderInjector;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use reqwest::{
+    Client, RequestBuilder, Url,
+    dns::{Addrs, Name, Resolve, Resolving},
+    header::HeaderMap,
+    redirect,
+};
+use tokio::net::lookup_host;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use crate::{Error, Result};
 
@@ -10,3 +24,190 @@ pub fn build_http_client() -> Result<Client> {
         .build()
         .map_err(|e| Error::Internal(format!("Failed to build http client: {:#?}", e)))
 }
+
+/// For requests to admin-configured URLs. Redirects aren't followed, and
+/// unless `allow_private_targets` is set hosts only resolve to public
+/// addresses, so a receiver can't point us at the internal network.
+pub fn build_webhook_client(allow_private_targets: bool) -> Result<Client> {
+    let builder = Client::builder()
+        .timeout(Duration::from_secs(5))
+        .redirect(redirect::Policy::none());
+    let builder = match allow_private_targets {
+        true => builder,
+        false => builder.dns_resolver(PublicResolver),
+    };
+
+    builder
+        .build()
+        .map_err(|e| Error::Internal(format!("Failed to build webhook client: {:#?}", e)))
+}
+
+/// Whether every address `url`'s host resolves to is public. Hosts that
+/// don't resolve pass, since requests to them fail anyway.
+pub async fn is_public_host(url: &Url) -> bool {
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    match lookup_host((host, 0)).await {
+        Ok(mut addrs) => addrs.all(|addr| is_public_ip(addr.ip())),
+        Err(_) => true,
+    }
+}
+
+/// False for loopback, private, link-local and other addresses that don't
+/// route on the internet.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip.to_canonical() {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || a == 0
+                // Carrier-grade NAT, 100.64.0.0/10.
+                || (a == 100 && b & 0xc0 == 64))
+        }
+        IpAddr::V6(ip) => {
+            let [a, b, ..] = ip.segments();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                || ip.is_unique_local()
+                || ip.is_unicast_link_local()
+                // Documentation, 2001:db8::/32.
+                || (a == 0x2001 && b == 0xdb8))
+        }
+    }
+}
+
+/// Resolves like the system resolver but drops non-public addresses. The
+/// check happens on connect, so a host can't pass validation and then
+/// re-resolve to an internal address.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", name.as_str()).into());
+            }
+
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+pub trait PropagateTrace {
+    /// Adds a W3C `traceparent` for the current span so the callee joins the trace.
+    fn propagate_trace(self) -> Self;
+}
+
+impl PropagateTrace for RequestBuilder {
+    fn propagate_trace(self) -> Self {
+        let mut headers = HeaderMap::new();
+
+        TraceContextPropagator::new().inject_context(
+            &Span::current().context(),
+            &mut HeaderInjector(&mut headers),
+        );
+
+        self.headers(headers)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use opentelemetry::trace::{TraceContextExt, TracerProvider};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tracing::info_span;
+    use tracing_subscriber::{Registry, layer::SubscriberExt};
+
+    use super::*;
+
+    #[test]
+    fn outbound_request_carries_current_trace() {
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = Registry::default()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = info_span!("oauth2").entered();
+            let trace_id = span.context().span().span_context().trace_id();
+
+            let request = Client::new()
+                .get("http://localhost/")
+                .propagate_trace()
+                .build()
+                .unwrap();
+
+            let traceparent = request.headers()["traceparent"].to_str().unwrap();
+            assert!(traceparent.starts_with(&format!("00-{trace_id}-")));
+        });
+    }
+
+    #[test]
+    fn internal_addresses_are_not_public() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fe80::1",
+            "fd00::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+
+        for ip in ["93.184.216.34", "2606:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[tokio::test]
+    async fn hosts_are_checked_after_resolution() {
+        for url in [
+            "http://127.0.0.1:8080/",
+            "http://[::1]/",
+            "http://localhost/",
+            "http://169.254.169.254/latest/meta-data",
+        ] {
+            assert!(!is_public_host(&Url::parse(url).unwrap()).await, "{url}");
+        }
+        assert!(is_public_host(&Url::parse("https://93.184.216.34/").unwrap()).await);
+    }
+
+    #[tokio::test]
+    async fn webhook_client_refuses_private_hosts() {
+        let client = build_webhook_client(false).unwrap();
+
+        let err = client.get("http://localhost:9/").send().await.unwrap_err();
+
+        assert!(format!("{err:?}").contains("no public address"), "{err:?}");
+    }
+
+    #[test]
+    fn outbound_request_without_trace_has_no_traceparent() {
+        let request = Client::new()
+            .get("http://localhost/")
+            .propagate_trace()
+            .build()
+            .unwrap();
+
+        assert!(!request.headers().contains_key("traceparent"));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod database_client;
 pub mod email_client;
 pub mod http_client;
 pub mod redis_client;
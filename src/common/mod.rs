@@ -1,6 +1,8 @@
 pub mod password_hashing;
 pub mod random_token;
+pub mod token_hashing;
 pub mod validator;
 
 pub use password_hashing::*;
 pub use random_token::*;
+pub use token_hashing::*;
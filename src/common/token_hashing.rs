@@ -0,0 +1,22 @@
+use sha2::{Digest, Sha256};
+
+/// Hashes high-entropy tokens (refresh tokens, API keys) for storage.
+/// Unlike passwords these don't need a slow, salted hash.
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_is_deterministic() {
+        assert_eq!(hash_token("token"), hash_token("token"));
+    }
+
+    #[test]
+    fn test_different_tokens_have_different_hashes() {
+        assert_ne!(hash_token("token"), hash_token("other token"));
+    }
+}
@@ -1,7 +1,8 @@
-use serde::Deserialize;
-use validator::Validate;
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum LogLevel {
     Debug,
@@ -23,12 +24,48 @@ impl LogLevel {
     }
 }
 
-#[derive(Debug, Validate, Deserialize, Clone)]
+/// Body shape of error responses.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorFormat {
+    /// RFC 9457 `application/problem+json`.
+    #[default]
+    Problem,
+    /// The v1 `{"error": ...}` / `{"errors": {...}}` bodies and status codes.
+    /// Requests sending `Accept: application/problem+json` still get problems.
+    Legacy,
+}
+
+/// The one forwarding header read from trusted proxies. Proxies usually
+/// append to one header and pass the other through from the client, so the
+/// other is ignored rather than used as a fallback.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ForwardedHeader {
+    #[default]
+    XForwardedFor,
+    Forwarded,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionStoreKind {
+    Redis,
+    Postgres,
+    Memory,
+    Fallback,
+}
+
+#[derive(Debug, Validate, Deserialize, Serialize, Clone)]
 pub struct ApplicationConfig {
     #[validate(length(min = 1))]
     pub host: String,
     #[validate(range(min = 1, max = 65535))]
     pub port: u16,
+    /// Proxies whose `forwarded_header` is believed.
+    pub trusted_proxies: Vec<IpNet>,
+    #[serde(default)]
+    pub forwarded_header: ForwardedHeader,
     #[validate(length(min = 1), url)]
     pub client_url: String,
     #[validate(length(min = 1))]
@@ -39,17 +76,75 @@ pub struct ApplicationConfig {
     pub session_cookie_name: String,
     #[validate(length(min = 1))]
     pub oauth_state_cookie_name: String,
+    #[validate(length(min = 1))]
+    pub csrf_cookie_name: String,
+    /// Cookie-authenticated paths that may change state without a CSRF
+    /// token, such as callbacks posted by a third party. Exact matches only.
+    #[serde(default)]
+    pub csrf_exempt_paths: Vec<String>,
     pub cookie_secure: bool,
     #[validate(length(min = 40))]
     pub cookie_secret: String,
+    /// Secrets retired by a rotation. Cookies signed with them are still
+    /// accepted and reissued with `cookie_secret`; drop them once every
+    /// such cookie has expired.
+    #[serde(default)]
+    #[validate(custom(function = "validate_cookie_secrets"))]
+    pub previous_cookie_secrets: Vec<String>,
     #[validate(range(min = 60, max = 1440))]
     pub account_verification_ttl_minutes: u64,
+    /// Absolute session lifetime, regardless of activity.
     #[validate(range(min = 1440, max = 43200))]
     pub session_ttl_minutes: u64,
+    #[validate(range(min = 5, max = 10080))]
+    pub session_idle_timeout_minutes: u64,
+    /// How recent the last credential check must be for sensitive actions.
+    #[validate(range(min = 1, max = 60))]
+    pub reauthentication_max_age_minutes: u64,
+    pub session_store: SessionStoreKind,
+    #[validate(length(min = 40))]
+    pub jwt_secret: String,
+    #[validate(range(min = 1, max = 60))]
+    pub access_token_ttl_minutes: u64,
+    #[validate(range(min = 1440, max = 86400))]
+    pub refresh_token_ttl_minutes: u64,
     #[validate(range(min = 1, max = 3))]
     pub oauth_state_ttl_minutes: u64,
     #[validate(range(min = 5, max = 10))]
     pub reset_password_ttl_minutes: u64,
+    /// How long a response is kept for replay to requests with the same `Idempotency-Key`.
+    #[validate(range(min = 1, max = 10080))]
+    pub idempotency_ttl_minutes: u64,
+    #[validate(range(min = 100, max = 10000))]
+    pub health_check_timeout_ms: u64,
+    /// How long readiness reports failing before the server stops accepting connections.
+    #[validate(range(max = 60))]
+    pub shutdown_drain_seconds: u64,
+    /// Lets webhook endpoints point at loopback, private and link-local
+    /// addresses. Only for tests and local development.
+    #[serde(default)]
+    pub allow_private_webhook_targets: bool,
+    #[serde(default)]
+    pub error_format: ErrorFormat,
     pub log_level: LogLevel,
     pub pretty_log: bool,
+    /// Full OTLP/HTTP traces URL, e.g. `http://localhost:4318/v1/traces`.
+    /// Spans are only exported when this is set.
+    #[validate(url)]
+    pub otlp_endpoint: Option<String>,
+    #[validate(length(min = 1))]
+    pub otlp_service_name: String,
+    /// Fraction of new traces that are sampled; propagated parents are honoured.
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub trace_sample_ratio: f64,
+}
+
+/// A signing key is derived from at least 64 bytes of secret.
+fn validate_cookie_secrets(secrets: &[String]) -> Result<(), ValidationError> {
+    match secrets.iter().all(|secret| secret.len() >= 64) {
+        true => Ok(()),
+        false => {
+            Err(ValidationError::new("length").with_message("must be at least 64 bytes".into()))
+        }
+    }
 }
@@ -1,8 +1,8 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sqlx::postgres::{PgConnectOptions, PgSslMode};
 use validator::Validate;
 
-#[derive(Debug, Validate, Deserialize)]
+#[derive(Debug, Validate, Deserialize, Serialize)]
 pub struct DatabaseConfig {
     #[validate(range(min = 1, max = 65535))]
     pub port: u16,
@@ -15,6 +15,8 @@ pub struct DatabaseConfig {
     #[validate(length(min = 1))]
     pub password: String,
     pub ssl: bool,
+    /// Apply pending embedded migrations before the server starts listening.
+    pub migrate_on_startup: bool,
 }
 
 impl DatabaseConfig {
@@ -0,0 +1,153 @@
+use std::{fmt, path::PathBuf};
+
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The config file is missing or a value does not fit its field.
+    Load(config::ConfigError),
+    /// A `*_FILE` variable points at a file that cannot be read.
+    SecretFile {
+        variable: String,
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// Every failed validation rule as `field.path: rule`.
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Load(err) => write!(f, "failed to load configuration: {err}"),
+            ConfigError::SecretFile {
+                variable,
+                path,
+                source,
+            } => write!(
+                f,
+                "failed to read {variable} from {}: {source}",
+                path.display()
+            ),
+            ConfigError::Invalid(errors) => {
+                write!(f, "invalid configuration:")?;
+
+                for error in errors {
+                    write!(f, "\n  - {error}")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Load(err) => Some(err),
+            ConfigError::SecretFile { source, .. } => Some(source),
+            ConfigError::Invalid(_) => None,
+        }
+    }
+}
+
+impl From<config::ConfigError> for ConfigError {
+    fn from(value: config::ConfigError) -> Self {
+        ConfigError::Load(value)
+    }
+}
+
+impl From<ValidationErrors> for ConfigError {
+    fn from(value: ValidationErrors) -> Self {
+        let mut errors = Vec::new();
+        flatten(&value, "", &mut errors);
+        errors.sort();
+
+        ConfigError::Invalid(errors)
+    }
+}
+
+fn flatten(errors: &ValidationErrors, prefix: &str, out: &mut Vec<String>) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{prefix}.{field}")
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                for error in field_errors {
+                    let mut params: Vec<_> = error
+                        .params
+                        .iter()
+                        .filter(|(name, _)| *name != "value")
+                        .map(|(name, value)| format!("{name}={value}"))
+                        .collect();
+                    params.sort();
+
+                    if params.is_empty() {
+                        out.push(format!("{path}: {}", error.code));
+                    } else {
+                        out.push(format!("{path}: {} ({})", error.code, params.join(", ")));
+                    }
+                }
+            }
+            ValidationErrorsKind::Struct(nested) => flatten(nested, &path, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    flatten(nested, &format!("{path}[{index}]"), out);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use validator::Validate;
+
+    use super::*;
+
+    #[derive(Validate)]
+    struct Inner {
+        #[validate(range(min = 1, max = 10))]
+        port: u16,
+    }
+
+    #[derive(Validate)]
+    struct Outer {
+        #[validate(length(min = 1))]
+        host: String,
+        #[validate(nested)]
+        inner: Inner,
+    }
+
+    #[test]
+    fn every_nested_failure_is_reported() {
+        let outer = Outer {
+            host: "".into(),
+            inner: Inner { port: 0 },
+        };
+
+        let ConfigError::Invalid(errors) = ConfigError::from(outer.validate().unwrap_err()) else {
+            panic!("expected validation errors");
+        };
+
+        assert_eq!(
+            errors,
+            ["host: length (min=1)", "inner.port: range (max=10, min=1)"]
+        );
+    }
+
+    #[test]
+    fn display_lists_each_failure() {
+        let error = ConfigError::Invalid(vec!["a: length".into(), "b: range".into()]);
+
+        assert_eq!(
+            error.to_string(),
+            "invalid configuration:\n  - a: length\n  - b: range"
+        );
+    }
+}
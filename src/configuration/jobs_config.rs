@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::jobs::CronSchedule;
+
+#[derive(Debug, Validate, Deserialize, Serialize, Clone)]
+pub struct JobsConfig {
+    /// Jobs run concurrently per instance.
+    #[validate(range(min = 1, max = 64))]
+    pub workers: usize,
+    /// How often an idle worker looks for due jobs.
+    #[validate(range(min = 10, max = 60000))]
+    pub poll_interval_ms: u64,
+    /// A job still running after this is assumed lost with its worker and
+    /// handed to another one.
+    #[validate(range(min = 60, max = 86400))]
+    pub lock_timeout_seconds: u64,
+    /// How long completed and failed jobs are kept.
+    #[validate(range(min = 1, max = 8760))]
+    pub retention_hours: u64,
+    /// When accounts whose verification link expired are deleted, as a
+    /// five-field cron expression in UTC.
+    #[validate(custom(function = "validate_cron"))]
+    pub cleanup_unverified_accounts_schedule: String,
+    /// When expired sessions are deleted from Postgres, as a five-field cron
+    /// expression in UTC.
+    #[validate(custom(function = "validate_cron"))]
+    pub cleanup_expired_sessions_schedule: String,
+}
+
+fn validate_cron(expression: &str) -> Result<(), ValidationError> {
+    CronSchedule::parse(expression)
+        .map(|_| ())
+        .map_err(|err| ValidationError::new("cron").with_message(err.into()))
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Address of the Prometheus scrape listener. Kept apart from the API port so
+/// it can stay on an internal interface.
+#[derive(Debug, Validate, Deserialize, Serialize, Clone)]
+pub struct MetricsConfig {
+    #[validate(length(min = 1))]
+    pub host: String,
+    #[validate(range(min = 1, max = 65535))]
+    pub port: u16,
+}
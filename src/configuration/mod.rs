@@ -1,24 +1,38 @@
-use std::{env, path::Path};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
 
 pub mod app_config;
 pub mod cloudinary_config;
 pub mod database_config;
+pub mod error;
+pub mod jobs_config;
+pub mod metrics_config;
 pub mod oauth2_config;
 pub mod ratelimit_config;
 pub mod redis_config;
+pub mod reload;
 pub mod smtp_config;
 
 use config::Config;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+pub use error::ConfigError;
+
 use crate::configuration::{
     app_config::ApplicationConfig, cloudinary_config::CloudinaryConfig,
-    database_config::DatabaseConfig, oauth2_config::OAuth2Config,
-    ratelimit_config::RateLimitConfig, redis_config::RedisConfig, smtp_config::SmtpConfig,
+    database_config::DatabaseConfig, jobs_config::JobsConfig, metrics_config::MetricsConfig,
+    oauth2_config::OAuth2Config, ratelimit_config::RateLimitConfig, redis_config::RedisConfig,
+    smtp_config::SmtpConfig,
 };
 
-#[derive(Validate, Deserialize, Debug)]
+pub const DEFAULT_CONFIG_DIR: &str = "configs";
+
+const REDACTED: &str = "[redacted]";
+
+#[derive(Validate, Deserialize, Serialize, Debug)]
 pub struct Configuration {
     #[validate(nested)]
     pub application: ApplicationConfig,
@@ -34,35 +48,202 @@ pub struct Configuration {
     pub oauth2: OAuth2Config,
     #[validate(nested)]
     pub ratelimit: RateLimitConfig,
+    #[validate(nested)]
+    pub metrics: MetricsConfig,
+    #[validate(nested)]
+    pub jobs: JobsConfig,
 }
 
 impl Configuration {
-    // #[allow(warnings)] is used here to ignore warnings like missing `Default`.
-    #[allow(warnings)]
-    pub fn new() -> Configuration {
-        let environment = env::var("APPLICATION__ENV").unwrap_or("development".into());
-        let project_root = env!("CARGO_MANIFEST_DIR");
-
-        let file_name = Path::new(project_root)
-            .join("configs")
-            .join(format!("{}.yaml", environment));
-
-        let config = Config::builder()
-            .add_source(config::File::with_name(&file_name.to_str().unwrap()))
-            .add_source(config::Environment::default().separator("__"))
-            .build()
-            .expect("Failed to read configuration.");
-
-        let config = config.try_deserialize::<Configuration>().unwrap();
-
-        if let Err(err) = config.validate() {
-            for (kind, error) in err.errors() {
-                eprintln!("{kind} {error:?}")
-            }
+    /// Loads `<config dir>/<environment>.yaml`, then `__`-separated environment
+    /// variables, then `*_FILE` secrets, and validates the result.
+    pub fn load() -> Result<Configuration, ConfigError> {
+        Self::load_from(&config_dir(), &environment())
+    }
+
+    pub fn load_from(dir: &Path, environment: &str) -> Result<Configuration, ConfigError> {
+        let mut builder = Config::builder()
+            .add_source(config::File::from(dir.join(format!("{environment}.yaml"))))
+            .add_source(config::Environment::default().separator("__"));
+
+        for (key, value) in secret_file_overrides(env::vars())? {
+            builder = builder.set_override(key, value)?;
+        }
+
+        let config = builder.build()?.try_deserialize::<Configuration>()?;
+        config.validate()?;
+
+        Ok(config)
+    }
 
-            panic!("config validation failed")
+    /// The effective configuration with every secret replaced, for `--print-config`.
+    pub fn redacted(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).expect("Configuration is always serializable");
+        redact(&mut value);
+
+        value
+    }
+}
+
+/// `APPLICATION__ENV`, defaulting to `development`.
+pub fn environment() -> String {
+    env::var("APPLICATION__ENV").unwrap_or("development".into())
+}
+
+/// `APPLICATION__CONFIG_DIR`, defaulting to `configs` in the working directory.
+pub fn config_dir() -> PathBuf {
+    env::var_os("APPLICATION__CONFIG_DIR")
+        .map(PathBuf::from)
+        .unwrap_or(PathBuf::from(DEFAULT_CONFIG_DIR))
+}
+
+/// Maps `SECTION__KEY_FILE=/path` to `section.key` set to the file contents,
+/// the convention used by Docker and Kubernetes secrets.
+fn secret_file_overrides(
+    vars: impl Iterator<Item = (String, String)>,
+) -> Result<Vec<(String, String)>, ConfigError> {
+    vars.filter(|(name, _)| name.contains("__"))
+        .filter_map(|(name, path)| {
+            let key = name
+                .strip_suffix("_FILE")?
+                .to_lowercase()
+                .replace("__", ".");
+            Some((name, key, PathBuf::from(path)))
+        })
+        .map(|(variable, key, path)| match fs::read_to_string(&path) {
+            Ok(contents) => Ok((key, contents.trim_end_matches(['\r', '\n']).to_owned())),
+            Err(source) => Err(ConfigError::SecretFile {
+                variable,
+                path,
+                source,
+            }),
+        })
+        .collect()
+}
+
+fn redact(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_secret(key) {
+                    redact_strings(value);
+                } else {
+                    redact(value);
+                }
+            }
         }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn redact_strings(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(_) => *value = serde_json::Value::String(REDACTED.into()),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_strings),
+        _ => {}
+    }
+}
+
+fn is_secret(key: &str) -> bool {
+    key.ends_with("secret")
+        || key.ends_with("secrets")
+        || key.ends_with("password")
+        || key == "api_key"
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn secret_file(contents: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("kicks-secret-{}", Uuid::new_v4()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn var(name: &str, value: &Path) -> (String, String) {
+        (name.to_owned(), value.to_string_lossy().into_owned())
+    }
+
+    #[test]
+    fn secret_files_override_nested_keys() {
+        let path = secret_file("s3cr3t\n");
+
+        let overrides =
+            secret_file_overrides([var("APPLICATION__COOKIE_SECRET_FILE", &path)].into_iter())
+                .unwrap();
+
+        assert_eq!(
+            overrides,
+            [("application.cookie_secret".to_owned(), "s3cr3t".to_owned())]
+        );
+    }
+
+    #[test]
+    fn unrelated_variables_are_ignored() {
+        let path = secret_file("value");
+
+        let overrides = secret_file_overrides(
+            [var("HISTFILE", &path), var("APPLICATION__PORT", &path)].into_iter(),
+        )
+        .unwrap();
+
+        assert!(overrides.is_empty());
+    }
+
+    #[test]
+    fn unreadable_secret_file_names_the_variable() {
+        let missing = env::temp_dir().join(format!("kicks-missing-{}", Uuid::new_v4()));
+
+        let error = secret_file_overrides([var("DATABASE__PASSWORD_FILE", &missing)].into_iter())
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ConfigError::SecretFile { ref variable, .. } if variable == "DATABASE__PASSWORD_FILE"
+        ));
+    }
+
+    #[test]
+    fn missing_config_file_is_an_error() {
+        let dir = env::temp_dir().join(format!("kicks-configs-{}", Uuid::new_v4()));
+
+        let error = Configuration::load_from(&dir, "nowhere").unwrap_err();
+
+        assert!(matches!(error, ConfigError::Load(_)));
+    }
+
+    #[test]
+    fn redact_hides_secrets_at_any_depth() {
+        let mut value = json!({
+            "application": {
+                "cookie_secret": "a",
+                "previous_cookie_secrets": ["b", "c"],
+                "reset_password_path": "/reset",
+            },
+            "database": { "password": "b", "user": "postgres" },
+            "cloudinary": { "api_key": "c" },
+            "redis": { "password": "" },
+        });
+
+        redact(&mut value);
 
-        config
+        assert_eq!(
+            value,
+            json!({
+                "application": {
+                    "cookie_secret": REDACTED,
+                    "previous_cookie_secrets": [REDACTED, REDACTED],
+                    "reset_password_path": "/reset",
+                },
+                "database": { "password": REDACTED, "user": "postgres" },
+                "cloudinary": { "api_key": REDACTED },
+                "redis": { "password": REDACTED },
+            })
+        );
     }
 }
@@ -1,7 +1,7 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-#[derive(Debug, Deserialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Serialize, Validate, Clone)]
 pub struct OAuth2Config {
     #[validate(length(min = 1))]
     pub google_client_id: String,
@@ -1,20 +1,49 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-#[derive(Validate, Debug, Deserialize)]
+/// What a rate limit bucket is keyed by. `User` and `Email` fall back to the
+/// client IP when the request is anonymous or carries no email.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitKey {
+    Ip,
+    User,
+    Email,
+}
+
+#[derive(Validate, Debug, Deserialize, Serialize, Clone)]
+pub struct RateLimitRule {
+    #[validate(range(min = 1, max = 10000))]
+    pub requests: u32,
+    #[validate(range(min = 1, max = 86400))]
+    pub window_seconds: u64,
+    pub key: RateLimitKey,
+}
+
+#[derive(Validate, Debug, Deserialize, Serialize, Clone)]
 pub struct RateLimitConfig {
-    #[validate(range(min = 4, max = 10))]
-    pub sign_up: u32,
-    #[validate(range(min = 4, max = 10))]
-    pub sign_in: u32,
-    #[validate(range(min = 4, max = 10))]
-    pub verify_account: u32,
-    #[validate(range(min = 4, max = 20))]
-    pub get_me: u32,
-    #[validate(range(min = 3, max = 5))]
-    pub forgot_password: u32,
-    #[validate(range(min = 3, max = 5))]
-    pub reset_password: u32,
-    #[validate(range(min = 3, max = 5))]
-    pub logout: u32,
+    #[validate(nested)]
+    pub global: RateLimitRule,
+    #[validate(nested)]
+    pub sign_up: RateLimitRule,
+    #[validate(nested)]
+    pub sign_in: RateLimitRule,
+    #[validate(nested)]
+    pub verify_account: RateLimitRule,
+    #[validate(nested)]
+    pub get_me: RateLimitRule,
+    #[validate(nested)]
+    pub forgot_password: RateLimitRule,
+    #[validate(nested)]
+    pub reset_password: RateLimitRule,
+    #[validate(nested)]
+    pub logout: RateLimitRule,
+    #[validate(nested)]
+    pub refresh_token: RateLimitRule,
+    #[validate(nested)]
+    pub api_keys: RateLimitRule,
+    #[validate(nested)]
+    pub admin: RateLimitRule,
+    #[validate(nested)]
+    pub catalog: RateLimitRule,
 }
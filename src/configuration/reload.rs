@@ -0,0 +1,257 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
+
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::{
+    configuration::{
+        ConfigError, Configuration, app_config::LogLevel, ratelimit_config::RateLimitConfig,
+    },
+    telemetry::LogFilter,
+};
+
+/// The part of the configuration that takes effect without a restart.
+/// Everything else in a reloaded file is validated but ignored.
+#[derive(Debug, Clone)]
+pub struct RuntimeSettings {
+    pub log_level: LogLevel,
+    pub account_verification_ttl_minutes: u64,
+    pub session_ttl_minutes: u64,
+    pub session_idle_timeout_minutes: u64,
+    pub reauthentication_max_age_minutes: u64,
+    pub access_token_ttl_minutes: u64,
+    pub refresh_token_ttl_minutes: u64,
+    pub oauth_state_ttl_minutes: u64,
+    pub reset_password_ttl_minutes: u64,
+    pub idempotency_ttl_minutes: u64,
+    pub ratelimit: RateLimitConfig,
+}
+
+impl From<&Configuration> for RuntimeSettings {
+    fn from(config: &Configuration) -> Self {
+        let app = &config.application;
+
+        Self {
+            log_level: app.log_level.clone(),
+            account_verification_ttl_minutes: app.account_verification_ttl_minutes,
+            session_ttl_minutes: app.session_ttl_minutes,
+            session_idle_timeout_minutes: app.session_idle_timeout_minutes,
+            reauthentication_max_age_minutes: app.reauthentication_max_age_minutes,
+            access_token_ttl_minutes: app.access_token_ttl_minutes,
+            refresh_token_ttl_minutes: app.refresh_token_ttl_minutes,
+            oauth_state_ttl_minutes: app.oauth_state_ttl_minutes,
+            reset_password_ttl_minutes: app.reset_password_ttl_minutes,
+            idempotency_ttl_minutes: app.idempotency_ttl_minutes,
+            ratelimit: config.ratelimit.clone(),
+        }
+    }
+}
+
+/// Shared view of the current [`RuntimeSettings`]. Readers take a snapshot
+/// per use, so a reload never changes values in the middle of a request.
+#[derive(Debug, Clone)]
+pub struct SettingsHandle(Arc<RwLock<Arc<RuntimeSettings>>>);
+
+impl SettingsHandle {
+    pub fn new(settings: RuntimeSettings) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(settings))))
+    }
+
+    pub fn current(&self) -> Arc<RuntimeSettings> {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn replace(&self, settings: RuntimeSettings) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(settings);
+    }
+}
+
+/// Re-reads the configuration on SIGHUP and publishes it to a [`SettingsHandle`].
+pub struct SettingsReloader {
+    settings: SettingsHandle,
+    log_filter: LogFilter,
+    config_dir: PathBuf,
+    environment: String,
+}
+
+impl SettingsReloader {
+    pub fn new(
+        settings: SettingsHandle,
+        log_filter: LogFilter,
+        config_dir: PathBuf,
+        environment: String,
+    ) -> Self {
+        Self {
+            settings,
+            log_filter,
+            config_dir,
+            environment,
+        }
+    }
+
+    /// Loads and validates the configuration from scratch; on any error the
+    /// running settings are left untouched.
+    pub fn reload(&self) -> Result<(), ConfigError> {
+        let config = Configuration::load_from(&self.config_dir, &self.environment)?;
+        let settings = RuntimeSettings::from(&config);
+
+        if let Err(err) = self.log_filter.set_level(&settings.log_level) {
+            warn!(%err, "Failed to change log level");
+        }
+
+        self.settings.replace(settings);
+
+        Ok(())
+    }
+
+    pub async fn run(self, token: CancellationToken) {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{SignalKind, signal};
+
+            let mut hangup =
+                signal(SignalKind::hangup()).expect("failed to install SIGHUP handler");
+
+            loop {
+                tokio::select! {
+                    _ = hangup.recv() => match self.reload() {
+                        Ok(()) => info!("Reloaded settings"),
+                        Err(err) => error!(%err, "Rejected settings reload, keeping current settings"),
+                    },
+                    _ = token.cancelled() => break,
+                }
+            }
+        }
+
+        #[cfg(not(unix))]
+        token.cancelled().await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::Path};
+
+    use config::Config;
+    use serde_json::{Value, json};
+    use uuid::Uuid;
+
+    use super::*;
+
+    const ENVIRONMENT: &str = "reload";
+
+    /// test-ci plus the sections CI provides through the environment, so the
+    /// file validates on its own. JSON is valid YAML, so it is written as-is.
+    fn base_config() -> Value {
+        let mut config: Value = Config::builder()
+            .add_source(config::File::with_name("configs/test-ci.yaml"))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap();
+
+        let provided = json!({
+            "smtp": {
+                "host": "localhost",
+                "port": 1025,
+                "user": "user@example.com",
+                "password": "password",
+                "from": "noreply@example.com",
+            },
+            "cloudinary": {
+                "api_key": "key",
+                "secret": "secret",
+                "cloud_name": "cloud",
+                "folder": "kicks",
+            },
+            "oauth2": {
+                "google_client_id": "id",
+                "google_client_secret": "secret",
+                "facebook_client_id": "id",
+                "facebook_client_secret": "secret",
+            },
+        });
+
+        for (section, values) in provided.as_object().unwrap() {
+            for (key, value) in values.as_object().unwrap() {
+                config[section][key] = value.clone();
+            }
+        }
+
+        config
+    }
+
+    fn write(dir: &Path, config: &Value) {
+        fs::write(dir.join(format!("{ENVIRONMENT}.yaml")), config.to_string()).unwrap();
+    }
+
+    fn reloader() -> (SettingsReloader, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("kicksapi-reload-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut config = base_config();
+        config["ratelimit"]["sign_in"]["requests"] = 5.into();
+        write(&dir, &config);
+
+        let initial = Configuration::load_from(&dir, ENVIRONMENT).unwrap();
+        let reloader = SettingsReloader::new(
+            SettingsHandle::new(RuntimeSettings::from(&initial)),
+            LogFilter::disabled(),
+            dir.clone(),
+            ENVIRONMENT.into(),
+        );
+
+        (reloader, dir)
+    }
+
+    #[test]
+    fn valid_reload_is_published() {
+        let (reloader, dir) = reloader();
+        let before = reloader.settings.current();
+
+        let mut config = base_config();
+        config["ratelimit"]["sign_in"]["requests"] = 50.into();
+        config["application"]["access_token_ttl_minutes"] = 30.into();
+        write(&dir, &config);
+
+        reloader.reload().unwrap();
+
+        let after = reloader.settings.current();
+        assert_eq!(before.ratelimit.sign_in.requests, 5);
+        assert_eq!(after.ratelimit.sign_in.requests, 50);
+        assert_eq!(after.access_token_ttl_minutes, 30);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_reload_keeps_current_settings() {
+        let (reloader, dir) = reloader();
+
+        let mut config = base_config();
+        config["ratelimit"]["sign_in"]["requests"] = 0.into();
+        write(&dir, &config);
+
+        let err = reloader.reload().unwrap_err();
+
+        assert!(matches!(err, ConfigError::Invalid(_)));
+        assert_eq!(reloader.settings.current().ratelimit.sign_in.requests, 5);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn unreadable_reload_keeps_current_settings() {
+        let (reloader, dir) = reloader();
+
+        fs::write(dir.join(format!("{ENVIRONMENT}.yaml")), "application: [").unwrap();
+
+        assert!(matches!(reloader.reload(), Err(ConfigError::Load(_))));
+        assert_eq!(reloader.settings.current().ratelimit.sign_in.requests, 5);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
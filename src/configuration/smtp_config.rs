@@ -1,8 +1,8 @@
 use lettre::transport::smtp::authentication::Credentials;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-#[derive(Debug, Validate, Deserialize)]
+#[derive(Debug, Validate, Deserialize, Serialize)]
 pub struct SmtpConfig {
     #[validate(range(min = 1, max = 65535))]
     pub port: u16,
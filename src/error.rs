@@ -1,11 +1,21 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
-use axum::{extract::rejection::JsonRejection, http::StatusCode, response::IntoResponse};
+use axum::{
+    Json,
+    extract::rejection::JsonRejection,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
 use derive_more::From;
 
 use redis::RedisError;
 use serde::Serialize;
-use serde_json::json;
+use utoipa::ToSchema;
+
+use crate::i18n::{self, Locale, Message};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -13,7 +23,18 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     Unauthorized,
     Forbidden,
+    ReauthenticationRequired,
+    NotFound(String),
+    TooManyRequests,
+    PayloadTooLarge,
     Conflict(String),
+    InvalidCredentials,
+    InvalidToken,
+    InvalidOAuthState,
+    EmailNotVerified,
+    InvalidIdempotencyKey,
+    IdempotencyKeyReused,
+    IdempotentRequestInProgress,
     Internal(String),
     #[from(serde_json::Error)]
     SerdeJson,
@@ -22,6 +43,8 @@ pub enum Error {
     #[from]
     Database(sqlx::Error),
     #[from]
+    Migrate(sqlx::migrate::MigrateError),
+    #[from]
     Redis(RedisError),
     #[from]
     Smtp(lettre::error::Error),
@@ -30,78 +53,456 @@ pub enum Error {
     #[from]
     Reqwest(reqwest::Error),
     #[from]
-    DomainValidationError(Vec<String>),
-    ValidationErrors(HashMap<String, Vec<String>>),
+    DomainValidationError(Vec<Message>),
+    ValidationErrors(HashMap<String, Vec<Message>>),
+}
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Stable, machine-readable identifier of an error kind. Clients should
+/// branch on this rather than on `detail`, which is meant for humans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    Unauthorized,
+    Forbidden,
+    ReauthenticationRequired,
+    NotFound,
+    RateLimited,
+    PayloadTooLarge,
+    Conflict,
+    InvalidCredentials,
+    InvalidToken,
+    InvalidOauthState,
+    EmailNotVerified,
+    InvalidIdempotencyKey,
+    IdempotencyKeyReused,
+    IdempotentRequestInProgress,
+    InvalidBody,
+    UnsupportedMediaType,
+    ValidationFailed,
+    UpstreamUnavailable,
+    InternalError,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::Forbidden => "forbidden",
+            ErrorCode::ReauthenticationRequired => "reauthentication_required",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::RateLimited => "rate_limited",
+            ErrorCode::PayloadTooLarge => "payload_too_large",
+            ErrorCode::Conflict => "conflict",
+            ErrorCode::InvalidCredentials => "invalid_credentials",
+            ErrorCode::InvalidToken => "invalid_token",
+            ErrorCode::InvalidOauthState => "invalid_oauth_state",
+            ErrorCode::EmailNotVerified => "email_not_verified",
+            ErrorCode::InvalidIdempotencyKey => "invalid_idempotency_key",
+            ErrorCode::IdempotencyKeyReused => "idempotency_key_reused",
+            ErrorCode::IdempotentRequestInProgress => "idempotent_request_in_progress",
+            ErrorCode::InvalidBody => "invalid_body",
+            ErrorCode::UnsupportedMediaType => "unsupported_media_type",
+            ErrorCode::ValidationFailed => "validation_failed",
+            ErrorCode::UpstreamUnavailable => "upstream_unavailable",
+            ErrorCode::InternalError => "internal_error",
+        }
+    }
+
+    fn message_key(&self) -> String {
+        format!("error.{}", self.as_str())
+    }
+}
+
+/// One validation failure; `field` is absent when it isn't tied to a field.
+/// `code` and `params` let clients render their own text instead of `detail`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FieldError {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    #[schema(example = "value.too_short")]
+    pub code: &'static str,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[schema(value_type = Object, example = json!({ "min": "8" }))]
+    pub params: BTreeMap<&'static str, String>,
+    pub detail: String,
+}
+
+impl FieldError {
+    fn new(field: Option<String>, message: Message) -> Self {
+        Self {
+            field,
+            detail: message.translate(Locale::default()),
+            code: message.key,
+            params: message.params,
+        }
+    }
+}
+
+/// RFC 9457 problem details, served as `application/problem+json`.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    #[schema(example = "about:blank")]
+    pub kind: String,
+    #[schema(example = "Bad Request")]
+    pub title: String,
+    #[schema(example = 400)]
+    pub status: u16,
+    pub code: ErrorCode,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl Problem {
+    /// A problem whose detail is the catalog text for `code`.
+    fn new(status: StatusCode, code: ErrorCode) -> Self {
+        Self {
+            kind: "about:blank".into(),
+            title: status.canonical_reason().unwrap_or_default().into(),
+            status: status.as_u16(),
+            code,
+            detail: i18n::translate(Locale::default(), &code.message_key(), &BTreeMap::new()),
+            errors: Vec::new(),
+            request_id: None,
+        }
+    }
+
+    fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = detail.into();
+        self
+    }
+
+    fn with_errors(mut self, errors: Vec<FieldError>) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    /// Translates the messages, which are rendered in English up to here.
+    /// Specific English details (e.g. which record was not found) give way
+    /// to the generic text for the error code in other languages.
+    pub fn localize(&mut self, locale: Locale) {
+        for error in &mut self.errors {
+            error.detail = i18n::translate(locale, error.code, &error.params);
+        }
+
+        if locale != Locale::default() {
+            self.detail = i18n::translate(locale, &self.code.message_key(), &BTreeMap::new());
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    /// The status the v1 error shape used for this kind of error.
+    pub fn legacy_status(&self) -> StatusCode {
+        match self.code {
+            ErrorCode::Conflict
+            | ErrorCode::InvalidCredentials
+            | ErrorCode::InvalidToken
+            | ErrorCode::InvalidOauthState
+            | ErrorCode::EmailNotVerified
+            | ErrorCode::InvalidBody
+            | ErrorCode::UnsupportedMediaType
+            | ErrorCode::ValidationFailed => StatusCode::BAD_REQUEST,
+            ErrorCode::UpstreamUnavailable => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => self.status(),
+        }
+    }
+
+    /// Renders the v1 `{"error": ...}` / `{"errors": {...}}` shape.
+    pub fn into_legacy_response(self) -> Response {
+        let status = self.legacy_status();
+
+        if self.code != ErrorCode::ValidationFailed {
+            return (status, Json(ErrorResponse { error: self.detail })).into_response();
+        }
+
+        if self.errors.iter().all(|e| e.field.is_none()) {
+            let error = self
+                .errors
+                .into_iter()
+                .map(|e| e.detail)
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            return (status, Json(ErrorResponse { error })).into_response();
+        }
+
+        let mut errors: HashMap<String, Vec<String>> = HashMap::new();
+        for error in self.errors {
+            errors
+                .entry(error.field.unwrap_or_default())
+                .or_default()
+                .push(error.detail);
+        }
+
+        (status, Json(ValidationErrorResponse { errors })).into_response()
+    }
+}
+
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        let mut response = (self.status(), Json(self)).into_response();
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+
+        response
+    }
 }
 
+/// The v1 error body, still served when `error_format` is `legacy`.
 #[derive(Serialize)]
 pub struct ErrorResponse {
     pub error: String,
 }
 
-impl IntoResponse for Error {
-    fn into_response(self) -> axum::response::Response {
-        let (status_code, message, error) = match self {
-            Error::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized".to_owned(), None),
-            Error::Forbidden => (StatusCode::FORBIDDEN, "Forbidden".to_owned(), None),
-            Error::Conflict(message) => (StatusCode::BAD_REQUEST, message, None),
-            Error::Internal(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_owned(),
-                Some(self),
-            ),
-            Error::Io(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_owned(),
-                Some(self),
-            ),
-            Error::SerdeJson => (
-                StatusCode::BAD_REQUEST,
-                String::from("Invalid request"),
-                Some(self),
+/// The v1 validation error body, still served when `error_format` is `legacy`.
+#[derive(Serialize)]
+pub struct ValidationErrorResponse {
+    pub errors: HashMap<String, Vec<String>>,
+}
+
+impl Error {
+    pub fn problem(&self) -> Problem {
+        match self {
+            Error::Unauthorized => Problem::new(StatusCode::UNAUTHORIZED, ErrorCode::Unauthorized),
+            Error::Forbidden => Problem::new(StatusCode::FORBIDDEN, ErrorCode::Forbidden),
+            Error::ReauthenticationRequired => {
+                Problem::new(StatusCode::FORBIDDEN, ErrorCode::ReauthenticationRequired)
+            }
+            Error::NotFound(message) => {
+                Problem::new(StatusCode::NOT_FOUND, ErrorCode::NotFound).with_detail(message)
+            }
+            Error::TooManyRequests => {
+                Problem::new(StatusCode::TOO_MANY_REQUESTS, ErrorCode::RateLimited)
+            }
+            Error::PayloadTooLarge => {
+                Problem::new(StatusCode::PAYLOAD_TOO_LARGE, ErrorCode::PayloadTooLarge)
+            }
+            Error::Conflict(message) => {
+                Problem::new(StatusCode::CONFLICT, ErrorCode::Conflict).with_detail(message)
+            }
+            Error::InvalidCredentials => {
+                Problem::new(StatusCode::UNAUTHORIZED, ErrorCode::InvalidCredentials)
+            }
+            Error::InvalidToken => Problem::new(StatusCode::BAD_REQUEST, ErrorCode::InvalidToken),
+            Error::InvalidOAuthState => {
+                Problem::new(StatusCode::BAD_REQUEST, ErrorCode::InvalidOauthState)
+            }
+            Error::EmailNotVerified => {
+                Problem::new(StatusCode::FORBIDDEN, ErrorCode::EmailNotVerified)
+            }
+            Error::InvalidIdempotencyKey => {
+                Problem::new(StatusCode::BAD_REQUEST, ErrorCode::InvalidIdempotencyKey)
+            }
+            Error::IdempotencyKeyReused => Problem::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ErrorCode::IdempotencyKeyReused,
             ),
+            Error::IdempotentRequestInProgress => {
+                Problem::new(StatusCode::CONFLICT, ErrorCode::IdempotentRequestInProgress)
+            }
             Error::JsonRejection(rejection) => {
-                (StatusCode::BAD_REQUEST, rejection.body_text(), None)
+                let code = match rejection.status() {
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE => ErrorCode::UnsupportedMediaType,
+                    StatusCode::PAYLOAD_TOO_LARGE => ErrorCode::PayloadTooLarge,
+                    _ => ErrorCode::InvalidBody,
+                };
+
+                Problem::new(rejection.status(), code).with_detail(rejection.body_text())
             }
-            Error::Database(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_owned(),
-                Some(self),
-            ),
-            Error::Redis(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_owned(),
-                Some(self),
-            ),
-            Error::Smtp(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_owned(),
-                Some(self),
-            ),
-            Error::Reqwest(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_owned(),
-                Some(self),
-            ),
-            Error::DomainValidationError(errors) => {
-                (StatusCode::BAD_REQUEST, errors.join("\n"), None)
+            Error::DomainValidationError(messages) => {
+                Problem::new(StatusCode::BAD_REQUEST, ErrorCode::ValidationFailed).with_errors(
+                    messages
+                        .iter()
+                        .map(|message| FieldError::new(None, message.clone()))
+                        .collect(),
+                )
             }
             Error::ValidationErrors(errors) => {
-                return (
-                    StatusCode::BAD_REQUEST,
-                    axum::Json(json!({"errors": errors})),
+                let mut fields: Vec<_> = errors.iter().collect();
+                fields.sort_by_key(|(field, _)| *field);
+
+                Problem::new(StatusCode::BAD_REQUEST, ErrorCode::ValidationFailed).with_errors(
+                    fields
+                        .into_iter()
+                        .flat_map(|(field, messages)| {
+                            messages.iter().map(|message| {
+                                FieldError::new(Some(field.clone()), message.clone())
+                            })
+                        })
+                        .collect(),
                 )
-                    .into_response();
             }
-        };
+            Error::Reqwest(_) => {
+                Problem::new(StatusCode::BAD_GATEWAY, ErrorCode::UpstreamUnavailable)
+            }
+            Error::Internal(_)
+            | Error::Io(_)
+            | Error::SerdeJson
+            | Error::Database(_)
+            | Error::Migrate(_)
+            | Error::Redis(_)
+            | Error::Smtp(_) => {
+                Problem::new(StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::InternalError)
+            }
+        }
+    }
 
-        let error_response = ErrorResponse { error: message };
+    /// Errors that are bugs or outages rather than the caller's fault.
+    fn is_unexpected(&self) -> bool {
+        matches!(
+            self,
+            Error::Internal(_)
+                | Error::Io(_)
+                | Error::SerdeJson
+                | Error::Database(_)
+                | Error::Migrate(_)
+                | Error::Redis(_)
+                | Error::Smtp(_)
+                | Error::Reqwest(_)
+        )
+    }
+}
 
-        let mut response = (status_code, axum::Json(error_response)).into_response();
-        if let Some(err) = error {
-            response.extensions_mut().insert(Arc::new(err));
+/// Renders problem details without a request ID; the `problem_details`
+/// middleware fills it in and applies the configured error format.
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let problem = self.problem();
+
+        let mut response = problem.clone().into_response();
+        response.extensions_mut().insert(problem);
+        if self.is_unexpected() {
+            response.extensions_mut().insert(Arc::new(self));
         }
 
         response
     }
 }
+
+#[cfg(test)]
+mod test {
+    use axum::body::to_bytes;
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    #[test]
+    fn conflict_is_409() {
+        let problem = Error::Conflict("Record already exists".into()).problem();
+
+        assert_eq!(problem.status(), StatusCode::CONFLICT);
+        assert_eq!(problem.code, ErrorCode::Conflict);
+        assert_eq!(problem.legacy_status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn validation_errors_are_listed_per_field() {
+        let problem = Error::ValidationErrors(HashMap::from([
+            (
+                "password".into(),
+                vec![Message::new("value.too_short").with("min", 8)],
+            ),
+            (
+                "email".into(),
+                vec![Message::new("email.invalid"), Message::new("value.empty")],
+            ),
+        ]))
+        .problem();
+
+        assert_eq!(problem.code, ErrorCode::ValidationFailed);
+        assert_eq!(
+            serde_json::to_value(&problem.errors).unwrap(),
+            json!([
+                { "field": "email", "code": "email.invalid", "detail": "Invalid email address" },
+                { "field": "email", "code": "value.empty", "detail": "Value cannot be empty." },
+                {
+                    "field": "password",
+                    "code": "value.too_short",
+                    "params": { "min": "8" },
+                    "detail": "Value must be at least 8 characters.",
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn domain_validation_messages_are_not_joined() {
+        let problem = Error::DomainValidationError(vec![
+            Message::new("value.empty"),
+            Message::new("value.too_short").with("min", 8),
+        ])
+        .problem();
+
+        assert_eq!(problem.errors.len(), 2);
+        assert!(problem.errors.iter().all(|e| e.field.is_none()));
+    }
+
+    #[test]
+    fn problem_is_localized() {
+        let mut problem = Error::ValidationErrors(HashMap::from([(
+            "password".into(),
+            vec![Message::new("value.too_long").with("max", 40)],
+        )]))
+        .problem();
+
+        problem.localize(Locale::De);
+
+        assert_eq!(problem.detail, "Validierung fehlgeschlagen");
+        assert_eq!(
+            problem.errors[0].detail,
+            "Der Wert darf höchstens 40 Zeichen lang sein."
+        );
+        assert_eq!(problem.errors[0].code, "value.too_long");
+    }
+
+    #[test]
+    fn english_keeps_specific_details() {
+        let mut problem = Error::NotFound("API key not found".into()).problem();
+
+        problem.localize(Locale::En);
+        assert_eq!(problem.detail, "API key not found");
+
+        problem.localize(Locale::De);
+        assert_eq!(problem.detail, "Nicht gefunden");
+    }
+
+    #[tokio::test]
+    async fn response_is_problem_json_and_hides_internal_details() {
+        let resp = Error::Internal("db password is hunter2".into()).into_response();
+
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(resp.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+        assert!(resp.extensions().get::<Arc<Error>>().is_some());
+
+        let body: Value =
+            serde_json::from_slice(&to_bytes(resp.into_body(), usize::MAX).await.unwrap()).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "type": "about:blank",
+                "title": "Internal Server Error",
+                "status": 500,
+                "code": "internal_error",
+                "detail": "Internal server error",
+            })
+        );
+    }
+
+    #[test]
+    fn expected_errors_are_not_logged() {
+        let resp = Error::InvalidCredentials.into_response();
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert!(resp.extensions().get::<Arc<Error>>().is_none());
+        assert!(resp.extensions().get::<Problem>().is_some());
+    }
+}
@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    Result,
+    events::{Event, Subscriber},
+};
+
+/// Logs every event it receives under the `audit` target.
+pub struct AuditLog;
+
+#[async_trait]
+impl<E: Event> Subscriber<E> for AuditLog {
+    async fn handle(&self, id: Uuid, event: E) -> Result<()> {
+        let payload = serde_json::to_string(&event).unwrap_or_default();
+        info!(target: "audit", event = E::NAME, %id, %payload, "Domain event");
+
+        Ok(())
+    }
+}
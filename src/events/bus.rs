@@ -0,0 +1,296 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, RwLock},
+};
+
+use serde_json::Value;
+use sqlx::PgConnection;
+use tracing::{Instrument, error, info_span, warn};
+use uuid::Uuid;
+
+use crate::{
+    Result,
+    events::{DeliverEvent, Delivery, Event, Subscriber},
+    jobs::JobQueue,
+};
+
+type Handler =
+    Arc<dyn Fn(Uuid, Value) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> + Send + Sync>;
+
+#[derive(Clone)]
+struct Registration {
+    subscriber: &'static str,
+    delivery: Delivery,
+    handler: Handler,
+}
+
+/// Hands published events to their subscribers in this process. Features
+/// publish through a clone; subscribers are registered once in
+/// [`Application::build`](crate::app::Application::build).
+#[derive(Clone)]
+pub struct EventBus {
+    jobs: JobQueue,
+    subscribers: Arc<RwLock<HashMap<&'static str, Vec<Registration>>>>,
+}
+
+impl EventBus {
+    pub fn new(jobs: JobQueue) -> Self {
+        Self {
+            jobs,
+            subscribers: Arc::default(),
+        }
+    }
+
+    /// `name` identifies the subscriber in queued deliveries, so it must be
+    /// unique per event and stable across deploys.
+    pub fn subscribe<E: Event>(
+        &self,
+        name: &'static str,
+        delivery: Delivery,
+        subscriber: impl Subscriber<E>,
+    ) -> &Self {
+        let subscriber = Arc::new(subscriber);
+        let handler: Handler = Arc::new(move |id, payload| {
+            let subscriber = subscriber.clone();
+            Box::pin(async move {
+                subscriber
+                    .handle(id, serde_json::from_value(payload)?)
+                    .await
+            })
+        });
+
+        let mut subscribers = self.subscribers.write().expect("subscribers lock poisoned");
+        let registrations = subscribers.entry(E::NAME).or_default();
+        assert!(
+            registrations.iter().all(|r| r.subscriber != name),
+            "{name} is already subscribed to {}",
+            E::NAME
+        );
+        registrations.push(Registration {
+            subscriber: name,
+            delivery,
+            handler,
+        });
+
+        self
+    }
+
+    /// Never fails the caller: the change the event describes is already
+    /// stored, so delivery problems are logged instead. Events with
+    /// persistent subscribers should go through [`publish_in`](Self::publish_in).
+    pub async fn publish<E: Event>(&self, event: &E) {
+        let persistent = match self.dispatch(event) {
+            Ok(persistent) => persistent,
+            Err(err) => {
+                error!(?err, event = E::NAME, "Failed to serialize event");
+                return;
+            }
+        };
+
+        for delivery in persistent {
+            if let Err(err) = self.jobs.enqueue(&delivery).await {
+                error!(
+                    ?err,
+                    event = E::NAME,
+                    subscriber = delivery.subscriber,
+                    "Failed to queue event delivery"
+                );
+            }
+        }
+    }
+
+    /// Queues persistent deliveries on `conn`, the publisher's transaction,
+    /// so they're committed with the change the event describes or not at
+    /// all. Immediate subscribers are started right away, so call this last
+    /// before committing.
+    pub async fn publish_in<E: Event>(&self, conn: &mut PgConnection, event: &E) -> Result<()> {
+        for delivery in self.dispatch(event)? {
+            JobQueue::enqueue_in(conn, &delivery).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Starts the immediate subscribers and returns the deliveries to queue
+    /// for the persistent ones.
+    fn dispatch<E: Event>(&self, event: &E) -> Result<Vec<DeliverEvent>> {
+        let payload = serde_json::to_value(event)?;
+
+        let id = Uuid::new_v4();
+        let mut persistent = Vec::new();
+        for registration in self.registrations(E::NAME) {
+            match registration.delivery {
+                Delivery::Immediate => {
+                    let span = info_span!(
+                        "event",
+                        name = E::NAME,
+                        subscriber = registration.subscriber
+                    );
+                    let handle = (registration.handler)(id, payload.clone());
+                    tokio::spawn(
+                        async move {
+                            if let Err(err) = handle.await {
+                                error!(?err, "Event subscriber failed");
+                            }
+                        }
+                        .instrument(span),
+                    );
+                }
+                Delivery::Persistent => persistent.push(DeliverEvent {
+                    id,
+                    event: E::NAME.into(),
+                    subscriber: registration.subscriber.into(),
+                    payload: payload.clone(),
+                }),
+            }
+        }
+
+        Ok(persistent)
+    }
+
+    /// Runs a queued delivery. Subscribers removed since it was queued are
+    /// skipped rather than retried.
+    pub async fn deliver(
+        &self,
+        id: Uuid,
+        event: &str,
+        subscriber: &str,
+        payload: Value,
+    ) -> Result<()> {
+        let handler = self
+            .registrations(event)
+            .into_iter()
+            .find(|r| r.subscriber == subscriber)
+            .map(|r| r.handler);
+
+        match handler {
+            Some(handler) => handler(id, payload).await,
+            None => {
+                warn!(
+                    event,
+                    subscriber, "No subscriber for queued event, skipping"
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// A snapshot, so the lock isn't held while handlers run.
+    fn registrations(&self, event: &str) -> Vec<Registration> {
+        let subscribers = self.subscribers.read().expect("subscribers lock poisoned");
+
+        subscribers.get(event).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use serde::{Deserialize, Serialize};
+    use sqlx::PgPool;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Pinged {
+        n: u32,
+    }
+
+    impl Event for Pinged {
+        const NAME: &'static str = "test.pinged";
+    }
+
+    struct Forward(mpsc::UnboundedSender<u32>);
+
+    #[async_trait]
+    impl Subscriber<Pinged> for Forward {
+        async fn handle(&self, _id: Uuid, event: Pinged) -> Result<()> {
+            self.0.send(event.n).unwrap();
+            Ok(())
+        }
+    }
+
+    fn bus() -> EventBus {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        EventBus::new(JobQueue::new(pool))
+    }
+
+    #[tokio::test]
+    async fn immediate_subscribers_receive_published_events() {
+        let (first_tx, mut first) = mpsc::unbounded_channel();
+        let (second_tx, mut second) = mpsc::unbounded_channel();
+        let bus = bus();
+        bus.subscribe("first", Delivery::Immediate, Forward(first_tx))
+            .subscribe("second", Delivery::Immediate, Forward(second_tx));
+
+        bus.publish(&Pinged { n: 7 }).await;
+
+        let timeout = Duration::from_secs(1);
+        assert_eq!(
+            tokio::time::timeout(timeout, first.recv()).await,
+            Ok(Some(7))
+        );
+        assert_eq!(
+            tokio::time::timeout(timeout, second.recv()).await,
+            Ok(Some(7))
+        );
+    }
+
+    #[tokio::test]
+    async fn deliver_runs_only_the_named_subscriber() {
+        let (first_tx, mut first) = mpsc::unbounded_channel();
+        let (second_tx, mut second) = mpsc::unbounded_channel();
+        let bus = bus();
+        bus.subscribe("first", Delivery::Persistent, Forward(first_tx))
+            .subscribe("second", Delivery::Persistent, Forward(second_tx));
+
+        bus.deliver(
+            Uuid::new_v4(),
+            Pinged::NAME,
+            "second",
+            serde_json::json!({ "n": 3 }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(second.try_recv(), Ok(3));
+        assert!(first.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn deliver_skips_unknown_subscribers_and_rejects_bad_payloads() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let bus = bus();
+        bus.subscribe("forward", Delivery::Persistent, Forward(tx));
+
+        assert!(
+            bus.deliver(Uuid::new_v4(), Pinged::NAME, "gone", Value::Null)
+                .await
+                .is_ok()
+        );
+        assert!(
+            bus.deliver(Uuid::new_v4(), "test.unknown", "forward", Value::Null)
+                .await
+                .is_ok()
+        );
+        assert!(
+            bus.deliver(Uuid::new_v4(), Pinged::NAME, "forward", Value::Null)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "already subscribed")]
+    async fn subscriber_names_are_unique_per_event() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let bus = bus();
+        bus.subscribe("forward", Delivery::Immediate, Forward(tx.clone()))
+            .subscribe("forward", Delivery::Persistent, Forward(tx));
+    }
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{Result, app::AppState, jobs::Job};
+
+/// One event for one [`Persistent`](super::Delivery::Persistent) subscriber.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeliverEvent {
+    /// The published event's id, shared by its deliveries to every subscriber.
+    pub id: Uuid,
+    pub event: String,
+    pub subscriber: String,
+    pub payload: Value,
+}
+
+#[async_trait]
+impl Job for DeliverEvent {
+    const KIND: &'static str = "events.deliver";
+
+    async fn run(self, state: &AppState) -> Result<()> {
+        state
+            .events
+            .deliver(self.id, &self.event, &self.subscriber, self.payload)
+            .await
+    }
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use serde::{Serialize, de::DeserializeOwned};
+use uuid::Uuid;
+
+use crate::Result;
+
+mod audit;
+mod bus;
+mod delivery;
+
+pub use audit::AuditLog;
+pub use bus::EventBus;
+pub use delivery::DeliverEvent;
+
+/// Something that happened in a feature, published on the [`EventBus`]
+/// after the change it describes has been stored.
+pub trait Event: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Stored with persistent deliveries; renaming an event orphans them.
+    const NAME: &'static str;
+}
+
+#[async_trait]
+pub trait Subscriber<E: Event>: Send + Sync + 'static {
+    /// `id` is assigned when the event is published and stays the same
+    /// across retries of a persistent delivery, so subscribers can use it to
+    /// deduplicate.
+    async fn handle(&self, id: Uuid, event: E) -> Result<()>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// Handled on a task of its own right after publishing; a failure is
+    /// logged and the event is lost.
+    Immediate,
+    /// Queued as a [`DeliverEvent`] job, so the subscriber is retried until
+    /// it succeeds or runs out of attempts. It may see an event more than once.
+    /// Published with [`EventBus::publish_in`], the job is stored in the same
+    /// transaction as the change.
+    Persistent,
+}
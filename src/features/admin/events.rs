@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::events::Event;
+
+/// Published when a ban is put in place, after the user's sessions are revoked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserBanned {
+    pub user_id: Uuid,
+    pub email: String,
+}
+
+impl Event for UserBanned {
+    const NAME: &'static str = "admin.user_banned";
+}
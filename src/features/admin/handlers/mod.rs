@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+use crate::features::{
+    admin::listing::UserSummary,
+    auth::{UserGender, UserRole},
+};
+
+mod users_handler;
+
+pub use users_handler::list_users_v1;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(users_handler::list_users_v1),
+    tags((name = "admin", description = "Back-office endpoints for admin accounts")),
+)]
+pub struct AdminApi;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct AdminUserResponse {
+    pub id: Uuid,
+    pub email: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub role: UserRole,
+    pub gender: Option<UserGender>,
+    pub is_verified: bool,
+    pub is_banned: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<UserSummary> for AdminUserResponse {
+    fn from(value: UserSummary) -> Self {
+        Self {
+            id: value.id,
+            email: value.email,
+            first_name: value.first_name,
+            last_name: value.last_name,
+            role: value.role,
+            gender: value.gender,
+            is_verified: value.is_verified,
+            is_banned: value.is_banned,
+            created_at: value.created_at,
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use axum::{Extension, Json, extract::State, http::StatusCode, response::IntoResponse};
+
+use crate::{
+    ApiResponse, Error, Problem, Result,
+    app::AppState,
+    features::{
+        admin::{handlers::AdminUserResponse, listing::UserListing},
+        shared::{AppUser, Credential, Page, PageQuery, ensure_admin, ensure_interactive},
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/users",
+    tag = "admin",
+    security(("session_cookie" = []), ("bearer" = [])),
+    params(
+        ("limit" = Option<u32>, Query, description = "Page size, 1 to 100; defaults to 20"),
+        ("cursor" = Option<String>, Query, description = "`next_cursor` from the previous page, with the same sort and filters"),
+        ("sort" = Option<String>, Query, description = "`created_at` or `email`, prefixed with `-` for descending; defaults to `-created_at`"),
+        ("role" = Option<String>, Query, description = "`admin` or `regular`"),
+        ("is_verified" = Option<bool>, Query),
+        ("is_banned" = Option<bool>, Query),
+        ("email" = Option<String>, Query, description = "Case-insensitive email prefix"),
+    ),
+    responses(
+        (status = 200, description = "A page of users", body = ApiResponse<Page<AdminUserResponse>>),
+        (status = 400, description = "Invalid query parameters or cursor", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not an admin, or signed in with an API key", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn list_users_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    page: Result<PageQuery<UserListing>>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    ensure_admin(&user)?;
+
+    // Parsed up front but only reported once the caller is known to be an
+    // admin, so the listing's fields aren't disclosed to anyone else.
+    let page = state.admin_service.list_users(&page?).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: page.map(AdminUserResponse::from),
+        }),
+    ))
+}
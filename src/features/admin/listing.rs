@@ -0,0 +1,89 @@
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use uuid::Uuid;
+
+use crate::features::{
+    auth::{UserGender, UserRole},
+    shared::{Column, FilterField, FilterKind, ListSpec, Listing, SortField},
+};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct UserSummary {
+    pub id: Uuid,
+    pub email: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub role: UserRole,
+    pub gender: Option<UserGender>,
+    pub is_verified: bool,
+    pub is_banned: bool,
+    pub created_at: OffsetDateTime,
+}
+
+/// `GET /api/v1/admin/users`: newest accounts first by default.
+pub struct UserListing;
+
+impl Listing for UserListing {
+    const SPEC: &'static ListSpec = &ListSpec {
+        sorts: &[
+            SortField {
+                name: "created_at",
+                column: Column {
+                    name: "created_at",
+                    sql_type: "timestamptz",
+                },
+            },
+            SortField {
+                name: "email",
+                column: Column {
+                    name: "email",
+                    sql_type: "text",
+                },
+            },
+        ],
+        filters: &[
+            FilterField {
+                name: "role",
+                column: "role",
+                kind: FilterKind::Enum {
+                    sql_type: "user_role",
+                    values: &["admin", "regular"],
+                },
+            },
+            FilterField {
+                name: "is_verified",
+                column: "is_verified",
+                kind: FilterKind::Bool,
+            },
+            FilterField {
+                name: "is_banned",
+                column: "is_banned",
+                kind: FilterKind::Bool,
+            },
+            FilterField {
+                name: "email",
+                column: "email",
+                kind: FilterKind::Prefix,
+            },
+        ],
+        default_sort: "-created_at",
+        tiebreaker: Column {
+            name: "id",
+            sql_type: "uuid",
+        },
+        default_limit: 20,
+        max_limit: 100,
+    };
+
+    type Row = UserSummary;
+
+    fn sort_value(row: &UserSummary, sort: &SortField) -> String {
+        match sort.name {
+            "email" => row.email.clone(),
+            _ => row.created_at.format(&Rfc3339).unwrap_or_default(),
+        }
+    }
+
+    fn tiebreaker_value(row: &UserSummary) -> String {
+        row.id.to_string()
+    }
+}
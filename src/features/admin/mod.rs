@@ -0,0 +1,59 @@
+use axum::{Router, middleware, routing::get};
+use redis::aio::MultiplexedConnection;
+use sqlx::PgPool;
+
+use crate::{
+    app::AppState,
+    configuration::app_config::ApplicationConfig,
+    events::EventBus,
+    features::{admin::repository::AdminRepository, auth::build_session_store},
+    middlewares::{RateLimitLayer, authenticate, identify},
+};
+
+mod events;
+mod handlers;
+mod listing;
+mod repository;
+mod service;
+
+pub use events::UserBanned;
+pub use handlers::{AdminApi, AdminUserResponse};
+pub use listing::{UserListing, UserSummary};
+pub use service::{AdminService, CreateAdminInput, RevokedSessions};
+
+use handlers::*;
+
+pub struct AdminModule {
+    pub admin_service: AdminService,
+}
+
+impl AdminModule {
+    pub fn new(
+        app_config: &ApplicationConfig,
+        pool: PgPool,
+        redis: MultiplexedConnection,
+        events: EventBus,
+    ) -> Self {
+        let sessions = build_session_store(app_config.session_store, redis, pool.clone());
+        let repository = AdminRepository::new(pool);
+
+        Self {
+            admin_service: AdminService::new(repository, sessions, events),
+        }
+    }
+
+    pub fn v1(state: AppState) -> Router<AppState> {
+        Router::new().route(
+            "/users",
+            get(list_users_v1)
+                .route_layer(middleware::from_fn(authenticate))
+                .layer(RateLimitLayer::new(
+                    state.redis.clone(),
+                    state.settings.clone(),
+                    "admin_users",
+                    |r| &r.admin,
+                ))
+                .layer(middleware::from_fn_with_state(state, identify)),
+        )
+    }
+}
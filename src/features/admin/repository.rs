@@ -0,0 +1,112 @@
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder, Transaction, query};
+use tracing::instrument;
+
+use crate::{
+    Result,
+    features::{
+        admin::listing::{UserListing, UserSummary},
+        auth::{EmailAddress, HashedPassword, UserID, UserRole},
+        shared::PageQuery,
+    },
+};
+
+#[derive(Debug)]
+pub struct AdminRepository {
+    pool: PgPool,
+}
+
+impl AdminRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// For changes that publish events with
+    /// [`EventBus::publish_in`](crate::events::EventBus::publish_in).
+    pub async fn begin(&self) -> Result<Transaction<'static, Postgres>> {
+        Ok(self.pool.begin().await?)
+    }
+
+    #[instrument(skip_all, name = "adminrepository - create admin")]
+    pub async fn create_admin(
+        &self,
+        email: &EmailAddress,
+        password: &HashedPassword,
+    ) -> Result<UserID> {
+        let record = query!(
+            r#"
+                INSERT INTO users (email, password, role, is_verified)
+                VALUES ($1, $2, $3, true)
+                RETURNING id
+            "#,
+            email.as_ref(),
+            password.as_ref(),
+            UserRole::Admin as UserRole
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(UserID::from(record.id))
+    }
+
+    #[instrument(skip_all, name = "adminrepository - get user id by email")]
+    pub async fn get_user_id_by_email(&self, email: &EmailAddress) -> Result<Option<UserID>> {
+        let record = query!("SELECT id FROM users WHERE email = $1", email.as_ref())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(record.map(|r| UserID::from(r.id)))
+    }
+
+    #[instrument(skip_all, name = "adminrepository - list users")]
+    pub async fn list_users(&self, page: &PageQuery<UserListing>) -> Result<Vec<UserSummary>> {
+        let mut builder = QueryBuilder::new(
+            r#"
+                SELECT id, email, first_name, last_name, role, gender,
+                    is_verified, is_banned, created_at
+                FROM users
+            "#,
+        );
+        page.push_sql(&mut builder);
+
+        Ok(builder.build_query_as().fetch_all(&self.pool).await?)
+    }
+
+    #[instrument(skip_all, name = "adminrepository - set banned")]
+    pub async fn set_banned(
+        &self,
+        conn: &mut PgConnection,
+        email: &EmailAddress,
+        banned: bool,
+    ) -> Result<Option<UserID>> {
+        let record = query!(
+            r#"
+                UPDATE users
+                SET is_banned = $2, updated_at = NOW()
+                WHERE email = $1
+                RETURNING id
+            "#,
+            email.as_ref(),
+            banned
+        )
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(record.map(|r| UserID::from(r.id)))
+    }
+
+    #[instrument(skip_all, name = "adminrepository - revoke refresh tokens")]
+    pub async fn revoke_refresh_tokens(&self, user_id: &UserID) -> Result<u64> {
+        let result = query!(
+            r#"
+                UPDATE refresh_tokens
+                SET revoked_at = NOW()
+                WHERE user_id = $1 AND revoked_at IS NULL
+            "#,
+            user_id.as_ref()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use tracing::instrument;
+
+use crate::{
+    Error, Result,
+    common::hash_password,
+    events::EventBus,
+    features::{
+        admin::{
+            UserBanned,
+            listing::{UserListing, UserSummary},
+            repository::AdminRepository,
+        },
+        auth::{EmailAddress, HashedPassword, Password, SessionStore, UserID},
+        shared::{Page, PageQuery, map_unique_violation},
+    },
+};
+
+pub struct AdminService {
+    repository: AdminRepository,
+    sessions: Arc<dyn SessionStore>,
+    events: EventBus,
+}
+
+pub struct CreateAdminInput {
+    pub email: EmailAddress,
+    pub password: Password,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RevokedSessions {
+    pub sessions: u64,
+    pub refresh_tokens: u64,
+}
+
+impl AdminService {
+    pub fn new(
+        repository: AdminRepository,
+        sessions: Arc<dyn SessionStore>,
+        events: EventBus,
+    ) -> Self {
+        Self {
+            repository,
+            sessions,
+            events,
+        }
+    }
+
+    #[instrument(name = "admin.create_admin", skip(self, data), fields(email = %data.email))]
+    pub async fn create_admin(&self, data: CreateAdminInput) -> Result<UserID> {
+        let hashed_password = HashedPassword::parse(hash_password(data.password.as_ref())?)?;
+
+        self.repository
+            .create_admin(&data.email, &hashed_password)
+            .await
+            .map_err(map_unique_violation(Some(Error::Conflict(
+                "An account with this email already exists".into(),
+            ))))
+    }
+
+    /// Banning also signs the user out everywhere. The ban is only committed
+    /// once that worked, so a failure can be retried without a second event.
+    #[instrument(name = "admin.set_banned", skip(self), fields(email = %email))]
+    pub async fn set_banned(&self, email: &EmailAddress, banned: bool) -> Result<UserID> {
+        let mut tx = self.repository.begin().await?;
+        let user_id = self
+            .repository
+            .set_banned(&mut tx, email, banned)
+            .await?
+            .ok_or(Error::NotFound("User not found".into()))?;
+
+        if banned {
+            self.revoke_user_sessions(&user_id).await?;
+            self.events
+                .publish_in(
+                    &mut tx,
+                    &UserBanned {
+                        user_id: user_id.clone().into_inner(),
+                        email: email.to_string(),
+                    },
+                )
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok(user_id)
+    }
+
+    #[instrument(name = "admin.revoke_sessions", skip(self), fields(email = %email))]
+    pub async fn revoke_sessions(&self, email: &EmailAddress) -> Result<RevokedSessions> {
+        let user_id = self
+            .repository
+            .get_user_id_by_email(email)
+            .await?
+            .ok_or(Error::NotFound("User not found".into()))?;
+
+        self.revoke_user_sessions(&user_id).await
+    }
+
+    #[instrument(name = "admin.list_users", skip_all, fields(limit = page.limit))]
+    pub async fn list_users(&self, page: &PageQuery<UserListing>) -> Result<Page<UserSummary>> {
+        let rows = self.repository.list_users(page).await?;
+
+        Ok(page.page(rows))
+    }
+
+    async fn revoke_user_sessions(&self, user_id: &UserID) -> Result<RevokedSessions> {
+        let sessions = self.sessions.remove_user_sessions(user_id).await?;
+        let refresh_tokens = self.repository.revoke_refresh_tokens(user_id).await?;
+
+        Ok(RevokedSessions {
+            sessions,
+            refresh_tokens,
+        })
+    }
+}
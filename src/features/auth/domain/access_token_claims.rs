@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccessTokenClaims {
+    pub sub: Uuid,
+    pub iat: i64,
+    pub exp: i64,
+    /// When the user last proved their credentials, carried across refreshes.
+    pub auth_time: i64,
+}
@@ -0,0 +1,229 @@
+use std::{fmt::Display, net::IpAddr};
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    Error, Result,
+    common::generate_secure_random_string,
+    features::{auth::UserID, shared::NonEmptyString},
+    i18n::Message,
+};
+
+pub const API_KEY_PREFIX: &str = "kicks";
+pub const API_KEY_MAX_EXPIRY_DAYS: u32 = 365;
+const API_KEY_PREFIX_LENGTH: usize = 8;
+const API_KEY_SECRET_LENGTH: usize = 40;
+
+pub type ApiKeyName = NonEmptyString<1, 100>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum ApiKeyScope {
+    #[serde(rename = "profile:read")]
+    ProfileRead,
+}
+
+impl ApiKeyScope {
+    pub fn parse(value: String) -> Result<Self> {
+        match value.trim() {
+            "profile:read" => Ok(ApiKeyScope::ProfileRead),
+            _ => Err(Error::DomainValidationError(vec![
+                Message::new("api_key.unknown_scope").with("scope", value),
+            ])),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            ApiKeyScope::ProfileRead => "profile:read",
+        }
+    }
+}
+
+impl Display for ApiKeyScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A freshly generated key. The full value is only ever shown to the user once.
+#[derive(Debug)]
+pub struct PlainApiKey {
+    pub prefix: String,
+    pub value: String,
+}
+
+impl PlainApiKey {
+    pub fn generate() -> Self {
+        let prefix = format!(
+            "{}_{}",
+            API_KEY_PREFIX,
+            generate_secure_random_string(API_KEY_PREFIX_LENGTH)
+        );
+        let value = format!(
+            "{}_{}",
+            prefix,
+            generate_secure_random_string(API_KEY_SECRET_LENGTH)
+        );
+
+        Self { prefix, value }
+    }
+
+    /// Extracts the lookup prefix from a presented key.
+    pub fn prefix_of(value: &str) -> Option<&str> {
+        let (prefix, secret) = value.rsplit_once('_')?;
+        let (namespace, id) = prefix.split_once('_')?;
+
+        if namespace != API_KEY_PREFIX
+            || id.len() != API_KEY_PREFIX_LENGTH
+            || secret.len() != API_KEY_SECRET_LENGTH
+        {
+            return None;
+        }
+
+        Some(prefix)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ApiKeyExpiry(OffsetDateTime);
+
+impl ApiKeyExpiry {
+    pub fn parse(days: u32) -> Result<Self> {
+        if days == 0 || days > API_KEY_MAX_EXPIRY_DAYS {
+            return Err(Error::DomainValidationError(vec![
+                Message::new("api_key.expiry_out_of_range").with("max", API_KEY_MAX_EXPIRY_DAYS),
+            ]));
+        }
+
+        Ok(Self(
+            OffsetDateTime::now_utc() + time::Duration::days(days as i64),
+        ))
+    }
+
+    pub fn into_inner(self) -> OffsetDateTime {
+        self.0
+    }
+}
+
+#[derive(Debug)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub user_id: UserID,
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub created_at: OffsetDateTime,
+    pub expires_at: Option<OffsetDateTime>,
+    pub last_used_at: Option<OffsetDateTime>,
+    pub last_used_ip: Option<IpAddr>,
+    pub revoked_at: Option<OffsetDateTime>,
+}
+
+impl ApiKey {
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+            && self
+                .expires_at
+                .is_none_or(|expires_at| expires_at > OffsetDateTime::now_utc())
+    }
+}
+
+#[derive(Debug)]
+pub struct NewApiKey {
+    pub user_id: UserID,
+    pub name: ApiKeyName,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unknown_scope_should_fail_parse() {
+        assert!(ApiKeyScope::parse("admin:all".into()).is_err());
+    }
+
+    #[test]
+    fn valid_scope_should_pass_parse() {
+        assert_eq!(
+            ApiKeyScope::parse("profile:read".into()).unwrap(),
+            ApiKeyScope::ProfileRead
+        );
+    }
+
+    #[test]
+    fn expiry_out_of_range_should_fail_parse() {
+        assert!(ApiKeyExpiry::parse(0).is_err());
+        assert!(ApiKeyExpiry::parse(API_KEY_MAX_EXPIRY_DAYS + 1).is_err());
+    }
+
+    #[test]
+    fn valid_expiry_should_pass_parse() {
+        assert!(ApiKeyExpiry::parse(1).is_ok());
+        assert!(ApiKeyExpiry::parse(API_KEY_MAX_EXPIRY_DAYS).is_ok());
+    }
+
+    #[test]
+    fn generated_key_starts_with_its_prefix() {
+        let key = PlainApiKey::generate();
+
+        assert!(key.value.starts_with(&key.prefix));
+        assert_eq!(
+            PlainApiKey::prefix_of(&key.value),
+            Some(key.prefix.as_str())
+        );
+    }
+
+    #[test]
+    fn malformed_key_has_no_prefix() {
+        assert!(PlainApiKey::prefix_of("").is_none());
+        assert!(PlainApiKey::prefix_of("kicks_short_secret").is_none());
+        assert!(PlainApiKey::prefix_of(&format!("other_abcdefgh_{}", "a".repeat(40))).is_none());
+    }
+
+    #[test]
+    fn revoked_key_is_not_active() {
+        let key = ApiKey {
+            id: Uuid::new_v4(),
+            user_id: UserID::from(Uuid::new_v4()),
+            name: "key".into(),
+            prefix: "kicks_abcdefgh".into(),
+            key_hash: "hash".into(),
+            scopes: vec![],
+            created_at: OffsetDateTime::now_utc(),
+            expires_at: None,
+            last_used_at: None,
+            last_used_ip: None,
+            revoked_at: Some(OffsetDateTime::now_utc()),
+        };
+
+        assert!(!key.is_active());
+    }
+
+    #[test]
+    fn expired_key_is_not_active() {
+        let key = ApiKey {
+            id: Uuid::new_v4(),
+            user_id: UserID::from(Uuid::new_v4()),
+            name: "key".into(),
+            prefix: "kicks_abcdefgh".into(),
+            key_hash: "hash".into(),
+            scopes: vec![],
+            created_at: OffsetDateTime::now_utc(),
+            expires_at: Some(OffsetDateTime::now_utc() - time::Duration::minutes(1)),
+            last_used_at: None,
+            last_used_ip: None,
+            revoked_at: None,
+        };
+
+        assert!(!key.is_active());
+    }
+}
@@ -4,7 +4,7 @@ use serde::de;
 use serde::{Deserialize, Deserializer};
 use validator::ValidateEmail;
 
-use crate::{Error, Result};
+use crate::{Error, Result, i18n::Message};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, AsRef, Display)]
 #[as_ref(str)]
@@ -15,9 +15,9 @@ impl EmailAddress {
         if value.validate_email() {
             Ok(Self(value))
         } else {
-            Err(Error::DomainValidationError(vec![
-                "Invalid email address".into(),
-            ]))
+            Err(Error::DomainValidationError(vec![Message::new(
+                "email.invalid",
+            )]))
         }
     }
 }
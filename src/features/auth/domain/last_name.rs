@@ -1,7 +1,7 @@
 use derive_more::{AsRef, Display};
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::{Error, Result};
+use crate::{Error, Result, i18n::Message};
 
 pub const LAST_NAME_MAX_LENGTH: usize = 40;
 
@@ -11,24 +11,21 @@ pub struct LastName(String);
 
 impl LastName {
     pub fn parse(mut value: String) -> Result<Self> {
-        let mut errors: Vec<String> = Vec::new();
+        let mut errors: Vec<Message> = Vec::new();
 
         value.retain(|c| !c.is_whitespace());
         let char_count = value.graphemes(true).count();
 
         if value.is_empty() {
-            errors.push("Last name cannot be empty".into());
+            errors.push(Message::new("last_name.empty"));
         }
 
         if value.chars().any(|c| !c.is_alphabetic()) {
-            errors.push("Last name must contain only alphabetic characters".into());
+            errors.push(Message::new("last_name.not_alphabetic"));
         }
 
         if char_count > LAST_NAME_MAX_LENGTH {
-            errors.push(format!(
-                "Last name must be less than or equal to {} characters.",
-                { LAST_NAME_MAX_LENGTH },
-            ));
+            errors.push(Message::new("last_name.too_long").with("max", LAST_NAME_MAX_LENGTH));
         }
 
         if !errors.is_empty() {
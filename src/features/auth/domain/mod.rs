@@ -1,3 +1,5 @@
+mod access_token_claims;
+mod api_key;
 mod email_address;
 mod facebook_id;
 mod facebook_oauth2;
@@ -9,13 +11,17 @@ mod last_name;
 mod new_user;
 mod oauth2_code;
 mod oauth2_state;
+mod onboarding;
 mod password;
+mod refresh_token;
 mod update_user;
 mod user;
 mod user_gender;
 mod user_id;
 mod user_role;
 
+pub use access_token_claims::*;
+pub use api_key::*;
 pub use email_address::*;
 pub use facebook_id::*;
 pub use facebook_oauth2::*;
@@ -27,7 +33,9 @@ pub use last_name::*;
 pub use new_user::*;
 pub use oauth2_code::*;
 pub use oauth2_state::*;
+pub use onboarding::*;
 pub use password::*;
+pub use refresh_token::*;
 pub use update_user::*;
 pub use user::*;
 pub use user_gender::*;
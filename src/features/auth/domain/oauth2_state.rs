@@ -3,12 +3,13 @@ use std::fmt::Display;
 use unicode_segmentation::UnicodeSegmentation;
 use uuid::Uuid;
 
-use crate::{Error, Result};
+use crate::{Error, Result, i18n::Message};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct OAuth2State(Uuid, Option<String>);
 
 const OAUTH2_STATE_DELIMITER: &str = "|";
+const OAUTH2_STATE_MAX_LENGTH: usize = 100;
 
 impl OAuth2State {
     pub fn parse(mut value: String) -> Result<Self> {
@@ -18,18 +19,18 @@ impl OAuth2State {
         let char_count = value.graphemes(true).count();
 
         if value.is_empty() {
-            errors.push("OAuth state cannot be empty".into());
+            errors.push(Message::new("oauth_state.empty"));
         }
 
-        if char_count > 100 {
-            errors.push("OAuth state must be less than or equal to 100 characters.".into());
+        if char_count > OAUTH2_STATE_MAX_LENGTH {
+            errors.push(Message::new("oauth_state.too_long").with("max", OAUTH2_STATE_MAX_LENGTH));
         }
 
         let splitted: Vec<&str> = value.splitn(2, OAUTH2_STATE_DELIMITER).collect();
 
         let state_id = Uuid::parse_str(splitted[0]);
         if state_id.is_err() {
-            errors.push("Invalid oauth state".into());
+            errors.push(Message::new("oauth_state.invalid"));
         }
 
         if !errors.is_empty() {
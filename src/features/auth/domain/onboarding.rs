@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    ProfileCompleted,
+    ShoeSizeSet,
+    PreferencesChosen,
+}
+
+impl OnboardingStep {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "profile_completed" => Some(OnboardingStep::ProfileCompleted),
+            "shoe_size_set" => Some(OnboardingStep::ShoeSizeSet),
+            "preferences_chosen" => Some(OnboardingStep::PreferencesChosen),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OnboardingStep::ProfileCompleted => "profile_completed",
+            OnboardingStep::ShoeSizeSet => "shoe_size_set",
+            OnboardingStep::PreferencesChosen => "preferences_chosen",
+        }
+    }
+}
+
+/// The checklist shown after verification. Users without a row yet have
+/// nothing checked.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Onboarding {
+    pub profile_completed_at: Option<OffsetDateTime>,
+    pub shoe_size_set_at: Option<OffsetDateTime>,
+    pub preferences_chosen_at: Option<OffsetDateTime>,
+}
+
+impl Onboarding {
+    pub fn is_done(&self, step: OnboardingStep) -> bool {
+        match step {
+            OnboardingStep::ProfileCompleted => self.profile_completed_at.is_some(),
+            OnboardingStep::ShoeSizeSet => self.shoe_size_set_at.is_some(),
+            OnboardingStep::PreferencesChosen => self.preferences_chosen_at.is_some(),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        [
+            OnboardingStep::ProfileCompleted,
+            OnboardingStep::ShoeSizeSet,
+            OnboardingStep::PreferencesChosen,
+        ]
+        .into_iter()
+        .all(|step| self.is_done(step))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_known_steps_only() {
+        assert_eq!(
+            OnboardingStep::parse("shoe_size_set"),
+            Some(OnboardingStep::ShoeSizeSet)
+        );
+        assert_eq!(OnboardingStep::parse("ShoeSizeSet"), None);
+        assert_eq!(OnboardingStep::parse(""), None);
+    }
+
+    #[test]
+    fn is_complete_once_every_step_is_done() {
+        let now = OffsetDateTime::now_utc();
+        let mut onboarding = Onboarding::default();
+        assert!(!onboarding.is_complete());
+
+        onboarding.profile_completed_at = Some(now);
+        onboarding.shoe_size_set_at = Some(now);
+        assert!(onboarding.is_done(OnboardingStep::ShoeSizeSet));
+        assert!(!onboarding.is_complete());
+
+        onboarding.preferences_chosen_at = Some(now);
+        assert!(onboarding.is_complete());
+    }
+}
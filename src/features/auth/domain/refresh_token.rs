@@ -0,0 +1,24 @@
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::features::auth::UserID;
+
+#[derive(Debug)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub user_id: UserID,
+    pub family_id: Uuid,
+    pub authenticated_at: OffsetDateTime,
+    pub expires_at: OffsetDateTime,
+    pub used_at: Option<OffsetDateTime>,
+    pub revoked_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug)]
+pub struct NewRefreshToken {
+    pub user_id: UserID,
+    pub family_id: Uuid,
+    pub token_hash: String,
+    pub authenticated_at: OffsetDateTime,
+    pub expires_at: OffsetDateTime,
+}
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-use crate::{Error, Result};
+use crate::{Error, Result, i18n::Message};
 
-#[derive(Debug, sqlx::Type, Serialize, Deserialize, Clone)]
+#[derive(Debug, sqlx::Type, Serialize, Deserialize, Clone, ToSchema)]
 #[sqlx(type_name = "user_gender", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum UserGender {
@@ -17,9 +18,9 @@ impl UserGender {
             v if v.eq_ignore_ascii_case("male") => Ok(UserGender::Male),
             v if v.eq_ignore_ascii_case("female") => Ok(UserGender::Female),
             v if v.eq_ignore_ascii_case("other") => Ok(UserGender::Other),
-            _ => Err(Error::DomainValidationError(vec![
-                "Must be male, female or other".into(),
-            ])),
+            _ => Err(Error::DomainValidationError(vec![Message::new(
+                "gender.invalid",
+            )])),
         }
     }
 }
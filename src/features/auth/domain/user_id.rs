@@ -1,7 +1,7 @@
 use derive_more::{AsRef, Display};
 use uuid::Uuid;
 
-use crate::{Error, Result};
+use crate::{Error, Result, i18n::Message};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, AsRef, Display)]
 #[as_ref(Uuid)]
@@ -12,7 +12,9 @@ impl UserID {
         if let Ok(id) = Uuid::parse_str(value) {
             Ok(Self(id))
         } else {
-            Err(Error::DomainValidationError(vec!["Invalid user id".into()]))
+            Err(Error::DomainValidationError(vec![Message::new(
+                "user_id.invalid",
+            )]))
         }
     }
 
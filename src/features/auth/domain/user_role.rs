@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, sqlx::Type, Serialize, Deserialize, Clone)]
+#[derive(Debug, sqlx::Type, Serialize, Deserialize, Clone, ToSchema)]
 #[sqlx(type_name = "user_role", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum UserRole {
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::events::Event;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignInMethod {
+    Password,
+    Google,
+    Facebook,
+    /// The session opened by verifying an account.
+    Verification,
+}
+
+/// Published for password sign-ups and for accounts created on a first
+/// OAuth2 sign-in; the latter are verified already.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserSignedUp {
+    pub user_id: Uuid,
+    pub email: String,
+    pub first_name: Option<String>,
+    pub method: SignInMethod,
+}
+
+impl Event for UserSignedUp {
+    const NAME: &'static str = "auth.user_signed_up";
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserVerified {
+    pub user_id: Uuid,
+    pub email: String,
+    pub first_name: Option<String>,
+}
+
+impl Event for UserVerified {
+    const NAME: &'static str = "auth.user_verified";
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordReset {
+    pub user_id: Uuid,
+    pub email: String,
+}
+
+impl Event for PasswordReset {
+    const NAME: &'static str = "auth.password_reset";
+}
+
+/// Published for session and token sign-ins alike.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserSignedIn {
+    pub user_id: Uuid,
+    pub method: SignInMethod,
+}
+
+impl Event for UserSignedIn {
+    const NAME: &'static str = "auth.user_signed_in";
+}
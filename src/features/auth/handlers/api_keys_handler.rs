@@ -0,0 +1,203 @@
+use std::net::IpAddr;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use axum_extra::extract::WithRejection;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    ApiResponse, Error, Problem, Result,
+    app::AppState,
+    features::{
+        auth::{
+            ApiKey, ApiKeyExpiry, ApiKeyName, ApiKeyScope, service::api_keys::CreateApiKeyInput,
+        },
+        shared::{AppUser, Credential, ensure_interactive, ensure_recent_authentication},
+    },
+    validate_and_parse,
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    #[schema(value_type = Option<Vec<ApiKeyScope>>)]
+    pub scopes: Option<Vec<String>>,
+    /// Never expires when omitted.
+    pub expires_in_days: Option<u32>,
+}
+
+impl TryFrom<CreateApiKeyRequest> for CreateApiKeyInput {
+    type Error = Error;
+
+    fn try_from(value: CreateApiKeyRequest) -> std::result::Result<Self, Self::Error> {
+        let (name, scopes, expires_at) = validate_and_parse!(
+            name => ApiKeyName::parse(value.name),
+            scopes => value
+                .scopes
+                .unwrap_or_default()
+                .into_iter()
+                .map(ApiKeyScope::parse)
+                .collect::<Result<Vec<_>>>(),
+            expires_at => value.expires_in_days.map(ApiKeyExpiry::parse).transpose(),
+        );
+
+        Ok(CreateApiKeyInput {
+            name,
+            scopes,
+            expires_at,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<ApiKeyScope>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_used_at: Option<OffsetDateTime>,
+    #[schema(value_type = Option<String>, format = "ip")]
+    pub last_used_ip: Option<IpAddr>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub revoked_at: Option<OffsetDateTime>,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(value: ApiKey) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            prefix: value.prefix,
+            scopes: value.scopes,
+            created_at: value.created_at,
+            expires_at: value.expires_at,
+            last_used_at: value.last_used_at,
+            last_used_ip: value.last_used_ip,
+            revoked_at: value.revoked_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct CreatedApiKeyResponse {
+    #[serde(flatten)]
+    pub api_key: ApiKeyResponse,
+    pub key: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api-keys",
+    tag = "auth",
+    request_body = CreateApiKeyRequest,
+    security(("session_cookie" = [], "csrf_token" = []), ("bearer" = [])),
+    responses(
+        (status = 201, description = "The new key; `key` is only ever returned here", body = ApiResponse<CreatedApiKeyResponse>),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Recent authentication or CSRF token required", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn create_api_key_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    WithRejection(Json(data), _): WithRejection<Json<CreateApiKeyRequest>, Error>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    ensure_recent_authentication(&credential, state.auth_service.reauthentication_max_age())?;
+    let user = user.ok_or(Error::Unauthorized)?;
+
+    let (api_key, key) = state
+        .auth_service
+        .create_api_key(&user.id, data.try_into()?)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: CreatedApiKeyResponse {
+                api_key: api_key.into(),
+                key,
+            },
+        }),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api-keys",
+    tag = "auth",
+    security(("session_cookie" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "Every key of the signed-in user", body = ApiResponse<Vec<ApiKeyResponse>>),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "API keys cannot manage API keys", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn list_api_keys_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+
+    let api_keys = state.auth_service.list_api_keys(&user.id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: api_keys
+                .into_iter()
+                .map(ApiKeyResponse::from)
+                .collect::<Vec<_>>(),
+        }),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api-keys/{id}",
+    tag = "auth",
+    params(("id" = Uuid, Path, description = "API key id")),
+    security(("session_cookie" = [], "csrf_token" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "Key revoked", body = ApiResponse<String>),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Recent authentication or CSRF token required", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "No such key", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn revoke_api_key_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    ensure_recent_authentication(&credential, state.auth_service.reauthentication_max_age())?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    let id = Uuid::parse_str(&id).map_err(|_| Error::NotFound("API key not found".into()))?;
+
+    state.auth_service.revoke_api_key(&user.id, &id).await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse { data: "Success" })).into_response())
+}
@@ -0,0 +1,54 @@
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use axum_extra::extract::{
+    SignedCookieJar,
+    cookie::{Cookie, SameSite},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    ApiResponse, Problem, Result, app::AppState, common::generate_secure_random_string,
+    configuration::app_config::ApplicationConfig,
+};
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct CsrfTokenResponse {
+    pub token: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/csrf-token",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Echo `token` in the `x-csrf-token` header of state-changing cookie requests", body = ApiResponse<CsrfTokenResponse>, headers(("set-cookie" = String, description = "Signed CSRF cookie"))),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn get_csrf_token_v1(
+    State(state): State<AppState>,
+    jar: SignedCookieJar,
+) -> Result<impl IntoResponse> {
+    let token = jar
+        .get(&state.config.csrf_cookie_name)
+        .map(|c| c.value().to_string())
+        .unwrap_or_else(|| generate_secure_random_string(32));
+
+    Ok((
+        StatusCode::OK,
+        jar.add(generate_csrf_cookie(token.clone(), &state.config)),
+        Json(ApiResponse {
+            data: CsrfTokenResponse { token },
+        }),
+    )
+        .into_response())
+}
+
+pub fn generate_csrf_cookie<'a>(token: String, config: &ApplicationConfig) -> Cookie<'a> {
+    Cookie::build((config.csrf_cookie_name.to_string(), token))
+        .same_site(SameSite::Strict)
+        .secure(config.cookie_secure)
+        .path("/")
+        .http_only(true)
+        .build()
+}
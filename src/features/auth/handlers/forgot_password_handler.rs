@@ -1,15 +1,16 @@
 use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
 use axum_extra::extract::WithRejection;
 use serde::Deserialize;
+use utoipa::ToSchema;
 
 use crate::{
-    ApiResponse, Error, Result,
+    ApiResponse, Error, Problem, Result,
     app::AppState,
     features::auth::{domain::EmailAddress, service::forgot_password::ForgotPasswordInput},
     validate_and_parse,
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct ForgotPasswordRequest {
     pub email: String,
 }
@@ -26,6 +27,20 @@ impl TryFrom<ForgotPasswordRequest> for ForgotPasswordInput {
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/forgot-password",
+    tag = "auth",
+    request_body = ForgotPasswordRequest,
+    params(("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key and body replay the first successful response")),
+    responses(
+        (status = 200, description = "A reset email is sent if the address is registered", body = ApiResponse<String>),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "A request with the same `Idempotency-Key` is in progress", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "`Idempotency-Key` reused with a different body", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
 pub async fn forgot_password_v1(
     State(state): State<AppState>,
     WithRejection(Json(data), _): WithRejection<Json<ForgotPasswordRequest>, Error>,
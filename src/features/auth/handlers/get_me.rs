@@ -1,27 +1,44 @@
-use axum::{Extension, Json, http::StatusCode, response::IntoResponse};
+use axum::{Extension, Json, extract::State, http::StatusCode, response::IntoResponse};
 
 use crate::{
-    ApiResponse, Error, Result,
-    features::{auth::UserResponse, shared::AppUser},
+    ApiResponse, Error, Problem, Result,
+    app::AppState,
+    features::{
+        auth::{ApiKeyScope, UserResponse},
+        shared::{AppUser, Credential, ensure_scope},
+    },
 };
 
-pub async fn get_me_v1(Extension(user): Extension<Option<AppUser>>) -> Result<impl IntoResponse> {
+#[utoipa::path(
+    get,
+    path = "/me",
+    tag = "auth",
+    security(("session_cookie" = []), ("bearer" = []), ("api_key" = [])),
+    responses(
+        (status = 200, description = "The signed-in user", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "API key lacks the `profile:read` scope", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn get_me_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+) -> Result<impl IntoResponse> {
+    ensure_scope(&credential, ApiKeyScope::ProfileRead)?;
+
     if user.is_none() {
         return Err(Error::Unauthorized);
     }
 
     let user = user.unwrap();
+    let onboarding = state.auth_service.get_onboarding(&user.id).await?;
 
     Ok((
         StatusCode::OK,
         Json(ApiResponse {
-            data: UserResponse {
-                email: user.email.to_string(),
-                gender: user.gender,
-                last_name: user.last_name.map(|x| x.to_string()),
-                first_name: user.first_name.map(|x| x.to_string()),
-                role: user.role,
-            },
+            data: UserResponse::new(user, &onboarding),
         }),
     )
         .into_response())
@@ -1,8 +1,20 @@
 use axum::{Extension, Json, extract::State, http::StatusCode, response::IntoResponse};
 use axum_extra::extract::SignedCookieJar;
 
-use crate::{ApiResponse, Error, Result, app::AppState, features::shared::AppUser};
+use crate::{ApiResponse, Error, Problem, Result, app::AppState, features::shared::AppUser};
 
+#[utoipa::path(
+    post,
+    path = "/logout",
+    tag = "auth",
+    security(("session_cookie" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, description = "Session ended and cookie removed", body = ApiResponse<String>),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Missing CSRF token", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
 pub async fn logout_v1(
     State(state): State<AppState>,
     Extension(user): Extension<Option<AppUser>>,
@@ -1,15 +1,28 @@
-use crate::features::auth::domain::{UserGender, UserRole};
+use crate::features::{
+    auth::domain::{Onboarding, OnboardingStep, UserGender, UserRole},
+    shared::AppUser,
+};
 use serde::{Deserialize, Serialize};
+use utoipa::{OpenApi, ToSchema};
 
+mod api_keys_handler;
+mod csrf_handler;
 mod forgot_password_handler;
 mod get_me;
 mod logout_handler;
 mod oauth2_handler;
+mod onboarding_handler;
+mod reauthenticate_handler;
 mod reset_password_handler;
 mod sign_in_handler;
 mod sign_up_handler;
+mod token_handler;
 mod verify_account_handler;
 
+pub use api_keys_handler::{
+    ApiKeyResponse, CreatedApiKeyResponse, create_api_key_v1, list_api_keys_v1, revoke_api_key_v1,
+};
+pub use csrf_handler::{CsrfTokenResponse, generate_csrf_cookie, get_csrf_token_v1};
 pub use forgot_password_handler::forgot_password_v1;
 pub use get_me::get_me_v1;
 pub use logout_handler::logout_v1;
@@ -17,16 +30,92 @@ pub use oauth2_handler::{
     facebook_sign_in_v1, get_facebook_redirect_url_v1, get_google_redirect_url_v1,
     google_sign_in_v1,
 };
+pub use onboarding_handler::complete_onboarding_step_v1;
+pub use reauthenticate_handler::reauthenticate_v1;
 pub use reset_password_handler::reset_password_v1;
 pub use sign_in_handler::{generate_session_cookie, sign_in_v1};
 pub use sign_up_handler::sign_up_v1;
+pub use token_handler::{refresh_token_v1, revoke_token_v1, token_sign_in_v1};
 pub use verify_account_handler::verify_account_v1;
 
-#[derive(Serialize, Deserialize)]
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        sign_up_handler::sign_up_v1,
+        verify_account_handler::verify_account_v1,
+        sign_in_handler::sign_in_v1,
+        token_handler::token_sign_in_v1,
+        token_handler::refresh_token_v1,
+        token_handler::revoke_token_v1,
+        forgot_password_handler::forgot_password_v1,
+        reset_password_handler::reset_password_v1,
+        oauth2_handler::get_google_redirect_url_v1,
+        oauth2_handler::google_sign_in_v1,
+        oauth2_handler::get_facebook_redirect_url_v1,
+        oauth2_handler::facebook_sign_in_v1,
+        logout_handler::logout_v1,
+        reauthenticate_handler::reauthenticate_v1,
+        csrf_handler::get_csrf_token_v1,
+        get_me::get_me_v1,
+        onboarding_handler::complete_onboarding_step_v1,
+        api_keys_handler::list_api_keys_v1,
+        api_keys_handler::create_api_key_v1,
+        api_keys_handler::revoke_api_key_v1,
+    ),
+    tags((name = "auth", description = "Accounts, sessions, tokens and API keys")),
+)]
+pub struct AuthApi;
+
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct UserResponse {
     pub email: String,
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub role: UserRole,
     pub gender: Option<UserGender>,
+    pub onboarding: OnboardingResponse,
+}
+
+impl UserResponse {
+    pub fn new(user: AppUser, onboarding: &Onboarding) -> Self {
+        Self {
+            email: user.email.to_string(),
+            gender: user.gender,
+            last_name: user.last_name.map(|x| x.to_string()),
+            first_name: user.first_name.map(|x| x.to_string()),
+            role: user.role,
+            onboarding: onboarding.into(),
+        }
+    }
+}
+
+/// The post-verification checklist.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct OnboardingResponse {
+    pub profile_completed: bool,
+    pub shoe_size_set: bool,
+    pub preferences_chosen: bool,
+    /// Every step is done.
+    pub completed: bool,
+}
+
+impl From<&Onboarding> for OnboardingResponse {
+    fn from(onboarding: &Onboarding) -> Self {
+        Self {
+            profile_completed: onboarding.is_done(OnboardingStep::ProfileCompleted),
+            shoe_size_set: onboarding.is_done(OnboardingStep::ShoeSizeSet),
+            preferences_chosen: onboarding.is_done(OnboardingStep::PreferencesChosen),
+            completed: onboarding.is_complete(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub refresh_token: String,
+    #[schema(example = "Bearer")]
+    pub token_type: String,
+    /// Seconds until the access token expires.
+    pub expires_in: u64,
 }
@@ -1,5 +1,5 @@
 use crate::{
-    Error, Result,
+    Error, Problem, Result,
     app::AppState,
     configuration::app_config::ApplicationConfig,
     features::auth::{
@@ -18,18 +18,30 @@ use axum_extra::extract::{
 };
 use serde::Deserialize;
 use time::{Duration, OffsetDateTime};
+use utoipa::IntoParams;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct OAuth2RedirectUrlRequestQuery {
+    /// Client path to return to after signing in.
     pub redirect_path: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct OAuth2SignInRequestQuery {
     state: String,
     code: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/google",
+    tag = "auth",
+    params(OAuth2RedirectUrlRequestQuery),
+    responses(
+        (status = 303, description = "Redirect to Google with the OAuth state cookie set", headers(("location" = String), ("set-cookie" = String))),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
 pub async fn get_google_redirect_url_v1(
     State(state): State<AppState>,
     Query(query): Query<OAuth2RedirectUrlRequestQuery>,
@@ -40,12 +52,26 @@ pub async fn get_google_redirect_url_v1(
         .generate_oauth2_redirect_url(OAuth2Provider::Google, query.redirect_path)?;
 
     Ok((
-        jar.add(generate_oauth_state_cookie(oauth_state, &state.config)),
+        jar.add(generate_oauth_state_cookie(
+            oauth_state,
+            &state.config,
+            state.settings.current().oauth_state_ttl_minutes,
+        )),
         Redirect::to(url.as_str()),
     )
         .into_response())
 }
 
+#[utoipa::path(
+    get,
+    path = "/facebook",
+    tag = "auth",
+    params(OAuth2RedirectUrlRequestQuery),
+    responses(
+        (status = 303, description = "Redirect to Facebook with the OAuth state cookie set", headers(("location" = String), ("set-cookie" = String))),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
 pub async fn get_facebook_redirect_url_v1(
     State(state): State<AppState>,
     Query(query): Query<OAuth2RedirectUrlRequestQuery>,
@@ -56,12 +82,28 @@ pub async fn get_facebook_redirect_url_v1(
         .generate_oauth2_redirect_url(OAuth2Provider::Facebook, query.redirect_path)?;
 
     Ok((
-        jar.add(generate_oauth_state_cookie(oauth_state, &state.config)),
+        jar.add(generate_oauth_state_cookie(
+            oauth_state,
+            &state.config,
+            state.settings.current().oauth_state_ttl_minutes,
+        )),
         Redirect::to(url.as_str()),
     )
         .into_response())
 }
 
+#[utoipa::path(
+    get,
+    path = "/google/callback",
+    tag = "auth",
+    params(OAuth2SignInRequestQuery),
+    responses(
+        (status = 303, description = "Signed in; redirect to the client with the session cookie set", headers(("location" = String), ("set-cookie" = String))),
+        (status = 400, description = "Invalid request or state mismatch", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Google email is not verified", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
 pub async fn google_sign_in_v1(
     State(state): State<AppState>,
     Query(query): Query<OAuth2SignInRequestQuery>,
@@ -69,13 +111,17 @@ pub async fn google_sign_in_v1(
 ) -> Result<Response> {
     let parsed = parse_oauth2_request(query, &jar, &state.config)?;
 
-    let (session_id, redirect_path) = state
+    let (session_id, session, redirect_path) = state
         .auth_service
         .oauth2_sign_in(OAuth2Provider::Google, parsed)
         .await?;
 
     let cookie = jar
-        .add(generate_session_cookie(session_id, &state.config))
+        .add(generate_session_cookie(
+            session_id,
+            session.cookie_expires_at(),
+            &state.config,
+        ))
         .remove(Cookie::from(
             state.config.oauth_state_cookie_name.to_string(),
         ));
@@ -90,6 +136,18 @@ pub async fn google_sign_in_v1(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/facebook/callback",
+    tag = "auth",
+    params(OAuth2SignInRequestQuery),
+    responses(
+        (status = 303, description = "Signed in; redirect to the client with the session cookie set", headers(("location" = String), ("set-cookie" = String))),
+        (status = 400, description = "Invalid request or state mismatch", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Facebook email is not verified", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
 pub async fn facebook_sign_in_v1(
     State(state): State<AppState>,
     Query(query): Query<OAuth2SignInRequestQuery>,
@@ -97,13 +155,17 @@ pub async fn facebook_sign_in_v1(
 ) -> Result<Response> {
     let parsed = parse_oauth2_request(query, &jar, &state.config)?;
 
-    let (session_id, redirect_path) = state
+    let (session_id, session, redirect_path) = state
         .auth_service
         .oauth2_sign_in(OAuth2Provider::Facebook, parsed)
         .await?;
 
     let cookie = jar
-        .add(generate_session_cookie(session_id, &state.config))
+        .add(generate_session_cookie(
+            session_id,
+            session.cookie_expires_at(),
+            &state.config,
+        ))
         .remove(Cookie::from(
             state.config.oauth_state_cookie_name.to_string(),
         ));
@@ -118,9 +180,12 @@ pub async fn facebook_sign_in_v1(
     }
 }
 
-fn generate_oauth_state_cookie<'a>(state: OAuth2State, config: &ApplicationConfig) -> Cookie<'a> {
-    let expires =
-        OffsetDateTime::now_utc() + Duration::minutes(config.oauth_state_ttl_minutes as i64);
+fn generate_oauth_state_cookie<'a>(
+    state: OAuth2State,
+    config: &ApplicationConfig,
+    ttl_minutes: u64,
+) -> Cookie<'a> {
+    let expires = OffsetDateTime::now_utc() + Duration::minutes(ttl_minutes as i64);
     Cookie::build((
         config.oauth_state_cookie_name.to_string(),
         state.to_string(),
@@ -140,7 +205,7 @@ fn parse_oauth2_request(
 ) -> Result<OAuth2SignInInput> {
     let cookie_state = cookie
         .get(&config.oauth_state_cookie_name)
-        .ok_or(Error::Conflict("Invalit state".into()))?;
+        .ok_or(Error::InvalidOAuthState)?;
 
     let (st, cookie_state, code) = validate_and_parse!(
         state => OAuth2State::parse(query.state),
@@ -0,0 +1,54 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+
+use crate::{
+    ApiResponse, Error, Problem, Result,
+    app::AppState,
+    features::{
+        auth::{domain::OnboardingStep, handlers::OnboardingResponse},
+        shared::{AppUser, Credential, ensure_interactive},
+    },
+};
+
+#[utoipa::path(
+    put,
+    path = "/me/onboarding/{step}",
+    tag = "auth",
+    params(("step" = OnboardingStep, Path, description = "Checklist step to mark as done")),
+    security(("session_cookie" = [], "csrf_token" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "The updated checklist", body = ApiResponse<OnboardingResponse>),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Signed in with an API key, or CSRF token missing", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "No such step", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn complete_onboarding_step_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    Path(step): Path<String>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    let step =
+        OnboardingStep::parse(&step).ok_or(Error::NotFound("Onboarding step not found".into()))?;
+
+    let onboarding = state
+        .auth_service
+        .complete_onboarding_step(&user.id, step)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: OnboardingResponse::from(&onboarding),
+        }),
+    )
+        .into_response())
+}
@@ -0,0 +1,69 @@
+use axum::{Extension, Json, extract::State, http::StatusCode, response::IntoResponse};
+use axum_extra::extract::{SignedCookieJar, WithRejection};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::{
+    ApiResponse, Error, Problem, Result,
+    app::AppState,
+    features::{
+        auth::{domain::Password, service::reauthenticate::ReauthenticateInput},
+        shared::Credential,
+    },
+    validate_and_parse,
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReauthenticateRequest {
+    pub password: String,
+}
+
+impl TryFrom<ReauthenticateRequest> for ReauthenticateInput {
+    type Error = Error;
+
+    fn try_from(value: ReauthenticateRequest) -> std::result::Result<Self, Self::Error> {
+        let password = validate_and_parse!(
+            password => Password::parse(value.password),
+        );
+
+        Ok(ReauthenticateInput { password })
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/reauthenticate",
+    tag = "auth",
+    request_body = ReauthenticateRequest,
+    security(("session_cookie" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, description = "Session marked as recently authenticated", body = ApiResponse<String>),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not signed in, or wrong password", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not a cookie session, or missing CSRF token", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+/// Step-up for cookie sessions. Token clients re-authenticate by signing in
+/// again, which stamps a fresh `auth_time` into their access token.
+pub async fn reauthenticate_v1(
+    State(state): State<AppState>,
+    Extension(credential): Extension<Credential>,
+    jar: SignedCookieJar,
+    WithRejection(Json(data), _): WithRejection<Json<ReauthenticateRequest>, Error>,
+) -> Result<impl IntoResponse> {
+    if !matches!(credential, Credential::Session { .. }) {
+        return Err(Error::Forbidden);
+    }
+
+    let session_id = jar
+        .get(&state.config.session_cookie_name)
+        .ok_or(Error::Unauthorized)?;
+
+    state
+        .auth_service
+        .reauthenticate(session_id.value(), data.try_into()?)
+        .await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse { data: "Success" })).into_response())
+}
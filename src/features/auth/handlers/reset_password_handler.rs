@@ -1,15 +1,16 @@
 use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
 use axum_extra::extract::WithRejection;
 use serde::Deserialize;
+use utoipa::ToSchema;
 
 use crate::{
-    ApiResponse, Error, Result,
+    ApiResponse, Error, Problem, Result,
     app::AppState,
     features::auth::{Password, domain::EmailAddress, service::reset_password::ResetPasswordInput},
     validate_and_parse,
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct ResetPasswordRequest {
     pub email: String,
     pub token: String,
@@ -33,6 +34,17 @@ impl TryFrom<ResetPasswordRequest> for ResetPasswordInput {
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/reset-password",
+    tag = "auth",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 200, description = "Password changed", body = ApiResponse<String>),
+        (status = 400, description = "Invalid request or token", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
 pub async fn reset_password_v1(
     State(state): State<AppState>,
     WithRejection(Json(data), _): WithRejection<Json<ResetPasswordRequest>, Error>,
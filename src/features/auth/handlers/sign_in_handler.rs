@@ -4,10 +4,11 @@ use axum_extra::extract::{
     cookie::{Cookie, Expiration, SameSite},
 };
 use serde::Deserialize;
-use time::{Duration, OffsetDateTime};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
 
 use crate::{
-    ApiResponse, Error, Result,
+    ApiResponse, Error, Problem, Result,
     app::AppState,
     configuration::app_config::ApplicationConfig,
     features::auth::{
@@ -18,10 +19,13 @@ use crate::{
     validate_and_parse,
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct SignInRequest {
     pub email: String,
     pub password: String,
+    /// Keep the session cookie after the browser closes.
+    #[serde(default)]
+    pub remember_me: bool,
 }
 
 impl TryFrom<SignInRequest> for SignInInput {
@@ -33,40 +37,54 @@ impl TryFrom<SignInRequest> for SignInInput {
             password => Password::parse(value.password),
         );
 
-        Ok(SignInInput { email, password })
+        Ok(SignInInput {
+            email,
+            password,
+            remember_me: value.remember_me,
+        })
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/sign-in",
+    tag = "auth",
+    request_body = SignInRequest,
+    responses(
+        (status = 200, description = "Signed in; the session cookie is set", body = ApiResponse<UserResponse>, headers(("set-cookie" = String, description = "Signed session cookie"))),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Invalid credentials", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
 pub async fn sign_in_v1(
     State(state): State<AppState>,
     jar: SignedCookieJar,
     WithRejection(Json(data), _): WithRejection<Json<SignInRequest>, Error>,
 ) -> Result<impl IntoResponse> {
-    let (user, session_id) = state.auth_service.sign_in(data.try_into()?).await?;
-    let cookie = generate_session_cookie(session_id, &state.config);
+    let (user, session_id, session) = state.auth_service.sign_in(data.try_into()?).await?;
+    let cookie = generate_session_cookie(session_id, session.cookie_expires_at(), &state.config);
+    let onboarding = state.auth_service.get_onboarding(&user.id).await?;
 
     Ok((
         StatusCode::OK,
         jar.add(cookie),
         Json(ApiResponse {
-            data: UserResponse {
-                email: user.email.to_string(),
-                gender: user.gender,
-                last_name: user.last_name.map(|x| x.to_string()),
-                first_name: user.first_name.map(|x| x.to_string()),
-                role: user.role,
-            },
+            data: UserResponse::new(user, &onboarding),
         }),
     )
         .into_response())
 }
 
-pub fn generate_session_cookie<'a>(session_id: String, config: &ApplicationConfig) -> Cookie<'a> {
-    let expires = OffsetDateTime::now_utc() + Duration::minutes(config.session_ttl_minutes as i64);
-
+/// Builds the session cookie; without `expires_at` it is a browser-session cookie.
+pub fn generate_session_cookie<'a>(
+    session_id: String,
+    expires_at: Option<OffsetDateTime>,
+    config: &ApplicationConfig,
+) -> Cookie<'a> {
     Cookie::build((config.session_cookie_name.to_string(), session_id))
         .same_site(SameSite::Strict)
-        .expires(Expiration::from(expires))
+        .expires(Expiration::from(expires_at))
         .secure(config.cookie_secure)
         .path("/")
         .http_only(true)
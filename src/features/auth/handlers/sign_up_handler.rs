@@ -1,9 +1,10 @@
 use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
 use axum_extra::extract::WithRejection;
 use serde::Deserialize;
+use utoipa::ToSchema;
 
 use crate::{
-    ApiResponse, Error, Result,
+    ApiResponse, Error, Problem, Result,
     app::AppState,
     features::auth::{
         domain::{EmailAddress, FirstName, LastName, Password, UserGender},
@@ -12,12 +13,13 @@ use crate::{
     validate_and_parse,
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct SignUpRequest {
     pub email: String,
     pub password: String,
     pub first_name: Option<String>,
     pub last_name: Option<String>,
+    #[schema(value_type = Option<UserGender>)]
     pub gender: Option<String>,
 }
 
@@ -43,6 +45,20 @@ impl TryFrom<SignUpRequest> for SignUpInput {
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/sign-up",
+    tag = "auth",
+    request_body = SignUpRequest,
+    params(("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key and body replay the first successful response")),
+    responses(
+        (status = 201, description = "Account created; a verification email has been sent", body = ApiResponse<String>),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "An account with this email already exists, or a request with the same `Idempotency-Key` is in progress", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "`Idempotency-Key` reused with a different body", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
 pub async fn sign_up_v1(
     State(state): State<AppState>,
     WithRejection(Json(data), _): WithRejection<Json<SignUpRequest>, Error>,
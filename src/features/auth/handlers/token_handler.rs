@@ -0,0 +1,109 @@
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use axum_extra::extract::WithRejection;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::{
+    ApiResponse, Error, Problem, Result,
+    app::AppState,
+    features::auth::{
+        handlers::{TokenResponse, sign_in_handler::SignInRequest},
+        service::token::TokenPair,
+    },
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+impl From<TokenPair> for TokenResponse {
+    fn from(value: TokenPair) -> Self {
+        Self {
+            access_token: value.access_token,
+            refresh_token: value.refresh_token,
+            token_type: "Bearer".into(),
+            expires_in: value.expires_in,
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/token",
+    tag = "auth",
+    request_body = SignInRequest,
+    responses(
+        (status = 200, description = "Access and refresh tokens", body = ApiResponse<TokenResponse>),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Invalid credentials", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn token_sign_in_v1(
+    State(state): State<AppState>,
+    WithRejection(Json(data), _): WithRejection<Json<SignInRequest>, Error>,
+) -> Result<impl IntoResponse> {
+    let tokens = state.auth_service.token_sign_in(data.try_into()?).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: TokenResponse::from(tokens),
+        }),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/token/refresh",
+    tag = "auth",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "A new token pair; the old refresh token is spent", body = ApiResponse<TokenResponse>),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Refresh token is invalid, expired or reused", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn refresh_token_v1(
+    State(state): State<AppState>,
+    WithRejection(Json(data), _): WithRejection<Json<RefreshTokenRequest>, Error>,
+) -> Result<impl IntoResponse> {
+    let tokens = state
+        .auth_service
+        .refresh_token(&data.refresh_token)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: TokenResponse::from(tokens),
+        }),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/token/revoke",
+    tag = "auth",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "Refresh token revoked", body = ApiResponse<String>),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn revoke_token_v1(
+    State(state): State<AppState>,
+    WithRejection(Json(data), _): WithRejection<Json<RefreshTokenRequest>, Error>,
+) -> Result<impl IntoResponse> {
+    state
+        .auth_service
+        .revoke_refresh_token(&data.refresh_token)
+        .await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse { data: "Success" })).into_response())
+}
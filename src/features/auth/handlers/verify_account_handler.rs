@@ -2,15 +2,17 @@ use axum::Json;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use axum_extra::extract::WithRejection;
+use axum_extra::extract::{SignedCookieJar, WithRejection};
 use serde::Deserialize;
+use utoipa::ToSchema;
 
 use crate::app::AppState;
 use crate::features::auth::domain::EmailAddress;
+use crate::features::auth::handlers::{UserResponse, generate_session_cookie};
 use crate::features::auth::service::verify_account::VerifyAccountInput;
-use crate::{ApiResponse, Error, Result, validate_and_parse};
+use crate::{ApiResponse, Error, Problem, Result, validate_and_parse};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct VerifyAccountRequest {
     pub email: String,
     pub token: String,
@@ -29,17 +31,32 @@ impl TryInto<VerifyAccountInput> for VerifyAccountRequest {
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/verify-account",
+    tag = "auth",
+    request_body = VerifyAccountRequest,
+    responses(
+        (status = 200, description = "Account verified and signed in; the session cookie is set", body = ApiResponse<UserResponse>, headers(("set-cookie" = String, description = "Signed session cookie"))),
+        (status = 400, description = "Invalid request or token", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
 pub async fn verify_account_v1(
     State(state): State<AppState>,
+    jar: SignedCookieJar,
     WithRejection(Json(data), _): WithRejection<Json<VerifyAccountRequest>, Error>,
 ) -> Result<impl IntoResponse> {
-    state.auth_service.verify_account(data.try_into()?).await?;
+    let (user, session_id, session) = state.auth_service.verify_account(data.try_into()?).await?;
+    let cookie = generate_session_cookie(session_id, session.cookie_expires_at(), &state.config);
+    let onboarding = state.auth_service.get_onboarding(&user.id).await?;
 
     Ok((
-              StatusCode::OK,
-              Json(ApiResponse {
-                             data: "Your account has been successfully verified! Please log in now using your credentials.",
-              }),
-          )
-              .into_response())
+        StatusCode::OK,
+        jar.add(cookie),
+        Json(ApiResponse {
+            data: UserResponse::new(user, &onboarding),
+        }),
+    )
+        .into_response())
 }
@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{Result, app::AppState, jobs::Job};
+
+/// Runs on `jobs.cleanup_unverified_accounts_schedule`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CleanupUnverifiedAccounts;
+
+#[async_trait]
+impl Job for CleanupUnverifiedAccounts {
+    const KIND: &'static str = "auth.cleanup_unverified_accounts";
+    const MAX_ATTEMPTS: i32 = 3;
+
+    async fn run(self, state: &AppState) -> Result<()> {
+        let deleted = state
+            .auth_service
+            .delete_expired_unverified_accounts()
+            .await?;
+        info!(deleted, "Deleted expired unverified accounts");
+
+        Ok(())
+    }
+}
+
+/// Runs on `jobs.cleanup_expired_sessions_schedule`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CleanupExpiredSessions;
+
+#[async_trait]
+impl Job for CleanupExpiredSessions {
+    const KIND: &'static str = "auth.cleanup_expired_sessions";
+    const MAX_ATTEMPTS: i32 = 3;
+
+    async fn run(self, state: &AppState) -> Result<()> {
+        let deleted = state.auth_service.delete_expired_sessions().await?;
+        info!(deleted, "Deleted expired sessions");
+
+        Ok(())
+    }
+}
@@ -2,35 +2,51 @@ use std::sync::Arc;
 
 use axum::{
     Router, middleware,
-    routing::{get, post},
+    routing::{delete, get, post, put},
 };
 use redis::aio::MultiplexedConnection;
 use reqwest::Client;
 use sqlx::PgPool;
-use tower_governor::{GovernorLayer, governor::GovernorConfigBuilder};
 
 use crate::{
     app::AppState,
     clients::email_client::EmailClient,
     configuration::{
-        app_config::ApplicationConfig, oauth2_config::OAuth2Config,
-        ratelimit_config::RateLimitConfig,
+        app_config::ApplicationConfig,
+        oauth2_config::OAuth2Config,
+        ratelimit_config::{RateLimitConfig, RateLimitRule},
+        reload::SettingsHandle,
     },
+    events::EventBus,
     features::auth::repository::AuthRepository,
-    middlewares::authenticate,
+    middlewares::{RateLimitLayer, authenticate, idempotency, identify},
 };
 
 mod constants;
 mod domain;
+mod events;
 mod handlers;
+mod jobs;
 mod repository;
 mod service;
+mod session_store;
+mod subscribers;
 
 pub use constants::*;
 pub use domain::*;
+pub use events::{PasswordReset, SignInMethod, UserSignedIn, UserSignedUp, UserVerified};
+pub use jobs::{CleanupExpiredSessions, CleanupUnverifiedAccounts};
 
-pub use handlers::{UserResponse, generate_session_cookie};
+pub use handlers::{
+    ApiKeyResponse, AuthApi, CreatedApiKeyResponse, CsrfTokenResponse, OnboardingResponse,
+    TokenResponse, UserResponse, generate_csrf_cookie, generate_session_cookie,
+};
 pub use service::AuthService;
+pub use session_store::{
+    FallbackSessionStore, MemorySessionStore, PostgresSessionStore, RedisSessionStore, Session,
+    SessionStore, build_session_store,
+};
+pub use subscribers::OnboardingPipeline;
 
 use handlers::*;
 
@@ -39,143 +55,143 @@ pub struct AuthModule {
 }
 
 impl AuthModule {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         app_config: ApplicationConfig,
         oauth2_config: OAuth2Config,
+        settings: SettingsHandle,
         pool: PgPool,
         redis: MultiplexedConnection,
         email_client: Arc<EmailClient>,
         http_client: Client,
+        events: EventBus,
     ) -> Self {
+        let sessions = build_session_store(app_config.session_store, redis.clone(), pool.clone());
         let repository = AuthRepository::new(pool);
 
         Self {
             auth_service: AuthService::new(
                 app_config,
                 oauth2_config,
+                settings,
                 redis,
+                sessions,
                 email_client,
                 http_client,
                 repository,
+                events,
             ),
         }
     }
 
-    pub fn v1(state: AppState, ratelimit: &RateLimitConfig) -> Router<AppState> {
+    pub fn v1(state: AppState) -> Router<AppState> {
+        let limit = |name: &'static str, rule: fn(&RateLimitConfig) -> &RateLimitRule| {
+            RateLimitLayer::new(state.redis.clone(), state.settings.clone(), name, rule)
+        };
+        // Identify before rate limiting so per-user limits see the caller,
+        // but reject anonymous requests only after they have been counted.
+        let identify = || middleware::from_fn_with_state(state.clone(), identify);
+        let idempotent = || middleware::from_fn_with_state(state.clone(), idempotency);
+
         Router::new()
             .route(
                 "/sign-up",
-                post(sign_up_v1).layer(GovernorLayer::new(
-                    GovernorConfigBuilder::default()
-                        .per_second(60)
-                        .burst_size(ratelimit.sign_up)
-                        .finish()
-                        .unwrap(),
-                )),
+                post(sign_up_v1)
+                    .route_layer(idempotent())
+                    .layer(limit("sign_up", |r| &r.sign_up)),
             )
             .route(
                 "/verify-account",
-                post(verify_account_v1).layer(GovernorLayer::new(
-                    GovernorConfigBuilder::default()
-                        .per_second(60)
-                        .burst_size(ratelimit.verify_account)
-                        .finish()
-                        .unwrap(),
-                )),
+                post(verify_account_v1).layer(limit("verify_account", |r| &r.verify_account)),
             )
             .route(
                 "/sign-in",
-                post(sign_in_v1).layer(GovernorLayer::new(
-                    GovernorConfigBuilder::default()
-                        .per_second(60)
-                        .burst_size(ratelimit.sign_in)
-                        .finish()
-                        .unwrap(),
-                )),
+                post(sign_in_v1).layer(limit("sign_in", |r| &r.sign_in)),
+            )
+            .route(
+                "/token",
+                post(token_sign_in_v1).layer(limit("token", |r| &r.sign_in)),
+            )
+            .route(
+                "/token/refresh",
+                post(refresh_token_v1).layer(limit("refresh_token", |r| &r.refresh_token)),
+            )
+            .route(
+                "/token/revoke",
+                post(revoke_token_v1).layer(limit("revoke_token", |r| &r.refresh_token)),
             )
             .route(
                 "/forgot-password",
-                post(forgot_password_v1).layer(GovernorLayer::new(
-                    GovernorConfigBuilder::default()
-                        .per_second(60)
-                        .burst_size(ratelimit.forgot_password)
-                        .finish()
-                        .unwrap(),
-                )),
+                post(forgot_password_v1)
+                    .route_layer(idempotent())
+                    .layer(limit("forgot_password", |r| &r.forgot_password)),
             )
             .route(
                 "/reset-password",
-                post(reset_password_v1).layer(GovernorLayer::new(
-                    GovernorConfigBuilder::default()
-                        .per_second(60)
-                        .burst_size(ratelimit.reset_password)
-                        .finish()
-                        .unwrap(),
-                )),
+                post(reset_password_v1).layer(limit("reset_password", |r| &r.reset_password)),
             )
             .route(
                 "/google",
-                get(get_google_redirect_url_v1).layer(GovernorLayer::new(
-                    GovernorConfigBuilder::default()
-                        .per_second(60)
-                        .burst_size(ratelimit.sign_in)
-                        .finish()
-                        .unwrap(),
-                )),
+                get(get_google_redirect_url_v1).layer(limit("google", |r| &r.sign_in)),
             )
             .route(
                 "/google/callback",
-                get(google_sign_in_v1).layer(GovernorLayer::new(
-                    GovernorConfigBuilder::default()
-                        .per_second(60)
-                        .burst_size(ratelimit.sign_in)
-                        .finish()
-                        .unwrap(),
-                )),
+                get(google_sign_in_v1).layer(limit("google_callback", |r| &r.sign_in)),
             )
             .route(
                 "/facebook",
-                get(get_facebook_redirect_url_v1).layer(GovernorLayer::new(
-                    GovernorConfigBuilder::default()
-                        .per_second(60)
-                        .burst_size(ratelimit.sign_in)
-                        .finish()
-                        .unwrap(),
-                )),
+                get(get_facebook_redirect_url_v1).layer(limit("facebook", |r| &r.sign_in)),
             )
             .route(
                 "/facebook/callback",
-                get(facebook_sign_in_v1).layer(GovernorLayer::new(
-                    GovernorConfigBuilder::default()
-                        .per_second(60)
-                        .burst_size(ratelimit.sign_in)
-                        .finish()
-                        .unwrap(),
-                )),
+                get(facebook_sign_in_v1).layer(limit("facebook_callback", |r| &r.sign_in)),
             )
             .route(
                 "/logout",
                 post(logout_v1)
-                    .route_layer(middleware::from_fn_with_state(state.clone(), authenticate))
-                    .layer(GovernorLayer::new(
-                        GovernorConfigBuilder::default()
-                            .per_second(60)
-                            .burst_size(ratelimit.logout)
-                            .finish()
-                            .unwrap(),
-                    )),
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit("logout", |r| &r.logout))
+                    .layer(identify()),
+            )
+            .route(
+                "/reauthenticate",
+                post(reauthenticate_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit("reauthenticate", |r| &r.sign_in))
+                    .layer(identify()),
+            )
+            .route(
+                "/csrf-token",
+                get(get_csrf_token_v1).layer(limit("csrf_token", |r| &r.get_me)),
             )
             .route(
                 "/me",
                 get(get_me_v1)
-                    .route_layer(middleware::from_fn_with_state(state.clone(), authenticate))
-                    .layer(GovernorLayer::new(
-                        GovernorConfigBuilder::default()
-                            .per_second(60)
-                            .burst_size(ratelimit.get_me)
-                            .finish()
-                            .unwrap(),
-                    )),
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit("get_me", |r| &r.get_me))
+                    .layer(identify()),
+            )
+            .route(
+                "/me/onboarding/{step}",
+                put(complete_onboarding_step_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit("onboarding", |r| &r.get_me))
+                    .layer(identify()),
+            )
+            .route(
+                "/api-keys",
+                get(list_api_keys_v1)
+                    .post(create_api_key_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit("api_keys", |r| &r.api_keys))
+                    .layer(identify()),
+            )
+            .route(
+                "/api-keys/{id}",
+                delete(revoke_api_key_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit("api_keys", |r| &r.api_keys))
+                    .layer(identify()),
             )
     }
 }
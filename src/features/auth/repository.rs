@@ -1,14 +1,21 @@
-use sqlx::{PgPool, query};
+use std::net::IpAddr;
+
+use sqlx::{PgConnection, PgPool, Postgres, Transaction, query, query_as};
+use time::OffsetDateTime;
 use tracing::instrument;
+use uuid::Uuid;
 
 use crate::{
-    Result,
+    Error, Result,
     features::auth::{
         EmailAddress, FacebookID, FirstName, GoogleID, HashedPassword, LastName,
-        domain::{NewUser, UpdateUser, User, UserGender, UserID, UserRole},
+        domain::{
+            ApiKey, ApiKeyScope, NewApiKey, NewRefreshToken, NewUser, Onboarding, OnboardingStep,
+            RefreshToken, UpdateUser, User, UserGender, UserID, UserRole,
+        },
     },
 };
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AuthRepository {
     pool: PgPool,
 }
@@ -18,6 +25,12 @@ impl AuthRepository {
         Self { pool }
     }
 
+    /// For changes that publish events with
+    /// [`EventBus::publish_in`](crate::events::EventBus::publish_in).
+    pub async fn begin(&self) -> Result<Transaction<'static, Postgres>> {
+        Ok(self.pool.begin().await?)
+    }
+
     #[instrument(skip_all, name = "authrepository - get user by email")]
     pub async fn get_user_by_email(&self, email: &EmailAddress) -> Result<Option<User>> {
         let record = query!(
@@ -117,7 +130,7 @@ impl AuthRepository {
     }
 
     #[instrument(skip_all, name = "authrepository - create user")]
-    pub async fn create_user(&self, user: &NewUser) -> Result<UserID> {
+    pub async fn create_user(&self, conn: &mut PgConnection, user: &NewUser) -> Result<UserID> {
         let record = query!(
             r#"
                 INSERT INTO users (email, password, first_name, last_name, gender, google_id, facebook_id, is_verified)
@@ -134,14 +147,46 @@ impl AuthRepository {
             user.facebook_id.as_ref().map(|f| f.as_ref()),
             user.is_verified
         )
-        .fetch_one(&self.pool)
+        .fetch_one(conn)
         .await?;
 
         Ok(UserID::from(record.id))
     }
 
-    #[instrument(skip_all, name = "authrepository - update user")]
+    /// Only accounts from `sign_up`; OAuth accounts carry a provider ID.
+    #[instrument(skip_all, name = "authrepository - delete unverified users")]
+    pub async fn delete_unverified_users_created_before(
+        &self,
+        cutoff: OffsetDateTime,
+    ) -> Result<u64> {
+        let result = query!(
+            r#"
+                DELETE FROM users
+                WHERE is_verified = false
+                    AND google_id IS NULL
+                    AND facebook_id IS NULL
+                    AND created_at < $1
+            "#,
+            cutoff
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     pub async fn update_user(&self, id: &UserID, user: UpdateUser) -> Result<()> {
+        self.update_user_in(&mut *self.pool.acquire().await?, id, user)
+            .await
+    }
+
+    #[instrument(skip_all, name = "authrepository - update user")]
+    pub async fn update_user_in(
+        &self,
+        conn: &mut PgConnection,
+        id: &UserID,
+        user: UpdateUser,
+    ) -> Result<()> {
         query!(
             r#"
               UPDATE users
@@ -164,9 +209,296 @@ impl AuthRepository {
             user.is_verified,
             id.as_ref()
         )
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, name = "authrepository - create refresh token")]
+    pub async fn create_refresh_token(&self, token: &NewRefreshToken) -> Result<()> {
+        query!(
+            r#"
+                INSERT INTO refresh_tokens (user_id, family_id, token_hash, authenticated_at, expires_at)
+                VALUES ($1, $2, $3, $4, $5)
+            "#,
+            token.user_id.as_ref(),
+            token.family_id,
+            token.token_hash,
+            token.authenticated_at,
+            token.expires_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, name = "authrepository - get refresh token by hash")]
+    pub async fn get_refresh_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RefreshToken>> {
+        let record = query!(
+            r#"
+                SELECT id, user_id, family_id, authenticated_at, expires_at, used_at, revoked_at
+                FROM refresh_tokens
+                WHERE token_hash = $1
+            "#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record.map(|r| RefreshToken {
+            id: r.id,
+            user_id: UserID::from(r.user_id),
+            family_id: r.family_id,
+            authenticated_at: r.authenticated_at,
+            expires_at: r.expires_at,
+            used_at: r.used_at,
+            revoked_at: r.revoked_at,
+        }))
+    }
+
+    /// Marks a refresh token as used. Returns `false` when another request
+    /// already used it, which callers must treat as token reuse.
+    #[instrument(skip_all, name = "authrepository - mark refresh token used")]
+    pub async fn mark_refresh_token_used(&self, id: &Uuid) -> Result<bool> {
+        let result = query!(
+            r#"
+                UPDATE refresh_tokens
+                SET used_at = NOW()
+                WHERE id = $1 AND used_at IS NULL AND revoked_at IS NULL
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[instrument(skip_all, name = "authrepository - revoke refresh token family")]
+    pub async fn revoke_refresh_token_family(&self, family_id: &Uuid) -> Result<()> {
+        query!(
+            r#"
+                UPDATE refresh_tokens
+                SET revoked_at = NOW()
+                WHERE family_id = $1 AND revoked_at IS NULL
+            "#,
+            family_id
+        )
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
+
+    #[instrument(skip_all, name = "authrepository - create api key")]
+    pub async fn create_api_key(&self, key: &NewApiKey) -> Result<ApiKey> {
+        let scopes: Vec<String> = key.scopes.iter().map(|s| s.to_string()).collect();
+
+        let record = query_as!(
+            ApiKeyRow,
+            r#"
+                INSERT INTO api_keys (user_id, name, prefix, key_hash, scopes, expires_at)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING id, user_id, name, prefix, key_hash, scopes, created_at, expires_at, last_used_at, last_used_ip, revoked_at
+            "#,
+            key.user_id.as_ref(),
+            key.name.as_ref(),
+            key.prefix,
+            key.key_hash,
+            &scopes,
+            key.expires_at
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        record.try_into()
+    }
+
+    #[instrument(skip_all, name = "authrepository - get api keys by user id")]
+    pub async fn get_api_keys_by_user_id(&self, user_id: &UserID) -> Result<Vec<ApiKey>> {
+        let records = query_as!(
+            ApiKeyRow,
+            r#"
+                SELECT id, user_id, name, prefix, key_hash, scopes, created_at, expires_at, last_used_at, last_used_ip, revoked_at
+                FROM api_keys
+                WHERE user_id = $1
+                ORDER BY created_at DESC
+            "#,
+            user_id.as_ref()
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        records.into_iter().map(ApiKey::try_from).collect()
+    }
+
+    #[instrument(skip_all, name = "authrepository - get api key by prefix")]
+    pub async fn get_api_key_by_prefix(&self, prefix: &str) -> Result<Option<ApiKey>> {
+        let record = query_as!(
+            ApiKeyRow,
+            r#"
+                SELECT id, user_id, name, prefix, key_hash, scopes, created_at, expires_at, last_used_at, last_used_ip, revoked_at
+                FROM api_keys
+                WHERE prefix = $1
+            "#,
+            prefix
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        record.map(ApiKey::try_from).transpose()
+    }
+
+    #[instrument(skip_all, name = "authrepository - revoke api key")]
+    pub async fn revoke_api_key(&self, id: &Uuid, user_id: &UserID) -> Result<bool> {
+        let result = query!(
+            r#"
+                UPDATE api_keys
+                SET revoked_at = NOW()
+                WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+            "#,
+            id,
+            user_id.as_ref()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Records key usage, at most once per minute per address to avoid a write on every request.
+    #[instrument(skip_all, name = "authrepository - touch api key")]
+    pub async fn touch_api_key(&self, id: &Uuid, ip: &IpAddr) -> Result<()> {
+        query!(
+            r#"
+                UPDATE api_keys
+                SET last_used_at = NOW(), last_used_ip = $2
+                WHERE id = $1
+                  AND (last_used_at IS NULL OR last_used_at < NOW() - INTERVAL '1 minute'
+                       OR last_used_ip IS DISTINCT FROM $2)
+            "#,
+            id,
+            ip.to_string()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, name = "authrepository - get onboarding")]
+    pub async fn get_onboarding(&self, user_id: &UserID) -> Result<Option<Onboarding>> {
+        let onboarding = query_as!(
+            Onboarding,
+            r#"
+                SELECT profile_completed_at, shoe_size_set_at, preferences_chosen_at
+                FROM user_onboarding
+                WHERE user_id = $1
+            "#,
+            user_id.as_ref()
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(onboarding)
+    }
+
+    /// Creates the checklist unless the user already has one.
+    #[instrument(skip_all, name = "authrepository - start onboarding")]
+    pub async fn start_onboarding(&self, user_id: &UserID, profile_completed: bool) -> Result<()> {
+        query!(
+            r#"
+                INSERT INTO user_onboarding (user_id, profile_completed_at)
+                VALUES ($1, CASE WHEN $2 THEN NOW() END)
+                ON CONFLICT (user_id) DO NOTHING
+            "#,
+            user_id.as_ref(),
+            profile_completed
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Checks off `step`, keeping the time it was first done.
+    #[instrument(skip_all, name = "authrepository - complete onboarding step")]
+    pub async fn complete_onboarding_step(
+        &self,
+        user_id: &UserID,
+        step: OnboardingStep,
+    ) -> Result<Onboarding> {
+        let onboarding = query_as!(
+            Onboarding,
+            r#"
+                INSERT INTO user_onboarding
+                    (user_id, profile_completed_at, shoe_size_set_at, preferences_chosen_at)
+                VALUES (
+                    $1,
+                    CASE WHEN $2 = 'profile_completed' THEN NOW() END,
+                    CASE WHEN $2 = 'shoe_size_set' THEN NOW() END,
+                    CASE WHEN $2 = 'preferences_chosen' THEN NOW() END
+                )
+                ON CONFLICT (user_id) DO UPDATE
+                SET profile_completed_at = COALESCE(
+                        user_onboarding.profile_completed_at, EXCLUDED.profile_completed_at),
+                    shoe_size_set_at = COALESCE(
+                        user_onboarding.shoe_size_set_at, EXCLUDED.shoe_size_set_at),
+                    preferences_chosen_at = COALESCE(
+                        user_onboarding.preferences_chosen_at, EXCLUDED.preferences_chosen_at),
+                    updated_at = NOW()
+                RETURNING profile_completed_at, shoe_size_set_at, preferences_chosen_at
+            "#,
+            user_id.as_ref(),
+            step.as_str()
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(onboarding)
+    }
+}
+
+/// An `api_keys` row as stored, before scopes and the last IP are parsed.
+struct ApiKeyRow {
+    id: Uuid,
+    user_id: Uuid,
+    name: String,
+    prefix: String,
+    key_hash: String,
+    scopes: Vec<String>,
+    created_at: OffsetDateTime,
+    expires_at: Option<OffsetDateTime>,
+    last_used_at: Option<OffsetDateTime>,
+    last_used_ip: Option<String>,
+    revoked_at: Option<OffsetDateTime>,
+}
+
+impl TryFrom<ApiKeyRow> for ApiKey {
+    type Error = Error;
+
+    fn try_from(row: ApiKeyRow) -> Result<Self> {
+        Ok(ApiKey {
+            id: row.id,
+            user_id: UserID::from(row.user_id),
+            name: row.name,
+            prefix: row.prefix,
+            key_hash: row.key_hash,
+            scopes: row
+                .scopes
+                .into_iter()
+                .map(ApiKeyScope::parse)
+                .collect::<Result<_>>()?,
+            created_at: row.created_at,
+            expires_at: row.expires_at,
+            last_used_at: row.last_used_at,
+            last_used_ip: row.last_used_ip.and_then(|ip| ip.parse().ok()),
+            revoked_at: row.revoked_at,
+        })
+    }
 }
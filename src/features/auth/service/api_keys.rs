@@ -0,0 +1,93 @@
+use std::net::IpAddr;
+
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    Error, Result,
+    common::hash_token,
+    features::{
+        auth::{
+            ApiKey, ApiKeyExpiry, ApiKeyName, ApiKeyScope, AuthService, NewApiKey, PlainApiKey,
+            UserID,
+        },
+        shared::AppUser,
+    },
+};
+
+pub struct CreateApiKeyInput {
+    pub name: ApiKeyName,
+    pub scopes: Vec<ApiKeyScope>,
+    pub expires_at: Option<ApiKeyExpiry>,
+}
+
+impl AuthService {
+    #[instrument(
+        name = "auth.create_api_key",
+        skip(self, data),
+        fields(user_id = %user_id)
+    )]
+    pub async fn create_api_key(
+        &self,
+        user_id: &UserID,
+        data: CreateApiKeyInput,
+    ) -> Result<(ApiKey, String)> {
+        let key = PlainApiKey::generate();
+
+        let api_key = self
+            .repository
+            .create_api_key(&NewApiKey {
+                user_id: user_id.clone(),
+                name: data.name,
+                prefix: key.prefix,
+                key_hash: hash_token(&key.value),
+                scopes: data.scopes,
+                expires_at: data.expires_at.map(ApiKeyExpiry::into_inner),
+            })
+            .await?;
+
+        Ok((api_key, key.value))
+    }
+
+    #[instrument(name = "auth.list_api_keys", skip(self), fields(user_id = %user_id))]
+    pub async fn list_api_keys(&self, user_id: &UserID) -> Result<Vec<ApiKey>> {
+        self.repository.get_api_keys_by_user_id(user_id).await
+    }
+
+    #[instrument(name = "auth.revoke_api_key", skip(self), fields(user_id = %user_id))]
+    pub async fn revoke_api_key(&self, user_id: &UserID, id: &Uuid) -> Result<()> {
+        if !self.repository.revoke_api_key(id, user_id).await? {
+            return Err(Error::NotFound("API key not found".into()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn authenticate_api_key(
+        &self,
+        value: &str,
+        client_ip: &IpAddr,
+    ) -> Result<(AppUser, Vec<ApiKeyScope>)> {
+        let prefix = PlainApiKey::prefix_of(value).ok_or(Error::Unauthorized)?;
+
+        let api_key = self
+            .repository
+            .get_api_key_by_prefix(prefix)
+            .await?
+            .filter(|k| k.key_hash == hash_token(value) && k.is_active())
+            .ok_or(Error::Unauthorized)?;
+
+        let user = self
+            .repository
+            .get_user_by_id(&api_key.user_id)
+            .await?
+            .filter(|u| !u.is_banned && u.is_verified)
+            .ok_or(Error::Unauthorized)?;
+
+        self.repository
+            .touch_api_key(&api_key.id, client_ip)
+            .await?;
+
+        Ok((user.into(), api_key.scopes))
+    }
+}
@@ -0,0 +1,12 @@
+use tracing::instrument;
+
+use crate::{Result, features::auth::AuthService};
+
+impl AuthService {
+    /// Deletes sessions that can no longer be used. Only stores without
+    /// their own expiry (Postgres, memory) have anything to delete.
+    #[instrument(name = "auth.delete_expired_sessions", skip(self))]
+    pub async fn delete_expired_sessions(&self) -> Result<u64> {
+        self.sessions.remove_expired().await
+    }
+}
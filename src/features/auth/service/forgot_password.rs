@@ -32,7 +32,7 @@ impl AuthService {
                 redis.set_ex(
                     self.generate_redis_key(super::KeyType::ResetPassword, &token),
                     user.id.to_string(),
-                    self.app_config.reset_password_ttl_minutes * 60
+                    self.settings.current().reset_password_ttl_minutes * 60
                 ),
                 self.email_client
                     .send_account_verification_email(user.email.as_ref(), &token)
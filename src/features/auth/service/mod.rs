@@ -1,4 +1,4 @@
-use std::{fmt::Display, sync::Arc};
+use std::{fmt::Display, sync::Arc, time::Duration};
 
 use redis::{AsyncTypedCommands, aio::MultiplexedConnection};
 use reqwest::Client;
@@ -7,36 +7,48 @@ use uuid::Uuid;
 use crate::{
     Error, Result,
     clients::email_client::EmailClient,
-    configuration::{app_config::ApplicationConfig, oauth2_config::OAuth2Config},
+    configuration::{
+        app_config::ApplicationConfig, oauth2_config::OAuth2Config, reload::SettingsHandle,
+    },
+    events::EventBus,
     features::auth::{
-        EmailAddress, REDIS_ACCOUNT_VERIFICATION_PREFIX, REDIS_RESET_PASSWORD_PREFIX,
-        REDIS_SESSION_PREFIX, User, UserID, repository::AuthRepository,
+        EmailAddress, REDIS_ACCOUNT_VERIFICATION_PREFIX, REDIS_RESET_PASSWORD_PREFIX, User, UserID,
+        repository::AuthRepository,
+        session_store::{Session, SessionStore},
     },
 };
 
+pub mod api_keys;
 pub mod authenticate;
+pub mod expired_sessions;
 pub mod forgot_password;
 pub mod logout;
 pub mod oauth2;
+pub mod onboarding;
+pub mod reauthenticate;
 pub mod reset_password;
 pub mod sign_in;
 pub mod sign_up;
+pub mod token;
+pub mod unverified_accounts;
 pub mod verify_account;
 
 pub struct AuthService {
     app_config: ApplicationConfig,
     oauth2_config: OAuth2Config,
+    settings: SettingsHandle,
     redis: MultiplexedConnection,
+    sessions: Arc<dyn SessionStore>,
     email_client: Arc<EmailClient>,
     repository: AuthRepository,
     http_client: Client,
+    events: EventBus,
 }
 
 #[derive(Debug)]
 enum KeyType {
     Verification,
     ResetPassword,
-    Session,
 }
 
 #[derive(Debug)]
@@ -46,37 +58,56 @@ enum TokenType {
 }
 
 impl AuthService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         app_config: ApplicationConfig,
         oauth2_config: OAuth2Config,
+        settings: SettingsHandle,
         redis: MultiplexedConnection,
+        sessions: Arc<dyn SessionStore>,
         email_client: Arc<EmailClient>,
         http_client: Client,
         repository: AuthRepository,
+        events: EventBus,
     ) -> Self {
         Self {
             app_config,
             oauth2_config,
+            settings,
             redis,
+            sessions,
             email_client,
             http_client,
             repository,
+            events,
         }
     }
 
-    async fn generate_session(&self, user_id: &UserID) -> Result<String> {
+    async fn generate_session(
+        &self,
+        user_id: &UserID,
+        persistent: bool,
+    ) -> Result<(String, Session)> {
         let session_id = Uuid::new_v4().to_string();
-        let mut redis = self.redis.clone();
+        let session = Session::new(user_id.clone(), self.session_lifetime(), persistent);
 
-        redis
-            .set_ex(
-                self.generate_redis_key(KeyType::Session, &session_id),
-                user_id.to_string(),
-                self.app_config.session_ttl_minutes * 60,
-            )
+        self.sessions
+            .insert(&session_id, &session, self.session_idle_timeout())
             .await?;
 
-        Ok(session_id)
+        Ok((session_id, session))
+    }
+
+    fn session_lifetime(&self) -> Duration {
+        Duration::from_secs(self.settings.current().session_ttl_minutes * 60)
+    }
+
+    fn session_idle_timeout(&self) -> Duration {
+        Duration::from_secs(self.settings.current().session_idle_timeout_minutes * 60)
+    }
+
+    pub fn reauthentication_max_age(&self) -> Duration {
+        Duration::from_secs(self.settings.current().reauthentication_max_age_minutes * 60)
     }
 
     async fn get_user_by_token(
@@ -95,9 +126,9 @@ impl AuthService {
         let user_id = redis
             .get_del(self.generate_redis_key(key_type, token))
             .await?
-            .map(|id| UserID::parse(&id).map_err(|_| Error::Conflict("Invalid token".into())))
+            .map(|id| UserID::parse(&id).map_err(|_| Error::InvalidToken))
             .transpose()?
-            .ok_or(Error::Conflict("Invalid token".into()))?;
+            .ok_or(Error::InvalidToken)?;
 
         let user = self
             .repository
@@ -111,7 +142,7 @@ impl AuthService {
                         _ => true,
                     }
             })
-            .ok_or(Error::Conflict("Invalid token".into()))?;
+            .ok_or(Error::InvalidToken)?;
 
         Ok(user)
     }
@@ -119,7 +150,6 @@ impl AuthService {
     fn generate_redis_key<T: Display>(&self, key_type: KeyType, value: T) -> String {
         match key_type {
             KeyType::Verification => format!("{}{}", REDIS_ACCOUNT_VERIFICATION_PREFIX, value),
-            KeyType::Session => format!("{}{}", REDIS_SESSION_PREFIX, value),
             KeyType::ResetPassword => format!("{}{}", REDIS_RESET_PASSWORD_PREFIX, value),
         }
     }
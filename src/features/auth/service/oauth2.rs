@@ -1,12 +1,16 @@
 use reqwest::Url;
+use tracing::instrument;
 
 use crate::{
     Error, Result,
+    clients::http_client::PropagateTrace,
     features::auth::{
         AuthService, FacebookAccessTokenResponse, FacebookUserResponse, GoogleAccessTokenError,
         GoogleAccessTokenResponse, GoogleAccessTokenSuccess, GoogleUserResponse, NewUser,
-        OAuth2Code, OAuth2State, UpdateUser,
+        OAuth2Code, OAuth2State, SignInMethod, UpdateUser, UserID, UserSignedUp,
+        session_store::Session,
     },
+    features::metrics::{AuthFlow, record_auth_failure, record_auth_success},
 };
 
 pub enum OAuth2Provider {
@@ -14,6 +18,22 @@ pub enum OAuth2Provider {
     Google,
 }
 
+impl OAuth2Provider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OAuth2Provider::Facebook => "facebook",
+            OAuth2Provider::Google => "google",
+        }
+    }
+
+    fn sign_in_method(&self) -> SignInMethod {
+        match self {
+            OAuth2Provider::Facebook => SignInMethod::Facebook,
+            OAuth2Provider::Google => SignInMethod::Google,
+        }
+    }
+}
+
 pub struct OAuth2SignInInput {
     pub state: OAuth2State,
     pub cookie_state: OAuth2State,
@@ -42,27 +62,39 @@ impl AuthService {
         &self,
         provider: OAuth2Provider,
         data: OAuth2SignInInput,
-    ) -> Result<(String, Option<String>)> {
+    ) -> Result<(String, Session, Option<String>)> {
         if data.state != data.cookie_state {
-            return Err(Error::Conflict("Something went wrong".into()));
+            record_auth_failure(AuthFlow::OAuth2, provider.as_str(), "state_mismatch");
+            return Err(Error::InvalidOAuthState);
         }
         let redirect_path = data.state.into_inner().1;
 
-        match provider {
-            OAuth2Provider::Google => {
-                let session_id = self.google_sign_in(data.code).await?;
-
-                Ok((session_id, redirect_path))
-            }
-            OAuth2Provider::Facebook => {
-                let session_id = self.facebook_sign_in(data.code).await?;
+        let result = match provider {
+            OAuth2Provider::Google => self.google_sign_in(data.code).await,
+            OAuth2Provider::Facebook => self.facebook_sign_in(data.code).await,
+        };
 
-                Ok((session_id, redirect_path))
+        match &result {
+            Ok(_) => record_auth_success(AuthFlow::OAuth2, provider.as_str()),
+            Err(err) => {
+                let reason = match err {
+                    Error::EmailNotVerified => "email_not_verified",
+                    Error::Reqwest(_) => "provider_unreachable",
+                    Error::Internal(_) => "provider_error",
+                    _ => "error",
+                };
+                record_auth_failure(AuthFlow::OAuth2, provider.as_str(), reason);
             }
         }
+
+        let (session_id, session) = result?;
+        self.publish_signed_in(&session.user_id, provider.sign_in_method())
+            .await;
+
+        Ok((session_id, session, redirect_path))
     }
 
-    async fn google_sign_in(&self, code: OAuth2Code) -> Result<String> {
+    async fn google_sign_in(&self, code: OAuth2Code) -> Result<(String, Session)> {
         let google_user = self.get_google_user(code).await?;
         let db_user = self
             .repository
@@ -81,9 +113,17 @@ impl AuthService {
                     hashed_password: None,
                     is_verified: true,
                 };
-                let user_id = self.repository.create_user(&new_user).await?;
-
-                self.generate_session(&user_id).await
+                let mut tx = self.repository.begin().await?;
+                let user_id = self.repository.create_user(&mut tx, &new_user).await?;
+                self.events
+                    .publish_in(
+                        &mut tx,
+                        &oauth2_sign_up(&user_id, &new_user, SignInMethod::Google),
+                    )
+                    .await?;
+                tx.commit().await?;
+
+                self.generate_session(&user_id, true).await
             }
             Some(db_user) => {
                 if db_user.google_id.is_none() {
@@ -103,12 +143,12 @@ impl AuthService {
                         .await?;
                 }
 
-                self.generate_session(&db_user.id).await
+                self.generate_session(&db_user.id, true).await
             }
         }
     }
 
-    async fn facebook_sign_in(&self, code: OAuth2Code) -> Result<String> {
+    async fn facebook_sign_in(&self, code: OAuth2Code) -> Result<(String, Session)> {
         let facebook_user = self.get_facebook_user(code).await?;
         let db_user = self
             .repository
@@ -127,9 +167,17 @@ impl AuthService {
                     hashed_password: None,
                     is_verified: true,
                 };
-                let user_id = self.repository.create_user(&new_user).await?;
-
-                self.generate_session(&user_id).await
+                let mut tx = self.repository.begin().await?;
+                let user_id = self.repository.create_user(&mut tx, &new_user).await?;
+                self.events
+                    .publish_in(
+                        &mut tx,
+                        &oauth2_sign_up(&user_id, &new_user, SignInMethod::Facebook),
+                    )
+                    .await?;
+                tx.commit().await?;
+
+                self.generate_session(&user_id, true).await
             }
             Some(db_user) => {
                 if db_user.google_id.is_none() {
@@ -149,11 +197,12 @@ impl AuthService {
                         .await?;
                 }
 
-                self.generate_session(&db_user.id).await
+                self.generate_session(&db_user.id, true).await
             }
         }
     }
 
+    #[instrument(skip_all, name = "oauth2.google_user")]
     async fn get_google_user(&self, code: OAuth2Code) -> Result<GoogleUserResponse> {
         let params = [
             ("code", code.as_ref()),
@@ -174,6 +223,7 @@ impl AuthService {
             .post("https://oauth2.googleapis.com/token")
             .form(&params)
             .header("Content-Type", "application/x-www-form-urlencoded")
+            .propagate_trace()
             .send()
             .await?
             .json::<GoogleAccessTokenResponse>()
@@ -196,6 +246,7 @@ impl AuthService {
                     .http_client
                     .post("https://openidconnect.googleapis.com/v1/userinfo")
                     .header("Authorization", format!("Bearer {}", access_token))
+                    .propagate_trace()
                     .send()
                     .await?
                     .json::<GoogleUserResponse>()
@@ -208,7 +259,7 @@ impl AuthService {
                     })?;
 
                 if !user.email_verified {
-                    return Err(Error::Conflict("Email is not verified".into()));
+                    return Err(Error::EmailNotVerified);
                 }
 
                 Ok(user)
@@ -216,6 +267,7 @@ impl AuthService {
         }
     }
 
+    #[instrument(skip_all, name = "oauth2.facebook_user")]
     async fn get_facebook_user(&self, code: OAuth2Code) -> Result<FacebookUserResponse> {
         let url = Url::parse_with_params(
             "https://graph.facebook.com/v20.0/oauth/access_token",
@@ -237,6 +289,7 @@ impl AuthService {
         let token_response = self
             .http_client
             .get(url)
+            .propagate_trace()
             .send()
             .await?
             .json::<FacebookAccessTokenResponse>()
@@ -261,6 +314,7 @@ impl AuthService {
                 let facebook_user = self
                     .http_client
                     .get(url)
+                    .propagate_trace()
                     .send()
                     .await?
                     .json::<FacebookUserResponse>()
@@ -318,3 +372,12 @@ impl AuthService {
         Ok(url)
     }
 }
+
+fn oauth2_sign_up(user_id: &UserID, new_user: &NewUser, method: SignInMethod) -> UserSignedUp {
+    UserSignedUp {
+        user_id: user_id.clone().into_inner(),
+        email: new_user.email.to_string(),
+        first_name: new_user.first_name.as_ref().map(ToString::to_string),
+        method,
+    }
+}
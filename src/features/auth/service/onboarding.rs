@@ -0,0 +1,32 @@
+use tracing::instrument;
+
+use crate::{
+    Result,
+    features::auth::{
+        UserID,
+        domain::{Onboarding, OnboardingStep},
+        service::AuthService,
+    },
+};
+
+impl AuthService {
+    #[instrument(name = "auth.get_onboarding", skip(self))]
+    pub async fn get_onboarding(&self, user_id: &UserID) -> Result<Onboarding> {
+        Ok(self
+            .repository
+            .get_onboarding(user_id)
+            .await?
+            .unwrap_or_default())
+    }
+
+    #[instrument(name = "auth.complete_onboarding_step", skip(self))]
+    pub async fn complete_onboarding_step(
+        &self,
+        user_id: &UserID,
+        step: OnboardingStep,
+    ) -> Result<Onboarding> {
+        self.repository
+            .complete_onboarding_step(user_id, step)
+            .await
+    }
+}
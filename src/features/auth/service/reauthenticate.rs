@@ -0,0 +1,38 @@
+use time::OffsetDateTime;
+use tracing::instrument;
+
+use crate::{
+    Error, Result,
+    features::auth::{AuthService, domain::Password, service::sign_in::verify_password},
+};
+
+pub struct ReauthenticateInput {
+    pub password: Password,
+}
+
+impl AuthService {
+    /// Confirms the password of the session owner and restarts the window in
+    /// which sensitive actions are allowed without another credential check.
+    #[instrument(name = "auth.reauthenticate", skip_all)]
+    pub async fn reauthenticate(&self, session_id: &str, data: ReauthenticateInput) -> Result<()> {
+        let mut session = self
+            .sessions
+            .touch(session_id, self.session_idle_timeout())
+            .await?
+            .ok_or(Error::Unauthorized)?;
+
+        let user = self
+            .repository
+            .get_user_by_id(&session.user_id)
+            .await?
+            .filter(|u| !u.is_banned && u.is_verified)
+            .ok_or(Error::Unauthorized)?;
+
+        verify_password(&user, &data.password)?;
+
+        session.authenticated_at = OffsetDateTime::now_utc();
+        self.sessions
+            .insert(session_id, &session, self.session_idle_timeout())
+            .await
+    }
+}
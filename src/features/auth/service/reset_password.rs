@@ -4,7 +4,8 @@ use crate::{
     Result,
     common::hash_password,
     features::auth::{
-        AuthService, EmailAddress, HashedPassword, Password, UpdateUser, service::TokenType,
+        AuthService, EmailAddress, HashedPassword, Password, PasswordReset, UpdateUser,
+        service::TokenType,
     },
 };
 
@@ -42,6 +43,13 @@ impl AuthService {
             )
             .await?;
 
+        self.events
+            .publish(&PasswordReset {
+                user_id: user.id.into_inner(),
+                email: user.email.to_string(),
+            })
+            .await;
+
         Ok(())
     }
 }
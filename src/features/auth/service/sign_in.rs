@@ -5,9 +5,12 @@ use crate::{
     common::verify,
     features::{
         auth::{
+            SignInMethod, User, UserID, UserSignedIn,
             domain::{EmailAddress, Password},
             service::AuthService,
+            session_store::Session,
         },
+        metrics::{AuthFlow, record_auth_failure, record_auth_success},
         shared::AppUser,
     },
 };
@@ -15,6 +18,7 @@ use crate::{
 pub struct SignInInput {
     pub email: EmailAddress,
     pub password: Password,
+    pub remember_me: bool,
 }
 
 impl AuthService {
@@ -23,25 +27,65 @@ impl AuthService {
         skip(self, data),
         fields(email = %data.email)
     )]
-    pub async fn sign_in(&self, data: SignInInput) -> Result<(AppUser, String)> {
-        let user = self
-            .repository
-            .get_user_by_email(&data.email)
-            .await?
-            .filter(|u| !u.is_banned && u.is_verified)
-            .ok_or(Error::Conflict("Invalid credentials".into()))?;
-
-        let stored_password = user
-            .password
-            .as_ref()
-            .ok_or(Error::Conflict("Invalid credentials".into()))?;
-
-        if !verify(data.password.as_ref(), stored_password.as_ref())? {
-            return Err(Error::Conflict("Invalid credentials".into()));
+    pub async fn sign_in(&self, data: SignInInput) -> Result<(AppUser, String, Session)> {
+        let user = self.verify_credentials(&data).await?;
+        let (session_id, session) = self.generate_session(&user.id, data.remember_me).await?;
+        self.publish_signed_in(&user.id, SignInMethod::Password)
+            .await;
+
+        Ok((user.into(), session_id, session))
+    }
+
+    pub(super) async fn publish_signed_in(&self, user_id: &UserID, method: SignInMethod) {
+        self.events
+            .publish(&UserSignedIn {
+                user_id: user_id.clone().into_inner(),
+                method,
+            })
+            .await;
+    }
+
+    pub(super) async fn verify_credentials(&self, data: &SignInInput) -> Result<User> {
+        let user = self.repository.get_user_by_email(&data.email).await?;
+
+        // The reason is only recorded in metrics; clients always see the same error.
+        let rejection = match &user {
+            None => Some("unknown_user"),
+            Some(u) if u.is_banned => Some("banned"),
+            Some(u) if !u.is_verified => Some("not_verified"),
+            Some(u) if u.password.is_none() => Some("no_password"),
+            Some(u) if !password_matches(u, &data.password)? => Some("wrong_password"),
+            Some(_) => None,
+        };
+
+        match (user, rejection) {
+            (Some(user), None) => {
+                record_auth_success(AuthFlow::SignIn, "password");
+                Ok(user)
+            }
+            (_, reason) => {
+                record_auth_failure(
+                    AuthFlow::SignIn,
+                    "password",
+                    reason.unwrap_or("unknown_user"),
+                );
+                Err(Error::InvalidCredentials)
+            }
         }
+    }
+}
 
-        let session_id = self.generate_session(&user.id).await?;
+pub(super) fn verify_password(user: &User, password: &Password) -> Result<()> {
+    if !password_matches(user, password)? {
+        return Err(Error::InvalidCredentials);
+    }
+
+    Ok(())
+}
 
-        Ok((user.into(), session_id))
+fn password_matches(user: &User, password: &Password) -> Result<bool> {
+    match &user.password {
+        Some(stored_password) => verify(password.as_ref(), stored_password.as_ref()),
+        None => Ok(false),
     }
 }
@@ -7,10 +7,12 @@ use crate::{
     common::{generate_secure_random_string, hash_password},
     features::{
         auth::{
-            EmailAddress, FirstName, HashedPassword, LastName, UserGender,
+            EmailAddress, FirstName, HashedPassword, LastName, SignInMethod, UserGender,
+            UserSignedUp,
             domain::{NewUser, Password},
             service::{AuthService, KeyType},
         },
+        metrics::{AuthFlow, record_auth_failure, record_auth_success},
         shared::map_unique_violation,
     },
 };
@@ -43,13 +45,23 @@ impl AuthService {
             is_verified: false,
         };
 
-        let user_id =
-            self.repository
-                .create_user(&new_user)
-                .await
-                .map_err(map_unique_violation(Some(Error::Conflict(
-                    "An account with this email already exists".into(),
-                ))))?;
+        // Committed only once the verification email is out, so a failed
+        // sign-up can be retried and doesn't publish an event.
+        let mut tx = self.repository.begin().await?;
+        let user_id = self
+            .repository
+            .create_user(&mut tx, &new_user)
+            .await
+            .map_err(map_unique_violation(Some(Error::Conflict(
+                "An account with this email already exists".into(),
+            ))))
+            .inspect_err(|err| {
+                let reason = match err {
+                    Error::Conflict(_) => "already_exists",
+                    _ => "error",
+                };
+                record_auth_failure(AuthFlow::SignUp, "password", reason);
+            })?;
 
         let token = generate_secure_random_string(42);
         let mut redis = self.redis.clone();
@@ -58,7 +70,7 @@ impl AuthService {
             redis.set_ex(
                 self.generate_redis_key(KeyType::Verification, &token),
                 user_id.to_string(),
-                self.app_config.account_verification_ttl_minutes * 60,
+                self.settings.current().account_verification_ttl_minutes * 60,
             ),
             self.email_client
                 .send_account_verification_email(new_user.email.as_ref(), &token)
@@ -67,6 +79,20 @@ impl AuthService {
         redis_result?;
         email_result?;
 
+        self.events
+            .publish_in(
+                &mut tx,
+                &UserSignedUp {
+                    user_id: user_id.clone().into_inner(),
+                    email: new_user.email.to_string(),
+                    first_name: new_user.first_name.as_ref().map(ToString::to_string),
+                    method: SignInMethod::Password,
+                },
+            )
+            .await?;
+        tx.commit().await?;
+        record_auth_success(AuthFlow::SignUp, "password");
+
         Ok(())
     }
 }
@@ -0,0 +1,154 @@
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use time::{Duration, OffsetDateTime};
+use tracing::{instrument, warn};
+use uuid::Uuid;
+
+use crate::{
+    Error, Result,
+    common::{generate_secure_random_string, hash_token},
+    features::{
+        auth::{
+            AccessTokenClaims, AuthService, NewRefreshToken, SignInMethod, UserID,
+            service::sign_in::SignInInput,
+        },
+        shared::AppUser,
+    },
+};
+
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_in: u64,
+}
+
+impl AuthService {
+    #[instrument(
+        name = "auth.token_sign_in",
+        skip(self, data),
+        fields(email = %data.email)
+    )]
+    pub async fn token_sign_in(&self, data: SignInInput) -> Result<TokenPair> {
+        let user = self.verify_credentials(&data).await?;
+        self.publish_signed_in(&user.id, SignInMethod::Password)
+            .await;
+
+        self.issue_token_pair(&user.id, Uuid::new_v4(), OffsetDateTime::now_utc())
+            .await
+    }
+
+    #[instrument(name = "auth.refresh_token", skip_all)]
+    pub async fn refresh_token(&self, refresh_token: &str) -> Result<TokenPair> {
+        let token = self
+            .repository
+            .get_refresh_token_by_hash(&hash_token(refresh_token))
+            .await?
+            .ok_or(Error::Unauthorized)?;
+
+        if token.revoked_at.is_some() || token.expires_at <= OffsetDateTime::now_utc() {
+            return Err(Error::Unauthorized);
+        }
+
+        if token.used_at.is_some() || !self.repository.mark_refresh_token_used(&token.id).await? {
+            warn!(family_id = %token.family_id, "Refresh token reuse detected, revoking token family");
+            self.repository
+                .revoke_refresh_token_family(&token.family_id)
+                .await?;
+
+            return Err(Error::Unauthorized);
+        }
+
+        self.repository
+            .get_user_by_id(&token.user_id)
+            .await?
+            .filter(|u| !u.is_banned && u.is_verified)
+            .ok_or(Error::Unauthorized)?;
+
+        self.issue_token_pair(&token.user_id, token.family_id, token.authenticated_at)
+            .await
+    }
+
+    #[instrument(name = "auth.revoke_refresh_token", skip_all)]
+    pub async fn revoke_refresh_token(&self, refresh_token: &str) -> Result<()> {
+        if let Some(token) = self
+            .repository
+            .get_refresh_token_by_hash(&hash_token(refresh_token))
+            .await?
+        {
+            self.repository
+                .revoke_refresh_token_family(&token.family_id)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the token owner together with the time they last proved their credentials.
+    pub async fn authenticate_access_token(
+        &self,
+        access_token: &str,
+    ) -> Result<(AppUser, OffsetDateTime)> {
+        let claims = decode::<AccessTokenClaims>(
+            access_token,
+            &DecodingKey::from_secret(self.app_config.jwt_secret.as_bytes()),
+            &Validation::new(Algorithm::HS256),
+        )
+        .map_err(|_| Error::Unauthorized)?
+        .claims;
+
+        let user = self
+            .repository
+            .get_user_by_id(&UserID::from(claims.sub))
+            .await?
+            .filter(|u| !u.is_banned && u.is_verified)
+            .ok_or(Error::Unauthorized)?;
+
+        let authenticated_at = OffsetDateTime::from_unix_timestamp(claims.auth_time)
+            .map_err(|_| Error::Unauthorized)?;
+
+        Ok((user.into(), authenticated_at))
+    }
+
+    async fn issue_token_pair(
+        &self,
+        user_id: &UserID,
+        family_id: Uuid,
+        authenticated_at: OffsetDateTime,
+    ) -> Result<TokenPair> {
+        let now = OffsetDateTime::now_utc();
+        let access_token_ttl =
+            Duration::minutes(self.settings.current().access_token_ttl_minutes as i64);
+
+        let claims = AccessTokenClaims {
+            sub: *user_id.as_ref(),
+            iat: now.unix_timestamp(),
+            exp: (now + access_token_ttl).unix_timestamp(),
+            auth_time: authenticated_at.unix_timestamp(),
+        };
+
+        let access_token = encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(self.app_config.jwt_secret.as_bytes()),
+        )
+        .map_err(|e| Error::Internal(format!("Failed to sign access token: {e}")))?;
+
+        let refresh_token = generate_secure_random_string(64);
+
+        self.repository
+            .create_refresh_token(&NewRefreshToken {
+                user_id: user_id.clone(),
+                family_id,
+                token_hash: hash_token(&refresh_token),
+                authenticated_at,
+                expires_at: now
+                    + Duration::minutes(self.settings.current().refresh_token_ttl_minutes as i64),
+            })
+            .await?;
+
+        Ok(TokenPair {
+            access_token,
+            refresh_token,
+            expires_in: access_token_ttl.whole_seconds() as u64,
+        })
+    }
+}
@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use time::OffsetDateTime;
+use tracing::instrument;
+
+use crate::{Result, features::auth::service::AuthService};
+
+/// Slack past the verification TTL, so an account whose link is being
+/// followed right as it expires isn't deleted underneath it.
+const CLEANUP_GRACE: Duration = Duration::from_secs(60 * 60);
+
+impl AuthService {
+    /// Deletes accounts created by `sign_up` whose verification link has
+    /// expired unused, which frees their email for a new sign-up.
+    #[instrument(name = "auth.delete_expired_unverified_accounts", skip(self))]
+    pub async fn delete_expired_unverified_accounts(&self) -> Result<u64> {
+        let ttl =
+            Duration::from_secs(self.settings.current().account_verification_ttl_minutes * 60);
+        let cutoff = OffsetDateTime::now_utc() - ttl - CLEANUP_GRACE;
+
+        self.repository
+            .delete_unverified_users_created_before(cutoff)
+            .await
+    }
+}
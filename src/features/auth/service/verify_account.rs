@@ -2,9 +2,14 @@ use tracing::instrument;
 
 use crate::{
     Result,
-    features::auth::{
-        domain::{EmailAddress, UpdateUser},
-        service::AuthService,
+    features::{
+        auth::{
+            SignInMethod, UserVerified,
+            domain::{EmailAddress, UpdateUser},
+            service::AuthService,
+            session_store::Session,
+        },
+        shared::AppUser,
     },
 };
 
@@ -14,18 +19,25 @@ pub struct VerifyAccountInput {
 }
 
 impl AuthService {
+    /// Verifies the account and signs the user in, so they don't have to
+    /// enter the credentials they just chose.
     #[instrument(
         name = "auth.verify_account",
         skip(self, data),
         fields(email = %data.email)
     )]
-    pub async fn verify_account(&self, data: VerifyAccountInput) -> Result<()> {
+    pub async fn verify_account(
+        &self,
+        data: VerifyAccountInput,
+    ) -> Result<(AppUser, String, Session)> {
         let user = self
             .get_user_by_token(super::TokenType::Verification, data.email, &data.token)
             .await?;
 
+        let mut tx = self.repository.begin().await?;
         self.repository
-            .update_user(
+            .update_user_in(
+                &mut tx,
                 &user.id,
                 UpdateUser {
                     first_name: None,
@@ -39,6 +51,22 @@ impl AuthService {
             )
             .await?;
 
-        Ok(())
+        self.events
+            .publish_in(
+                &mut tx,
+                &UserVerified {
+                    user_id: user.id.clone().into_inner(),
+                    email: user.email.to_string(),
+                    first_name: user.first_name.as_ref().map(ToString::to_string),
+                },
+            )
+            .await?;
+        tx.commit().await?;
+
+        let (session_id, session) = self.generate_session(&user.id, false).await?;
+        self.publish_signed_in(&user.id, SignInMethod::Verification)
+            .await;
+
+        Ok((user.into(), session_id, session))
     }
 }
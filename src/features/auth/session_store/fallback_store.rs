@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use crate::{
+    Result,
+    features::auth::{
+        UserID,
+        session_store::{PostgresSessionStore, RedisSessionStore, Session, SessionStore},
+    },
+};
+
+/// Writes sessions to both Redis and Postgres and reads from Redis first,
+/// falling back to Postgres when Redis is unavailable or has lost the key.
+/// Postgres is the source of truth: a Redis hit only counts while the
+/// session still exists there, so a delete that reached Postgres but not
+/// Redis (e.g. a sign-out during a Redis outage) can't be undone once Redis
+/// recovers.
+pub struct FallbackSessionStore {
+    primary: RedisSessionStore,
+    fallback: PostgresSessionStore,
+}
+
+impl FallbackSessionStore {
+    pub fn new(primary: RedisSessionStore, fallback: PostgresSessionStore) -> Self {
+        Self { primary, fallback }
+    }
+}
+
+#[async_trait]
+impl SessionStore for FallbackSessionStore {
+    async fn insert(
+        &self,
+        session_id: &str,
+        session: &Session,
+        idle_timeout: Duration,
+    ) -> Result<()> {
+        self.fallback
+            .insert(session_id, session, idle_timeout)
+            .await?;
+
+        if let Err(err) = self.primary.insert(session_id, session, idle_timeout).await {
+            warn!(
+                ?err,
+                "Failed to write session to redis, using postgres only"
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn touch(&self, session_id: &str, idle_timeout: Duration) -> Result<Option<Session>> {
+        match self.primary.touch(session_id, idle_timeout).await {
+            Ok(Some(session)) => match self.fallback.touch(session_id, idle_timeout).await {
+                Ok(Some(_)) => Ok(Some(session)),
+                Ok(None) => {
+                    if let Err(err) = self.primary.remove(session_id).await {
+                        warn!(?err, "Failed to remove stale session from redis");
+                    }
+
+                    Ok(None)
+                }
+                Err(err) => {
+                    warn!(?err, "Failed to extend session in postgres");
+                    Ok(Some(session))
+                }
+            },
+            Ok(None) => {
+                let session = self.fallback.touch(session_id, idle_timeout).await?;
+
+                if let Some(session) = session.as_ref()
+                    && let Err(err) = self.primary.insert(session_id, session, idle_timeout).await
+                {
+                    warn!(?err, "Failed to restore session in redis");
+                }
+
+                Ok(session)
+            }
+            Err(err) => {
+                warn!(
+                    ?err,
+                    "Failed to read session from redis, falling back to postgres"
+                );
+                self.fallback.touch(session_id, idle_timeout).await
+            }
+        }
+    }
+
+    async fn remove(&self, session_id: &str) -> Result<()> {
+        if let Err(err) = self.primary.remove(session_id).await {
+            warn!(?err, "Failed to remove session from redis");
+        }
+
+        self.fallback.remove(session_id).await
+    }
+
+    async fn remove_user_sessions(&self, user_id: &UserID) -> Result<u64> {
+        let removed = self.fallback.remove_user_sessions(user_id).await?;
+
+        match self.primary.remove_user_sessions(user_id).await {
+            Ok(cached) => Ok(removed.max(cached)),
+            Err(err) => {
+                warn!(?err, "Failed to remove user sessions from redis");
+                Ok(removed)
+            }
+        }
+    }
+
+    async fn remove_expired(&self) -> Result<u64> {
+        self.fallback.remove_expired().await
+    }
+}
@@ -0,0 +1,204 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use async_trait::async_trait;
+use time::OffsetDateTime;
+
+use crate::{
+    Result,
+    features::auth::{
+        UserID,
+        session_store::{Session, SessionStore},
+    },
+};
+
+/// Process-local store meant for tests and single-instance development setups.
+#[derive(Default)]
+pub struct MemorySessionStore {
+    sessions: Mutex<HashMap<String, (Session, OffsetDateTime)>>,
+}
+
+#[async_trait]
+impl SessionStore for MemorySessionStore {
+    async fn insert(
+        &self,
+        session_id: &str,
+        session: &Session,
+        idle_timeout: Duration,
+    ) -> Result<()> {
+        let mut sessions = self.sessions.lock().unwrap();
+
+        match session.idle_ttl(idle_timeout) {
+            Some(ttl) => {
+                sessions.insert(
+                    session_id.to_owned(),
+                    (session.clone(), OffsetDateTime::now_utc() + ttl),
+                );
+            }
+            None => {
+                sessions.remove(session_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn touch(&self, session_id: &str, idle_timeout: Duration) -> Result<Option<Session>> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let now = OffsetDateTime::now_utc();
+
+        match sessions.get_mut(session_id) {
+            Some((session, idle_expires_at)) if *idle_expires_at > now => {
+                match session.idle_ttl(idle_timeout) {
+                    Some(ttl) => {
+                        *idle_expires_at = now + ttl;
+                        Ok(Some(session.clone()))
+                    }
+                    None => {
+                        sessions.remove(session_id);
+                        Ok(None)
+                    }
+                }
+            }
+            Some(_) => {
+                sessions.remove(session_id);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn remove(&self, session_id: &str) -> Result<()> {
+        self.sessions.lock().unwrap().remove(session_id);
+
+        Ok(())
+    }
+
+    async fn remove_user_sessions(&self, user_id: &UserID) -> Result<u64> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let before = sessions.len();
+
+        sessions.retain(|_, (session, _)| session.user_id != *user_id);
+
+        Ok((before - sessions.len()) as u64)
+    }
+
+    async fn remove_expired(&self) -> Result<u64> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let before = sessions.len();
+        let now = OffsetDateTime::now_utc();
+
+        sessions.retain(|_, (session, idle_expires_at)| {
+            *idle_expires_at > now && session.expires_at > now
+        });
+
+        Ok((before - sessions.len()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uuid::Uuid;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    fn session(lifetime: Duration) -> Session {
+        Session::new(UserID::from(Uuid::new_v4()), lifetime, true)
+    }
+
+    #[tokio::test]
+    async fn touch_returns_inserted_session() {
+        let store = MemorySessionStore::default();
+        let session = session(MINUTE * 60);
+
+        store.insert("session", &session, MINUTE).await.unwrap();
+
+        let result = store.touch("session", MINUTE).await;
+        assert_eq!(result.unwrap(), Some(session));
+    }
+
+    #[tokio::test]
+    async fn touch_unknown_session_returns_none() {
+        let store = MemorySessionStore::default();
+
+        let result = store.touch("unknown", MINUTE).await;
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn touch_idle_session_returns_none() {
+        let store = MemorySessionStore::default();
+
+        store
+            .insert("session", &session(MINUTE * 60), Duration::from_millis(1))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let result = store.touch("session", MINUTE).await;
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn touch_after_absolute_expiry_returns_none() {
+        let store = MemorySessionStore::default();
+
+        store
+            .insert("session", &session(Duration::from_millis(5)), MINUTE)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let result = store.touch("session", MINUTE).await;
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn removed_session_cannot_be_touched() {
+        let store = MemorySessionStore::default();
+
+        store
+            .insert("session", &session(MINUTE * 60), MINUTE)
+            .await
+            .unwrap();
+        store.remove("session").await.unwrap();
+
+        let result = store.touch("session", MINUTE).await;
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn remove_user_sessions_keeps_other_users() {
+        let store = MemorySessionStore::default();
+        let first = session(MINUTE * 60);
+        let second = session(MINUTE * 60);
+
+        store.insert("a", &first, MINUTE).await.unwrap();
+        store.insert("b", &first, MINUTE).await.unwrap();
+        store.insert("c", &second, MINUTE).await.unwrap();
+
+        let removed = store.remove_user_sessions(&first.user_id).await.unwrap();
+        assert_eq!(removed, 2);
+
+        assert_eq!(store.touch("a", MINUTE).await.unwrap(), None);
+        assert_eq!(store.touch("c", MINUTE).await.unwrap(), Some(second));
+    }
+
+    #[tokio::test]
+    async fn remove_expired_keeps_live_sessions() {
+        let store = MemorySessionStore::default();
+
+        store
+            .insert("idle", &session(MINUTE * 60), Duration::from_millis(1))
+            .await
+            .unwrap();
+        store
+            .insert("live", &session(MINUTE * 60), MINUTE)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        assert_eq!(store.remove_expired().await.unwrap(), 1);
+        assert!(store.touch("live", MINUTE).await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,153 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use redis::aio::MultiplexedConnection;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+
+use crate::{Result, configuration::app_config::SessionStoreKind, features::auth::UserID};
+
+mod fallback_store;
+mod memory_store;
+mod postgres_store;
+mod redis_store;
+
+pub use fallback_store::FallbackSessionStore;
+pub use memory_store::MemorySessionStore;
+pub use postgres_store::PostgresSessionStore;
+pub use redis_store::RedisSessionStore;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub user_id: UserID,
+    pub created_at: OffsetDateTime,
+    /// Last time the user proved their credentials for this session.
+    pub authenticated_at: OffsetDateTime,
+    /// Absolute expiry, never extended by activity.
+    pub expires_at: OffsetDateTime,
+    /// Whether the session cookie outlives the browser ("remember me").
+    pub persistent: bool,
+}
+
+impl Session {
+    pub fn new(user_id: UserID, lifetime: Duration, persistent: bool) -> Self {
+        let now = OffsetDateTime::now_utc();
+
+        Self {
+            user_id,
+            created_at: now,
+            authenticated_at: now,
+            expires_at: now + lifetime,
+            persistent,
+        }
+    }
+
+    /// How long the session may stay idle from now on, capped by its absolute
+    /// expiry. `None` once the absolute lifetime is over.
+    pub fn idle_ttl(&self, idle_timeout: Duration) -> Option<Duration> {
+        let remaining = Duration::try_from(self.expires_at - OffsetDateTime::now_utc()).ok()?;
+
+        Some(remaining.min(idle_timeout)).filter(|ttl| !ttl.is_zero())
+    }
+
+    pub fn is_recently_authenticated(&self, max_age: Duration) -> bool {
+        self.authenticated_at + max_age > OffsetDateTime::now_utc()
+    }
+
+    /// Expiry for the session cookie, `None` for a browser-session cookie.
+    pub fn cookie_expires_at(&self) -> Option<OffsetDateTime> {
+        self.persistent.then_some(self.expires_at)
+    }
+}
+
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Stores or replaces a session that expires after `idle_timeout` of
+    /// inactivity or at its absolute expiry, whichever comes first.
+    async fn insert(
+        &self,
+        session_id: &str,
+        session: &Session,
+        idle_timeout: Duration,
+    ) -> Result<()>;
+
+    /// Returns a live session and pushes its idle expiry `idle_timeout` into
+    /// the future, never past its absolute expiry.
+    async fn touch(&self, session_id: &str, idle_timeout: Duration) -> Result<Option<Session>>;
+
+    async fn remove(&self, session_id: &str) -> Result<()>;
+
+    /// Removes every session belonging to `user_id`, returning how many were removed.
+    async fn remove_user_sessions(&self, user_id: &UserID) -> Result<u64>;
+
+    /// Deletes sessions past their idle or absolute expiry, returning how
+    /// many were deleted. Stores that expire entries on their own return 0.
+    async fn remove_expired(&self) -> Result<u64>;
+}
+
+pub fn build_session_store(
+    kind: SessionStoreKind,
+    redis: MultiplexedConnection,
+    pool: PgPool,
+) -> Arc<dyn SessionStore> {
+    match kind {
+        SessionStoreKind::Redis => Arc::new(RedisSessionStore::new(redis)),
+        SessionStoreKind::Postgres => Arc::new(PostgresSessionStore::new(pool)),
+        SessionStoreKind::Memory => Arc::new(MemorySessionStore::default()),
+        SessionStoreKind::Fallback => Arc::new(FallbackSessionStore::new(
+            RedisSessionStore::new(redis),
+            PostgresSessionStore::new(pool),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uuid::Uuid;
+
+    fn session(lifetime: Duration) -> Session {
+        Session::new(UserID::from(Uuid::new_v4()), lifetime, true)
+    }
+
+    #[test]
+    fn idle_ttl_is_capped_by_absolute_expiry() {
+        let session = session(Duration::from_secs(60));
+
+        let ttl = session.idle_ttl(Duration::from_secs(3600)).unwrap();
+        assert!(ttl <= Duration::from_secs(60));
+    }
+
+    #[test]
+    fn idle_ttl_uses_idle_timeout_when_shorter() {
+        let session = session(Duration::from_secs(3600));
+
+        let ttl = session.idle_ttl(Duration::from_secs(60));
+        assert_eq!(ttl, Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn idle_ttl_is_none_after_absolute_expiry() {
+        let session = session(Duration::ZERO);
+
+        assert_eq!(session.idle_ttl(Duration::from_secs(60)), None);
+    }
+
+    #[test]
+    fn stale_authentication_is_not_recent() {
+        let mut session = session(Duration::from_secs(3600));
+        assert!(session.is_recently_authenticated(Duration::from_secs(60)));
+
+        session.authenticated_at -= Duration::from_secs(120);
+        assert!(!session.is_recently_authenticated(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn browser_session_cookie_has_no_expiry() {
+        let mut session = session(Duration::from_secs(3600));
+        assert_eq!(session.cookie_expires_at(), Some(session.expires_at));
+
+        session.persistent = false;
+        assert_eq!(session.cookie_expires_at(), None);
+    }
+}
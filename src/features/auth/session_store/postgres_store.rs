@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sqlx::{PgPool, query};
+use time::OffsetDateTime;
+use tracing::instrument;
+
+use crate::{
+    Result,
+    features::auth::{
+        UserID,
+        session_store::{Session, SessionStore},
+    },
+};
+
+#[derive(Clone)]
+pub struct PostgresSessionStore {
+    pool: PgPool,
+}
+
+impl PostgresSessionStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SessionStore for PostgresSessionStore {
+    #[instrument(skip_all, name = "postgressessionstore - insert")]
+    async fn insert(
+        &self,
+        session_id: &str,
+        session: &Session,
+        idle_timeout: Duration,
+    ) -> Result<()> {
+        let Some(ttl) = session.idle_ttl(idle_timeout) else {
+            return self.remove(session_id).await;
+        };
+
+        query!(
+            r#"
+                INSERT INTO sessions (id, user_id, created_at, authenticated_at, persistent, expires_at, absolute_expires_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (id) DO UPDATE
+                SET user_id = EXCLUDED.user_id,
+                    authenticated_at = EXCLUDED.authenticated_at,
+                    persistent = EXCLUDED.persistent,
+                    expires_at = EXCLUDED.expires_at,
+                    absolute_expires_at = EXCLUDED.absolute_expires_at
+            "#,
+            session_id,
+            session.user_id.as_ref(),
+            session.created_at,
+            session.authenticated_at,
+            session.persistent,
+            OffsetDateTime::now_utc() + ttl,
+            session.expires_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, name = "postgressessionstore - touch")]
+    async fn touch(&self, session_id: &str, idle_timeout: Duration) -> Result<Option<Session>> {
+        let record = query!(
+            r#"
+                UPDATE sessions
+                SET expires_at = LEAST($2, absolute_expires_at)
+                WHERE id = $1 AND expires_at > NOW() AND absolute_expires_at > NOW()
+                RETURNING user_id, created_at, authenticated_at, absolute_expires_at, persistent
+            "#,
+            session_id,
+            OffsetDateTime::now_utc() + idle_timeout
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record.map(|r| Session {
+            user_id: UserID::from(r.user_id),
+            created_at: r.created_at,
+            authenticated_at: r.authenticated_at,
+            expires_at: r.absolute_expires_at,
+            persistent: r.persistent,
+        }))
+    }
+
+    #[instrument(skip_all, name = "postgressessionstore - remove")]
+    async fn remove(&self, session_id: &str) -> Result<()> {
+        query!("DELETE FROM sessions WHERE id = $1", session_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, name = "postgressessionstore - remove user sessions")]
+    async fn remove_user_sessions(&self, user_id: &UserID) -> Result<u64> {
+        let result = query!("DELETE FROM sessions WHERE user_id = $1", user_id.as_ref())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip_all, name = "postgressessionstore - remove expired")]
+    async fn remove_expired(&self) -> Result<u64> {
+        let result =
+            query!("DELETE FROM sessions WHERE expires_at < NOW() OR absolute_expires_at < NOW()")
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected())
+    }
+}
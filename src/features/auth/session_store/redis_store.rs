@@ -0,0 +1,160 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use redis::{AsyncTypedCommands, aio::MultiplexedConnection};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    Result,
+    features::auth::{
+        REDIS_SESSION_PREFIX, UserID,
+        session_store::{Session, SessionStore},
+    },
+};
+
+#[derive(Clone)]
+pub struct RedisSessionStore {
+    redis: MultiplexedConnection,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredSession {
+    user_id: Uuid,
+    #[serde(with = "time::serde::timestamp")]
+    created_at: OffsetDateTime,
+    #[serde(with = "time::serde::timestamp")]
+    authenticated_at: OffsetDateTime,
+    #[serde(with = "time::serde::timestamp")]
+    expires_at: OffsetDateTime,
+    persistent: bool,
+}
+
+impl From<&Session> for StoredSession {
+    fn from(value: &Session) -> Self {
+        Self {
+            user_id: *value.user_id.as_ref(),
+            created_at: value.created_at,
+            authenticated_at: value.authenticated_at,
+            expires_at: value.expires_at,
+            persistent: value.persistent,
+        }
+    }
+}
+
+impl From<StoredSession> for Session {
+    fn from(value: StoredSession) -> Self {
+        Self {
+            user_id: UserID::from(value.user_id),
+            created_at: value.created_at,
+            authenticated_at: value.authenticated_at,
+            expires_at: value.expires_at,
+            persistent: value.persistent,
+        }
+    }
+}
+
+impl RedisSessionStore {
+    pub fn new(redis: MultiplexedConnection) -> Self {
+        Self { redis }
+    }
+
+    fn key(session_id: &str) -> String {
+        format!("{}{}", REDIS_SESSION_PREFIX, session_id)
+    }
+}
+
+#[async_trait]
+impl SessionStore for RedisSessionStore {
+    async fn insert(
+        &self,
+        session_id: &str,
+        session: &Session,
+        idle_timeout: Duration,
+    ) -> Result<()> {
+        let mut redis = self.redis.clone();
+
+        let Some(ttl) = session.idle_ttl(idle_timeout) else {
+            redis.del(Self::key(session_id)).await?;
+            return Ok(());
+        };
+
+        redis
+            .set_ex(
+                Self::key(session_id),
+                serde_json::to_string(&StoredSession::from(session))?,
+                ttl.as_secs().max(1),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn touch(&self, session_id: &str, idle_timeout: Duration) -> Result<Option<Session>> {
+        let mut redis = self.redis.clone();
+        let key = Self::key(session_id);
+
+        // Sessions written in an older format are treated as expired.
+        let Some(session) = redis
+            .get(&key)
+            .await?
+            .and_then(|value| serde_json::from_str::<StoredSession>(&value).ok())
+            .map(Session::from)
+        else {
+            return Ok(None);
+        };
+
+        let Some(ttl) = session.idle_ttl(idle_timeout) else {
+            redis.del(&key).await?;
+            return Ok(None);
+        };
+
+        redis.expire(&key, ttl.as_secs().max(1) as i64).await?;
+
+        Ok(Some(session))
+    }
+
+    async fn remove(&self, session_id: &str) -> Result<()> {
+        let mut redis = self.redis.clone();
+        redis.del(Self::key(session_id)).await?;
+
+        Ok(())
+    }
+
+    /// Sessions are keyed by id only, so this scans the session keyspace.
+    /// Meant for administrative use, not request paths.
+    async fn remove_user_sessions(&self, user_id: &UserID) -> Result<u64> {
+        let mut scan = self.redis.clone();
+        let mut keys = Vec::new();
+        let mut iter = scan
+            .scan_match::<_, String>(format!("{}*", REDIS_SESSION_PREFIX))
+            .await?;
+
+        while let Some(key) = iter.next_item().await {
+            keys.push(key?);
+        }
+
+        let mut redis = self.redis.clone();
+        let mut removed = 0;
+
+        for key in keys {
+            let owned = redis
+                .get(&key)
+                .await?
+                .and_then(|value| serde_json::from_str::<StoredSession>(&value).ok())
+                .is_some_and(|session| session.user_id == *user_id.as_ref());
+
+            if owned {
+                removed += redis.del(&key).await? as u64;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Keys carry a TTL, so Redis drops expired sessions by itself.
+    async fn remove_expired(&self) -> Result<u64> {
+        Ok(0)
+    }
+}
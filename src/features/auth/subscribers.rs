@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    Result,
+    clients::email_client::EmailClient,
+    events::{Delivery, EventBus, Subscriber},
+    features::auth::{
+        SignInMethod, UserID, UserSignedUp, UserVerified, repository::AuthRepository,
+    },
+};
+
+/// Runs once an account is verified: password accounts when their email is
+/// confirmed, OAuth2 accounts as soon as they are created. Both steps are
+/// persistent subscribers so a failed email is retried on its own.
+pub struct OnboardingPipeline {
+    repository: AuthRepository,
+    email_client: Arc<EmailClient>,
+}
+
+impl OnboardingPipeline {
+    pub fn new(pool: PgPool, email_client: Arc<EmailClient>) -> Self {
+        Self {
+            repository: AuthRepository::new(pool),
+            email_client,
+        }
+    }
+
+    pub fn subscribe(self, events: &EventBus) {
+        let checklist = StartOnboarding {
+            repository: self.repository,
+        };
+        let welcome = SendWelcomeEmail {
+            email_client: self.email_client,
+        };
+
+        events
+            .subscribe::<UserVerified>("onboarding", Delivery::Persistent, checklist.clone())
+            .subscribe::<UserSignedUp>("onboarding", Delivery::Persistent, checklist)
+            .subscribe::<UserVerified>("welcome_email", Delivery::Persistent, welcome.clone())
+            .subscribe::<UserSignedUp>("welcome_email", Delivery::Persistent, welcome);
+    }
+}
+
+/// Password sign-ups are welcomed on verification instead.
+fn is_verified_sign_up(event: &UserSignedUp) -> bool {
+    event.method != SignInMethod::Password
+}
+
+#[derive(Clone)]
+struct StartOnboarding {
+    repository: AuthRepository,
+}
+
+impl StartOnboarding {
+    async fn start(&self, user_id: UserID) -> Result<()> {
+        // Gone already, e.g. deleted before the job ran.
+        let Some(user) = self.repository.get_user_by_id(&user_id).await? else {
+            return Ok(());
+        };
+        let profile_completed =
+            user.first_name.is_some() && user.last_name.is_some() && user.gender.is_some();
+
+        self.repository
+            .start_onboarding(&user_id, profile_completed)
+            .await
+    }
+}
+
+#[async_trait]
+impl Subscriber<UserVerified> for StartOnboarding {
+    async fn handle(&self, _id: Uuid, event: UserVerified) -> Result<()> {
+        self.start(event.user_id.into()).await
+    }
+}
+
+#[async_trait]
+impl Subscriber<UserSignedUp> for StartOnboarding {
+    async fn handle(&self, _id: Uuid, event: UserSignedUp) -> Result<()> {
+        if !is_verified_sign_up(&event) {
+            return Ok(());
+        }
+
+        self.start(event.user_id.into()).await
+    }
+}
+
+#[derive(Clone)]
+struct SendWelcomeEmail {
+    email_client: Arc<EmailClient>,
+}
+
+#[async_trait]
+impl Subscriber<UserVerified> for SendWelcomeEmail {
+    async fn handle(&self, _id: Uuid, event: UserVerified) -> Result<()> {
+        self.email_client
+            .send_welcome_email(&event.email, event.first_name.as_deref())
+            .await
+    }
+}
+
+#[async_trait]
+impl Subscriber<UserSignedUp> for SendWelcomeEmail {
+    async fn handle(&self, _id: Uuid, event: UserSignedUp) -> Result<()> {
+        if !is_verified_sign_up(&event) {
+            return Ok(());
+        }
+
+        self.email_client
+            .send_welcome_email(&event.email, event.first_name.as_deref())
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn sign_up(method: SignInMethod) -> UserSignedUp {
+        UserSignedUp {
+            user_id: Uuid::new_v4(),
+            email: "test@gmail.com".into(),
+            first_name: None,
+            method,
+        }
+    }
+
+    #[test]
+    fn only_oauth2_sign_ups_start_onboarding() {
+        assert!(!is_verified_sign_up(&sign_up(SignInMethod::Password)));
+        assert!(is_verified_sign_up(&sign_up(SignInMethod::Google)));
+        assert!(is_verified_sign_up(&sign_up(SignInMethod::Facebook)));
+    }
+}
@@ -0,0 +1,262 @@
+use serde::{Deserialize, Serialize};
+use time::{Date, OffsetDateTime, format_description::well_known::Iso8601};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{Error, Result, features::shared::NonEmptyString, i18n::Message};
+
+const SKU_MIN_LENGTH: usize = 3;
+const SKU_MAX_LENGTH: usize = 32;
+/// Anything above is a typo rather than a grail.
+const PRICE_MAX_CENTS: i64 = 10_000_000;
+
+pub type Brand = NonEmptyString<1, 60>;
+pub type ProductModel = NonEmptyString<1, 120>;
+pub type Colorway = NonEmptyString<1, 120>;
+pub type ProductDescription = NonEmptyString<1, 2000>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize, ToSchema)]
+#[sqlx(type_name = "product_gender", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ProductGender {
+    Men,
+    Women,
+    Unisex,
+    Kids,
+}
+
+impl ProductGender {
+    pub fn parse(value: String) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "men" => Ok(ProductGender::Men),
+            "women" => Ok(ProductGender::Women),
+            "unisex" => Ok(ProductGender::Unisex),
+            "kids" => Ok(ProductGender::Kids),
+            _ => Err(Error::DomainValidationError(vec![Message::new(
+                "product.invalid_gender",
+            )])),
+        }
+    }
+}
+
+/// The manufacturer's style code, e.g. `DZ5485-612`, stored upper-cased.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sku(String);
+
+impl Sku {
+    pub fn parse(value: String) -> Result<Self> {
+        let value = value.trim().to_ascii_uppercase();
+        let valid = (SKU_MIN_LENGTH..=SKU_MAX_LENGTH).contains(&value.len())
+            && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            && !value.starts_with('-')
+            && !value.ends_with('-');
+
+        if !valid {
+            return Err(Error::DomainValidationError(vec![
+                Message::new("product.invalid_sku")
+                    .with("min", SKU_MIN_LENGTH)
+                    .with("max", SKU_MAX_LENGTH),
+            ]));
+        }
+
+        Ok(Self(value))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// An amount in the currency's minor unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceCents(i64);
+
+impl PriceCents {
+    pub fn parse(value: i64) -> Result<Self> {
+        if !(0..=PRICE_MAX_CENTS).contains(&value) {
+            return Err(Error::DomainValidationError(vec![
+                Message::new("product.invalid_price").with("max", PRICE_MAX_CENTS),
+            ]));
+        }
+
+        Ok(Self(value))
+    }
+
+    pub fn cents(&self) -> i64 {
+        self.0
+    }
+}
+
+/// An ISO 4217 code such as `USD`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Currency(String);
+
+impl Currency {
+    pub fn parse(value: String) -> Result<Self> {
+        let value = value.trim().to_ascii_uppercase();
+        if value.len() != 3 || !value.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(Error::DomainValidationError(vec![Message::new(
+                "product.invalid_currency",
+            )]));
+        }
+
+        Ok(Self(value))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A `YYYY-MM-DD` date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReleaseDate(Date);
+
+impl ReleaseDate {
+    pub fn parse(value: String) -> Result<Self> {
+        Date::parse(value.trim(), &Iso8601::DATE)
+            .map(Self)
+            .map_err(|_| {
+                Error::DomainValidationError(vec![Message::new("product.invalid_release_date")])
+            })
+    }
+
+    pub fn date(&self) -> Date {
+        self.0
+    }
+}
+
+/// The public identifier in product URLs, derived once from the product's
+/// names and SKU so it stays unique and doesn't change on later edits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductSlug(String);
+
+impl ProductSlug {
+    pub fn generate(brand: &Brand, model: &ProductModel, colorway: &Colorway, sku: &Sku) -> Self {
+        let source = format!("{brand} {model} {colorway} {}", sku.as_str());
+        let mut slug = String::with_capacity(source.len());
+
+        for c in source.chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c.to_ascii_lowercase());
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+
+        Self(slug.trim_end_matches('-').to_owned())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct Product {
+    pub id: Uuid,
+    pub slug: String,
+    pub sku: String,
+    pub brand: String,
+    pub model: String,
+    pub colorway: String,
+    pub description: Option<String>,
+    pub gender: ProductGender,
+    pub release_date: Date,
+    pub price_cents: i64,
+    pub currency: String,
+    pub archived_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct NewProduct {
+    pub slug: ProductSlug,
+    pub sku: Sku,
+    pub brand: Brand,
+    pub model: ProductModel,
+    pub colorway: Colorway,
+    pub description: Option<ProductDescription>,
+    pub gender: ProductGender,
+    pub release_date: ReleaseDate,
+    pub price: PriceCents,
+    pub currency: Currency,
+}
+
+/// Fields left `None` keep their current value. The SKU and slug are fixed.
+#[derive(Debug, Default)]
+pub struct UpdateProduct {
+    pub brand: Option<Brand>,
+    pub model: Option<ProductModel>,
+    pub colorway: Option<Colorway>,
+    pub description: Option<ProductDescription>,
+    pub gender: Option<ProductGender>,
+    pub release_date: Option<ReleaseDate>,
+    pub price: Option<PriceCents>,
+    pub currency: Option<Currency>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sku_is_upper_cased_and_restricted() {
+        assert_eq!(
+            Sku::parse(" dz5485-612 ".into()).unwrap().as_str(),
+            "DZ5485-612"
+        );
+        assert!(Sku::parse("DZ".into()).is_err());
+        assert!(Sku::parse("DZ5485 612".into()).is_err());
+        assert!(Sku::parse("-DZ5485".into()).is_err());
+        assert!(Sku::parse("X".repeat(SKU_MAX_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn price_must_be_within_range() {
+        assert_eq!(PriceCents::parse(18_000).unwrap().cents(), 18_000);
+        assert!(PriceCents::parse(0).is_ok());
+        assert!(PriceCents::parse(-1).is_err());
+        assert!(PriceCents::parse(PRICE_MAX_CENTS + 1).is_err());
+    }
+
+    #[test]
+    fn currency_is_a_three_letter_code() {
+        assert_eq!(Currency::parse("usd".into()).unwrap().as_str(), "USD");
+        assert!(Currency::parse("US".into()).is_err());
+        assert!(Currency::parse("U5D".into()).is_err());
+    }
+
+    #[test]
+    fn release_date_is_an_iso_date() {
+        let date = ReleaseDate::parse("2025-11-28".into()).unwrap().date();
+        assert_eq!(date.to_string(), "2025-11-28");
+        assert!(ReleaseDate::parse("28/11/2025".into()).is_err());
+        assert!(ReleaseDate::parse("2025-02-30".into()).is_err());
+    }
+
+    #[test]
+    fn gender_is_case_insensitive() {
+        assert_eq!(
+            ProductGender::parse("Women".into()).unwrap(),
+            ProductGender::Women
+        );
+        assert!(ProductGender::parse("adults".into()).is_err());
+    }
+
+    #[test]
+    fn slug_is_built_from_names_and_sku() {
+        let slug = ProductSlug::generate(
+            &Brand::parse("Jordan".into()).unwrap(),
+            &ProductModel::parse("Air Jordan 1 Retro High OG".into()).unwrap(),
+            &Colorway::parse("Chicago 'Lost & Found'".into()).unwrap(),
+            &Sku::parse("DZ5485-612".into()).unwrap(),
+        );
+
+        assert_eq!(
+            slug.as_str(),
+            "jordan-air-jordan-1-retro-high-og-chicago-lost-found-dz5485-612"
+        );
+    }
+}
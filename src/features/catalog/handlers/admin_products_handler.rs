@@ -0,0 +1,238 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use axum_extra::extract::WithRejection;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    ApiResponse, Error, Problem, Result,
+    app::AppState,
+    features::{
+        catalog::{
+            domain::{
+                Brand, Colorway, Currency, PriceCents, ProductDescription, ProductGender,
+                ProductModel, ReleaseDate, Sku, UpdateProduct,
+            },
+            handlers::ProductResponse,
+            service::CreateProductInput,
+        },
+        shared::{
+            AppUser, Credential, ensure_admin, ensure_interactive, ensure_recent_authentication,
+        },
+    },
+    validate_and_parse,
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateProductRequest {
+    #[schema(example = "DZ5485-612")]
+    pub sku: String,
+    pub brand: String,
+    pub model: String,
+    pub colorway: String,
+    pub description: Option<String>,
+    #[schema(value_type = ProductGender)]
+    pub gender: String,
+    #[schema(format = Date, example = "2025-11-28")]
+    pub release_date: String,
+    /// In the currency's minor unit.
+    pub price_cents: i64,
+    #[schema(example = "USD")]
+    pub currency: String,
+}
+
+impl TryFrom<CreateProductRequest> for CreateProductInput {
+    type Error = Error;
+
+    fn try_from(value: CreateProductRequest) -> std::result::Result<Self, Self::Error> {
+        let (sku, brand, model, colorway, description, gender, release_date, price, currency) = validate_and_parse!(
+            sku => Sku::parse(value.sku),
+            brand => Brand::parse(value.brand),
+            model => ProductModel::parse(value.model),
+            colorway => Colorway::parse(value.colorway),
+            description => value.description.map(ProductDescription::parse).transpose(),
+            gender => ProductGender::parse(value.gender),
+            release_date => ReleaseDate::parse(value.release_date),
+            price_cents => PriceCents::parse(value.price_cents),
+            currency => Currency::parse(value.currency),
+        );
+
+        Ok(CreateProductInput {
+            sku,
+            brand,
+            model,
+            colorway,
+            description,
+            gender,
+            release_date,
+            price,
+            currency,
+        })
+    }
+}
+
+/// Omitted fields keep their current value. The SKU, and with it the slug,
+/// can't be changed.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateProductRequest {
+    pub brand: Option<String>,
+    pub model: Option<String>,
+    pub colorway: Option<String>,
+    pub description: Option<String>,
+    #[schema(value_type = Option<ProductGender>)]
+    pub gender: Option<String>,
+    #[schema(format = Date, example = "2025-11-28")]
+    pub release_date: Option<String>,
+    pub price_cents: Option<i64>,
+    pub currency: Option<String>,
+}
+
+impl TryFrom<UpdateProductRequest> for UpdateProduct {
+    type Error = Error;
+
+    fn try_from(value: UpdateProductRequest) -> std::result::Result<Self, Self::Error> {
+        let (brand, model, colorway, description, gender, release_date, price, currency) = validate_and_parse!(
+            brand => value.brand.map(Brand::parse).transpose(),
+            model => value.model.map(ProductModel::parse).transpose(),
+            colorway => value.colorway.map(Colorway::parse).transpose(),
+            description => value.description.map(ProductDescription::parse).transpose(),
+            gender => value.gender.map(ProductGender::parse).transpose(),
+            release_date => value.release_date.map(ReleaseDate::parse).transpose(),
+            price_cents => value.price_cents.map(PriceCents::parse).transpose(),
+            currency => value.currency.map(Currency::parse).transpose(),
+        );
+
+        Ok(UpdateProduct {
+            brand,
+            model,
+            colorway,
+            description,
+            gender,
+            release_date,
+            price,
+            currency,
+        })
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/products",
+    tag = "catalog",
+    request_body = CreateProductRequest,
+    security(("session_cookie" = [], "csrf_token" = []), ("bearer" = [])),
+    responses(
+        (status = 201, description = "The new product", body = ApiResponse<ProductResponse>),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not an admin, signed in with an API key, CSRF token missing, or recent authentication required", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "A product with this SKU already exists", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn create_product_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    WithRejection(Json(data), _): WithRejection<Json<CreateProductRequest>, Error>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    ensure_admin(&user)?;
+    ensure_recent_authentication(&credential, state.auth_service.reauthentication_max_age())?;
+
+    let product = state
+        .catalog_service
+        .create_product(data.try_into()?)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: ProductResponse::from(product),
+        }),
+    ))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/products/{id}",
+    tag = "catalog",
+    params(("id" = Uuid, Path, description = "Product id")),
+    request_body = UpdateProductRequest,
+    security(("session_cookie" = [], "csrf_token" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "The updated product", body = ApiResponse<ProductResponse>),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not an admin, signed in with an API key, CSRF token missing, or recent authentication required", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "No such product", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn update_product_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    Path(id): Path<String>,
+    WithRejection(Json(data), _): WithRejection<Json<UpdateProductRequest>, Error>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    ensure_admin(&user)?;
+    ensure_recent_authentication(&credential, state.auth_service.reauthentication_max_age())?;
+    let id = Uuid::parse_str(&id).map_err(|_| Error::NotFound("Product not found".into()))?;
+
+    let product = state
+        .catalog_service
+        .update_product(&id, data.try_into()?)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: ProductResponse::from(product),
+        }),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/products/{id}/archive",
+    tag = "catalog",
+    params(("id" = Uuid, Path, description = "Product id")),
+    security(("session_cookie" = [], "csrf_token" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "The product, no longer in the public catalog", body = ApiResponse<ProductResponse>),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not an admin, signed in with an API key, CSRF token missing, or recent authentication required", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "No such product", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn archive_product_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    ensure_admin(&user)?;
+    ensure_recent_authentication(&credential, state.auth_service.reauthentication_max_age())?;
+    let id = Uuid::parse_str(&id).map_err(|_| Error::NotFound("Product not found".into()))?;
+
+    let product = state.catalog_service.archive_product(&id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: ProductResponse::from(product),
+        }),
+    ))
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+use crate::features::catalog::domain::{Product, ProductGender};
+
+mod admin_products_handler;
+mod products_handler;
+
+pub use admin_products_handler::{
+    CreateProductRequest, UpdateProductRequest, archive_product_v1, create_product_v1,
+    update_product_v1,
+};
+pub use products_handler::{get_product_v1, list_products_v1};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(products_handler::list_products_v1, products_handler::get_product_v1),
+    tags((name = "catalog", description = "Sneaker products")),
+)]
+pub struct CatalogApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        admin_products_handler::create_product_v1,
+        admin_products_handler::update_product_v1,
+        admin_products_handler::archive_product_v1,
+    ),
+    tags((name = "catalog", description = "Sneaker products")),
+)]
+pub struct CatalogAdminApi;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ProductResponse {
+    pub id: Uuid,
+    pub slug: String,
+    pub sku: String,
+    pub brand: String,
+    pub model: String,
+    pub colorway: String,
+    pub description: Option<String>,
+    pub gender: ProductGender,
+    #[schema(format = Date, example = "2025-11-28")]
+    pub release_date: String,
+    /// In the currency's minor unit.
+    pub price_cents: i64,
+    #[schema(example = "USD")]
+    pub currency: String,
+    /// Set once an admin archives the product; archived products are only
+    /// returned by admin endpoints.
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub archived_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<Product> for ProductResponse {
+    fn from(value: Product) -> Self {
+        Self {
+            id: value.id,
+            slug: value.slug,
+            sku: value.sku,
+            brand: value.brand,
+            model: value.model,
+            colorway: value.colorway,
+            description: value.description,
+            gender: value.gender,
+            release_date: value.release_date.to_string(),
+            price_cents: value.price_cents,
+            currency: value.currency,
+            archived_at: value.archived_at,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
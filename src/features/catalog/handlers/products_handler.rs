@@ -0,0 +1,71 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+
+use crate::{
+    ApiResponse, Problem, Result,
+    app::AppState,
+    features::{
+        catalog::{handlers::ProductResponse, listing::ProductListing},
+        shared::{Page, PageQuery},
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/products",
+    tag = "catalog",
+    params(
+        ("limit" = Option<u32>, Query, description = "Page size, 1 to 100; defaults to 24"),
+        ("cursor" = Option<String>, Query, description = "`next_cursor` from the previous page, with the same sort and filters"),
+        ("sort" = Option<String>, Query, description = "`release_date`, `price` or `created_at`, prefixed with `-` for descending; defaults to `-release_date`"),
+        ("gender" = Option<String>, Query, description = "`men`, `women`, `unisex` or `kids`"),
+        ("brand" = Option<String>, Query, description = "Case-insensitive brand prefix"),
+    ),
+    responses(
+        (status = 200, description = "A page of products", body = ApiResponse<Page<ProductResponse>>),
+        (status = 400, description = "Invalid query parameters or cursor", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn list_products_v1(
+    State(state): State<AppState>,
+    page: Result<PageQuery<ProductListing>>,
+) -> Result<impl IntoResponse> {
+    let page = state.catalog_service.list_products(&page?).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: page.map(ProductResponse::from),
+        }),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/products/{slug}",
+    tag = "catalog",
+    params(("slug" = String, Path, description = "Product slug")),
+    responses(
+        (status = 200, description = "The product", body = ApiResponse<ProductResponse>),
+        (status = 404, description = "No such product, or it was archived", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn get_product_v1(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse> {
+    let product = state.catalog_service.get_product(&slug).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: ProductResponse::from(product),
+        }),
+    ))
+}
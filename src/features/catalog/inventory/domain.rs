@@ -0,0 +1,284 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    Error, Result,
+    features::catalog::{PriceCents, ProductGender, Sku},
+    i18n::Message,
+};
+
+const SIZE_MIN: f64 = 1.0;
+const SIZE_MAX: f64 = 22.0;
+const EU_SIZE_MIN: u32 = 15;
+const EU_SIZE_MAX: u32 = 55;
+pub const STOCK_MAX: i32 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize, ToSchema)]
+#[sqlx(type_name = "shoe_width", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ShoeWidth {
+    Narrow,
+    Standard,
+    Wide,
+    ExtraWide,
+}
+
+impl ShoeWidth {
+    pub fn parse(value: String) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "narrow" => Ok(ShoeWidth::Narrow),
+            "standard" => Ok(ShoeWidth::Standard),
+            "wide" => Ok(ShoeWidth::Wide),
+            "extra_wide" => Ok(ShoeWidth::ExtraWide),
+            _ => Err(Error::DomainValidationError(vec![Message::new(
+                "variant.invalid_width",
+            )])),
+        }
+    }
+}
+
+/// A US or UK size: whole or half sizes only.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShoeSize(f64);
+
+impl ShoeSize {
+    pub fn parse(value: f64) -> Result<Self> {
+        if !(SIZE_MIN..=SIZE_MAX).contains(&value) || (value * 2.0).fract() != 0.0 {
+            return Err(Error::DomainValidationError(vec![
+                Message::new("variant.invalid_size")
+                    .with("min", SIZE_MIN)
+                    .with("max", SIZE_MAX),
+            ]));
+        }
+
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+
+    /// The UK size for this US size on a generic chart. Brands' own charts
+    /// differ by up to half a size, so admins can pass the exact one instead.
+    pub fn to_uk(self, gender: ProductGender) -> Result<ShoeSize> {
+        let offset = match gender {
+            ProductGender::Men | ProductGender::Unisex => 1.0,
+            ProductGender::Women => 2.0,
+            ProductGender::Kids => 0.5,
+        };
+
+        ShoeSize::parse(self.0 - offset)
+    }
+
+    /// The EU size for this US size on the same generic chart as [`to_uk`](Self::to_uk).
+    pub fn to_eu(self, gender: ProductGender) -> Result<EuSize> {
+        let offset = match gender {
+            ProductGender::Men | ProductGender::Unisex => 33.0,
+            ProductGender::Women => 31.0,
+            ProductGender::Kids => 32.5,
+        };
+
+        EuSize::parse((self.0 + offset).to_string())
+    }
+}
+
+/// An EU size label. Brands disagree on EU steps, so besides whole and
+/// half sizes (`42`, `42.5`) thirds are accepted too (`42 2/3`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EuSize(String);
+
+impl EuSize {
+    pub fn parse(value: String) -> Result<Self> {
+        let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+        let (whole, step) = match value.split_once([' ', '.']) {
+            Some((whole, step)) => (whole, Some(step)),
+            None => (value.as_str(), None),
+        };
+        let valid = whole
+            .parse::<u32>()
+            .is_ok_and(|size| (EU_SIZE_MIN..=EU_SIZE_MAX).contains(&size))
+            && whole.chars().all(|c| c.is_ascii_digit())
+            && match step {
+                None => true,
+                Some("5") => value.contains('.'),
+                Some("1/3" | "2/3") => value.contains(' '),
+                Some(_) => false,
+            };
+
+        if !valid {
+            return Err(Error::DomainValidationError(vec![Message::new(
+                "variant.invalid_eu_size",
+            )]));
+        }
+
+        Ok(Self(value))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StockQuantity(i32);
+
+impl StockQuantity {
+    pub fn parse(value: i32) -> Result<Self> {
+        if !(0..=STOCK_MAX).contains(&value) {
+            return Err(Error::DomainValidationError(vec![
+                Message::new("variant.invalid_stock").with("max", STOCK_MAX),
+            ]));
+        }
+
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> i32 {
+        self.0
+    }
+}
+
+/// Units added to (positive) or taken from (negative) a variant's stock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StockAdjustment(i32);
+
+impl StockAdjustment {
+    pub fn parse(value: i32) -> Result<Self> {
+        if value == 0 || !(-STOCK_MAX..=STOCK_MAX).contains(&value) {
+            return Err(Error::DomainValidationError(vec![
+                Message::new("variant.invalid_adjustment").with("max", STOCK_MAX),
+            ]));
+        }
+
+        Ok(Self(value))
+    }
+
+    pub fn delta(&self) -> i32 {
+        self.0
+    }
+}
+
+#[derive(Debug)]
+pub struct ProductVariant {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub sku: String,
+    pub size_us: f64,
+    pub size_uk: f64,
+    pub size_eu: String,
+    pub width: ShoeWidth,
+    pub stock_quantity: i32,
+    pub price_cents_override: Option<i64>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct NewProductVariant {
+    pub sku: Sku,
+    pub size_us: ShoeSize,
+    pub size_uk: ShoeSize,
+    pub size_eu: EuSize,
+    pub width: ShoeWidth,
+    pub stock_quantity: StockQuantity,
+    pub price_override: Option<PriceCents>,
+}
+
+/// A size that can be bought right now, priced with any override applied.
+#[derive(Debug)]
+pub struct AvailableSize {
+    pub variant_id: Uuid,
+    pub sku: String,
+    pub size_us: f64,
+    pub size_uk: f64,
+    pub size_eu: String,
+    pub width: ShoeWidth,
+    pub price_cents: i64,
+}
+
+#[derive(Debug)]
+pub struct ProductAvailability {
+    pub product_id: Uuid,
+    pub slug: String,
+    pub currency: String,
+    pub sizes: Vec<AvailableSize>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sizes_come_in_halves() {
+        assert_eq!(ShoeSize::parse(9.5).unwrap().value(), 9.5);
+        assert!(ShoeSize::parse(10.0).is_ok());
+        assert!(ShoeSize::parse(9.25).is_err());
+        assert!(ShoeSize::parse(0.5).is_err());
+        assert!(ShoeSize::parse(SIZE_MAX + 0.5).is_err());
+        assert!(ShoeSize::parse(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn sizes_convert_by_gender() {
+        let size = ShoeSize::parse(9.5).unwrap();
+        assert_eq!(size.to_uk(ProductGender::Men).unwrap().value(), 8.5);
+        assert_eq!(size.to_uk(ProductGender::Women).unwrap().value(), 7.5);
+        assert_eq!(size.to_eu(ProductGender::Unisex).unwrap().as_str(), "42.5");
+        assert_eq!(size.to_eu(ProductGender::Women).unwrap().as_str(), "40.5");
+        assert_eq!(
+            ShoeSize::parse(10.0)
+                .unwrap()
+                .to_eu(ProductGender::Men)
+                .unwrap()
+                .as_str(),
+            "43"
+        );
+        assert!(
+            ShoeSize::parse(1.0)
+                .unwrap()
+                .to_uk(ProductGender::Women)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn eu_sizes_allow_halves_and_thirds() {
+        assert_eq!(EuSize::parse("42".into()).unwrap().as_str(), "42");
+        assert_eq!(EuSize::parse("42.5".into()).unwrap().as_str(), "42.5");
+        assert_eq!(
+            EuSize::parse(" 42  2/3 ".into()).unwrap().as_str(),
+            "42 2/3"
+        );
+
+        for invalid in [
+            "", "42,5", "42.3", "42 1/2", "42.2/3", "4x", "+42", "99", "42 5",
+        ] {
+            assert!(EuSize::parse(invalid.into()).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn width_uses_snake_case() {
+        assert_eq!(
+            ShoeWidth::parse("Extra_Wide".into()).unwrap(),
+            ShoeWidth::ExtraWide
+        );
+        assert!(ShoeWidth::parse("extra wide".into()).is_err());
+    }
+
+    #[test]
+    fn stock_must_not_be_negative() {
+        assert_eq!(StockQuantity::parse(0).unwrap().value(), 0);
+        assert!(StockQuantity::parse(-1).is_err());
+        assert!(StockQuantity::parse(STOCK_MAX + 1).is_err());
+    }
+
+    #[test]
+    fn adjustments_must_change_stock() {
+        assert_eq!(StockAdjustment::parse(-3).unwrap().delta(), -3);
+        assert!(StockAdjustment::parse(0).is_err());
+        assert!(StockAdjustment::parse(STOCK_MAX + 1).is_err());
+    }
+}
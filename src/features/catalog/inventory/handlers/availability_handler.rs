@@ -0,0 +1,37 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+
+use crate::{
+    ApiResponse, Problem, Result, app::AppState,
+    features::catalog::inventory::handlers::ProductAvailabilityResponse,
+};
+
+#[utoipa::path(
+    get,
+    path = "/products/{slug}/availability",
+    tag = "inventory",
+    params(("slug" = String, Path, description = "Product slug")),
+    responses(
+        (status = 200, description = "The sizes currently in stock", body = ApiResponse<ProductAvailabilityResponse>),
+        (status = 404, description = "No such product, or it was archived", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn get_availability_v1(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse> {
+    let product = state.catalog_service.get_product(&slug).await?;
+    let availability = state.inventory_service.get_availability(product).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: ProductAvailabilityResponse::from(availability),
+        }),
+    ))
+}
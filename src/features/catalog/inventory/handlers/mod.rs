@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+use crate::features::catalog::inventory::domain::{
+    AvailableSize, ProductAvailability, ProductVariant, ShoeWidth,
+};
+
+mod availability_handler;
+mod variants_handler;
+
+pub use availability_handler::get_availability_v1;
+pub use variants_handler::{
+    AdjustStockRequest, CreateVariantRequest, SetPriceOverrideRequest, adjust_stock_v1,
+    create_variant_v1, list_variants_v1, set_price_override_v1,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(availability_handler::get_availability_v1),
+    tags((name = "inventory", description = "Sizes and stock of sneaker products")),
+)]
+pub struct InventoryApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        variants_handler::create_variant_v1,
+        variants_handler::list_variants_v1,
+        variants_handler::adjust_stock_v1,
+        variants_handler::set_price_override_v1,
+    ),
+    tags((name = "inventory", description = "Sizes and stock of sneaker products")),
+)]
+pub struct InventoryAdminApi;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct VariantResponse {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub sku: String,
+    #[schema(example = 9.5)]
+    pub size_us: f64,
+    #[schema(example = 8.5)]
+    pub size_uk: f64,
+    #[schema(example = "42.5")]
+    pub size_eu: String,
+    pub width: ShoeWidth,
+    pub stock_quantity: i32,
+    /// Replaces the product price for this variant when set.
+    pub price_cents_override: Option<i64>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<ProductVariant> for VariantResponse {
+    fn from(value: ProductVariant) -> Self {
+        Self {
+            id: value.id,
+            product_id: value.product_id,
+            sku: value.sku,
+            size_us: value.size_us,
+            size_uk: value.size_uk,
+            size_eu: value.size_eu,
+            width: value.width,
+            stock_quantity: value.stock_quantity,
+            price_cents_override: value.price_cents_override,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct AvailableSizeResponse {
+    pub variant_id: Uuid,
+    pub sku: String,
+    #[schema(example = 9.5)]
+    pub size_us: f64,
+    #[schema(example = 8.5)]
+    pub size_uk: f64,
+    #[schema(example = "42.5")]
+    pub size_eu: String,
+    pub width: ShoeWidth,
+    /// The variant's price, in the product currency's minor unit.
+    pub price_cents: i64,
+}
+
+impl From<AvailableSize> for AvailableSizeResponse {
+    fn from(value: AvailableSize) -> Self {
+        Self {
+            variant_id: value.variant_id,
+            sku: value.sku,
+            size_us: value.size_us,
+            size_uk: value.size_uk,
+            size_eu: value.size_eu,
+            width: value.width,
+            price_cents: value.price_cents,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ProductAvailabilityResponse {
+    pub product_id: Uuid,
+    pub slug: String,
+    #[schema(example = "USD")]
+    pub currency: String,
+    /// In-stock sizes only, by width and then US size.
+    pub sizes: Vec<AvailableSizeResponse>,
+}
+
+impl From<ProductAvailability> for ProductAvailabilityResponse {
+    fn from(value: ProductAvailability) -> Self {
+        Self {
+            product_id: value.product_id,
+            slug: value.slug,
+            currency: value.currency,
+            sizes: value.sizes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use axum_extra::extract::WithRejection;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    ApiResponse, Error, Problem, Result,
+    app::AppState,
+    features::{
+        catalog::{
+            PriceCents, Sku,
+            inventory::{
+                domain::{EuSize, ShoeSize, ShoeWidth, StockAdjustment, StockQuantity},
+                handlers::VariantResponse,
+                service::CreateVariantInput,
+            },
+        },
+        shared::{
+            AppUser, Credential, ensure_admin, ensure_interactive, ensure_recent_authentication,
+        },
+    },
+    validate_and_parse,
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateVariantRequest {
+    #[schema(example = "DZ5485-612-095")]
+    pub sku: String,
+    /// Whole or half sizes.
+    #[schema(example = 9.5)]
+    pub size_us: f64,
+    /// Converted from `size_us` when omitted.
+    #[schema(example = 8.5)]
+    pub size_uk: Option<f64>,
+    /// Converted from `size_us` when omitted. Thirds are written as `42 2/3`.
+    #[schema(example = "42.5")]
+    pub size_eu: Option<String>,
+    /// Defaults to `standard`.
+    #[schema(value_type = Option<ShoeWidth>)]
+    pub width: Option<String>,
+    /// Defaults to 0.
+    pub stock_quantity: Option<i32>,
+    /// Replaces the product price for this variant, in the currency's minor unit.
+    pub price_cents_override: Option<i64>,
+}
+
+impl TryFrom<CreateVariantRequest> for CreateVariantInput {
+    type Error = Error;
+
+    fn try_from(value: CreateVariantRequest) -> std::result::Result<Self, Self::Error> {
+        let (sku, size_us, size_uk, size_eu, width, stock_quantity, price_override) = validate_and_parse!(
+            sku => Sku::parse(value.sku),
+            size_us => ShoeSize::parse(value.size_us),
+            size_uk => value.size_uk.map(ShoeSize::parse).transpose(),
+            size_eu => value.size_eu.map(EuSize::parse).transpose(),
+            width => value.width.map_or(Ok(ShoeWidth::Standard), ShoeWidth::parse),
+            stock_quantity => StockQuantity::parse(value.stock_quantity.unwrap_or_default()),
+            price_cents_override => value.price_cents_override.map(PriceCents::parse).transpose(),
+        );
+
+        Ok(CreateVariantInput {
+            sku,
+            size_us,
+            size_uk,
+            size_eu,
+            width,
+            stock_quantity,
+            price_override,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AdjustStockRequest {
+    /// Units received (positive) or removed (negative).
+    #[schema(example = -1)]
+    pub delta: i32,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetPriceOverrideRequest {
+    /// `null` clears the override so the variant sells at the product price.
+    pub price_cents_override: Option<i64>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/products/{id}/variants",
+    tag = "inventory",
+    params(("id" = Uuid, Path, description = "Product id")),
+    request_body = CreateVariantRequest,
+    security(("session_cookie" = [], "csrf_token" = []), ("bearer" = [])),
+    responses(
+        (status = 201, description = "The new variant", body = ApiResponse<VariantResponse>),
+        (status = 400, description = "Invalid request, or a size with no conversion", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not an admin, signed in with an API key, CSRF token missing, or recent authentication required", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "No such product", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "A variant with this SKU, or this size and width, already exists", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn create_variant_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    Path(id): Path<String>,
+    WithRejection(Json(data), _): WithRejection<Json<CreateVariantRequest>, Error>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    ensure_admin(&user)?;
+    ensure_recent_authentication(&credential, state.auth_service.reauthentication_max_age())?;
+    let id = Uuid::parse_str(&id).map_err(|_| Error::NotFound("Product not found".into()))?;
+
+    let variant = state
+        .inventory_service
+        .create_variant(&id, data.try_into()?)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: VariantResponse::from(variant),
+        }),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/products/{id}/variants",
+    tag = "inventory",
+    params(("id" = Uuid, Path, description = "Product id")),
+    security(("session_cookie" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "All variants of the product, including sold-out ones", body = ApiResponse<Vec<VariantResponse>>),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not an admin or signed in with an API key", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "No such product", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn list_variants_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    ensure_admin(&user)?;
+    let id = Uuid::parse_str(&id).map_err(|_| Error::NotFound("Product not found".into()))?;
+
+    let variants = state.inventory_service.list_variants(&id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: variants
+                .into_iter()
+                .map(VariantResponse::from)
+                .collect::<Vec<_>>(),
+        }),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/variants/{id}/stock",
+    tag = "inventory",
+    params(("id" = Uuid, Path, description = "Variant id")),
+    request_body = AdjustStockRequest,
+    security(("session_cookie" = [], "csrf_token" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "The variant with its new stock", body = ApiResponse<VariantResponse>),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not an admin, signed in with an API key, CSRF token missing, or recent authentication required", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "No such variant", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Stock would go below 0 or above 1000000", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn adjust_stock_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    Path(id): Path<String>,
+    WithRejection(Json(data), _): WithRejection<Json<AdjustStockRequest>, Error>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    ensure_admin(&user)?;
+    ensure_recent_authentication(&credential, state.auth_service.reauthentication_max_age())?;
+    let id = Uuid::parse_str(&id).map_err(|_| Error::NotFound("Variant not found".into()))?;
+    let delta = validate_and_parse!(delta => StockAdjustment::parse(data.delta));
+
+    let variant = state.inventory_service.adjust_stock(&id, delta).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: VariantResponse::from(variant),
+        }),
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/variants/{id}/price",
+    tag = "inventory",
+    params(("id" = Uuid, Path, description = "Variant id")),
+    request_body = SetPriceOverrideRequest,
+    security(("session_cookie" = [], "csrf_token" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "The variant with its new price override", body = ApiResponse<VariantResponse>),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not an admin, signed in with an API key, CSRF token missing, or recent authentication required", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "No such variant", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn set_price_override_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    Path(id): Path<String>,
+    WithRejection(Json(data), _): WithRejection<Json<SetPriceOverrideRequest>, Error>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    ensure_admin(&user)?;
+    ensure_recent_authentication(&credential, state.auth_service.reauthentication_max_age())?;
+    let id = Uuid::parse_str(&id).map_err(|_| Error::NotFound("Variant not found".into()))?;
+    let price = validate_and_parse!(
+        price_cents_override => data.price_cents_override.map(PriceCents::parse).transpose()
+    );
+
+    let variant = state
+        .inventory_service
+        .set_price_override(&id, price)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: VariantResponse::from(variant),
+        }),
+    ))
+}
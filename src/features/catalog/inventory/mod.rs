@@ -0,0 +1,93 @@
+use axum::{
+    Router, middleware,
+    routing::{get, post, put},
+};
+use sqlx::PgPool;
+
+use crate::{
+    app::AppState,
+    features::catalog::inventory::repository::InventoryRepository,
+    middlewares::{RateLimitLayer, authenticate, identify},
+};
+
+mod domain;
+mod handlers;
+mod repository;
+mod service;
+
+pub use domain::*;
+pub use handlers::{
+    AdjustStockRequest, AvailableSizeResponse, CreateVariantRequest, InventoryAdminApi,
+    InventoryApi, ProductAvailabilityResponse, SetPriceOverrideRequest, VariantResponse,
+};
+pub use service::{CreateVariantInput, InventoryService};
+
+use handlers::*;
+
+pub struct InventoryModule {
+    pub inventory_service: InventoryService,
+}
+
+impl InventoryModule {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            inventory_service: InventoryService::new(InventoryRepository::new(pool)),
+        }
+    }
+
+    /// Public routes, merged with [`CatalogModule::v1`](crate::features::catalog::CatalogModule::v1)
+    /// under `/api/v1`.
+    pub fn v1(state: AppState) -> Router<AppState> {
+        let limit = || {
+            RateLimitLayer::new(
+                state.redis.clone(),
+                state.settings.clone(),
+                "catalog",
+                |r| &r.catalog,
+            )
+        };
+
+        Router::new().route(
+            "/products/{slug}/availability",
+            get(get_availability_v1).layer(limit()),
+        )
+    }
+
+    /// Admin routes, merged with [`AdminModule::v1`](crate::features::admin::AdminModule::v1)
+    /// under `/api/v1/admin`.
+    pub fn admin_v1(state: AppState) -> Router<AppState> {
+        let limit = || {
+            RateLimitLayer::new(
+                state.redis.clone(),
+                state.settings.clone(),
+                "admin_inventory",
+                |r| &r.admin,
+            )
+        };
+        let identify = || middleware::from_fn_with_state(state.clone(), identify);
+
+        Router::new()
+            .route(
+                "/products/{id}/variants",
+                post(create_variant_v1)
+                    .get(list_variants_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit())
+                    .layer(identify()),
+            )
+            .route(
+                "/variants/{id}/stock",
+                post(adjust_stock_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit())
+                    .layer(identify()),
+            )
+            .route(
+                "/variants/{id}/price",
+                put(set_price_override_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit())
+                    .layer(identify()),
+            )
+    }
+}
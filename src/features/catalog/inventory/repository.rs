@@ -0,0 +1,179 @@
+use sqlx::{PgPool, query_as, query_scalar};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    Result,
+    features::catalog::{
+        ProductGender,
+        inventory::domain::{
+            AvailableSize, NewProductVariant, ProductVariant, STOCK_MAX, ShoeWidth, StockAdjustment,
+        },
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct InventoryRepository {
+    pool: PgPool,
+}
+
+impl InventoryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Archived products count too, so admins can stock up before a relaunch.
+    #[instrument(skip_all, name = "inventoryrepository - get product gender")]
+    pub async fn get_product_gender(&self, product_id: &Uuid) -> Result<Option<ProductGender>> {
+        let gender = query_scalar!(
+            r#"SELECT gender as "gender: ProductGender" FROM products WHERE id = $1"#,
+            product_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(gender)
+    }
+
+    #[instrument(skip_all, name = "inventoryrepository - create variant")]
+    pub async fn create_variant(
+        &self,
+        product_id: &Uuid,
+        variant: &NewProductVariant,
+    ) -> Result<ProductVariant> {
+        let variant = query_as!(
+            ProductVariant,
+            r#"
+                INSERT INTO product_variants
+                    (product_id, sku, size_us, size_uk, size_eu, width, stock_quantity,
+                     price_cents_override)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                RETURNING
+                    id, product_id, sku, size_us, size_uk, size_eu, width as "width: ShoeWidth",
+                    stock_quantity, price_cents_override, created_at, updated_at
+            "#,
+            product_id,
+            variant.sku.as_str(),
+            variant.size_us.value(),
+            variant.size_uk.value(),
+            variant.size_eu.as_str(),
+            variant.width as ShoeWidth,
+            variant.stock_quantity.value(),
+            variant.price_override.map(|p| p.cents())
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(variant)
+    }
+
+    #[instrument(skip_all, name = "inventoryrepository - list variants")]
+    pub async fn list_variants(&self, product_id: &Uuid) -> Result<Vec<ProductVariant>> {
+        let variants = query_as!(
+            ProductVariant,
+            r#"
+                SELECT
+                    id, product_id, sku, size_us, size_uk, size_eu, width as "width: ShoeWidth",
+                    stock_quantity, price_cents_override, created_at, updated_at
+                FROM product_variants
+                WHERE product_id = $1
+                ORDER BY width, size_us
+            "#,
+            product_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(variants)
+    }
+
+    #[instrument(skip_all, name = "inventoryrepository - variant exists")]
+    pub async fn variant_exists(&self, id: &Uuid) -> Result<bool> {
+        let exists = query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM product_variants WHERE id = $1) as "exists!""#,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Applies the adjustment in a single statement, so concurrent
+    /// adjustments can't oversell. Returns `None` when the variant doesn't
+    /// exist or the stock would leave `0..=STOCK_MAX`.
+    #[instrument(skip_all, name = "inventoryrepository - adjust stock")]
+    pub async fn adjust_stock(
+        &self,
+        id: &Uuid,
+        adjustment: StockAdjustment,
+    ) -> Result<Option<ProductVariant>> {
+        let variant = query_as!(
+            ProductVariant,
+            r#"
+                UPDATE product_variants
+                SET stock_quantity = stock_quantity + $2,
+                    updated_at = NOW()
+                WHERE id = $1 AND stock_quantity + $2 BETWEEN 0 AND $3
+                RETURNING
+                    id, product_id, sku, size_us, size_uk, size_eu, width as "width: ShoeWidth",
+                    stock_quantity, price_cents_override, created_at, updated_at
+            "#,
+            id,
+            adjustment.delta(),
+            STOCK_MAX
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(variant)
+    }
+
+    /// `None` clears the override, so the variant sells at the product price.
+    #[instrument(skip_all, name = "inventoryrepository - set price override")]
+    pub async fn set_price_override(
+        &self,
+        id: &Uuid,
+        price_cents: Option<i64>,
+    ) -> Result<Option<ProductVariant>> {
+        let variant = query_as!(
+            ProductVariant,
+            r#"
+                UPDATE product_variants
+                SET price_cents_override = $2,
+                    updated_at = NOW()
+                WHERE id = $1
+                RETURNING
+                    id, product_id, sku, size_us, size_uk, size_eu, width as "width: ShoeWidth",
+                    stock_quantity, price_cents_override, created_at, updated_at
+            "#,
+            id,
+            price_cents
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(variant)
+    }
+
+    #[instrument(skip_all, name = "inventoryrepository - list available sizes")]
+    pub async fn list_available_sizes(&self, product_id: &Uuid) -> Result<Vec<AvailableSize>> {
+        let sizes = query_as!(
+            AvailableSize,
+            r#"
+                SELECT v.id as variant_id, v.sku, v.size_us, v.size_uk, v.size_eu,
+                       v.width as "width: ShoeWidth",
+                       COALESCE(v.price_cents_override, p.price_cents) as "price_cents!"
+                FROM product_variants v
+                JOIN products p ON p.id = v.product_id
+                WHERE v.product_id = $1 AND v.stock_quantity > 0
+                ORDER BY v.width, v.size_us
+            "#,
+            product_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sizes)
+    }
+}
@@ -0,0 +1,137 @@
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    Error, Result,
+    features::{
+        catalog::{
+            PriceCents, Product, Sku,
+            inventory::{
+                domain::{
+                    EuSize, NewProductVariant, ProductAvailability, ProductVariant, STOCK_MAX,
+                    ShoeSize, ShoeWidth, StockAdjustment, StockQuantity,
+                },
+                repository::InventoryRepository,
+            },
+        },
+        shared::map_unique_violation,
+    },
+    validate_and_parse,
+};
+
+pub struct InventoryService {
+    repository: InventoryRepository,
+}
+
+/// UK and EU sizes left `None` are converted from the US size using the
+/// product's gender.
+pub struct CreateVariantInput {
+    pub sku: Sku,
+    pub size_us: ShoeSize,
+    pub size_uk: Option<ShoeSize>,
+    pub size_eu: Option<EuSize>,
+    pub width: ShoeWidth,
+    pub stock_quantity: StockQuantity,
+    pub price_override: Option<PriceCents>,
+}
+
+impl InventoryService {
+    pub fn new(repository: InventoryRepository) -> Self {
+        Self { repository }
+    }
+
+    #[instrument(name = "inventory.create_variant", skip(self, data), fields(sku = data.sku.as_str()))]
+    pub async fn create_variant(
+        &self,
+        product_id: &Uuid,
+        data: CreateVariantInput,
+    ) -> Result<ProductVariant> {
+        let gender = self
+            .repository
+            .get_product_gender(product_id)
+            .await?
+            .ok_or(Error::NotFound("Product not found".into()))?;
+
+        let (size_uk, size_eu) = validate_and_parse!(
+            size_uk => data.size_uk.map_or_else(|| data.size_us.to_uk(gender), Ok),
+            size_eu => data.size_eu.map_or_else(|| data.size_us.to_eu(gender), Ok),
+        );
+
+        self.repository
+            .create_variant(
+                product_id,
+                &NewProductVariant {
+                    sku: data.sku,
+                    size_us: data.size_us,
+                    size_uk,
+                    size_eu,
+                    width: data.width,
+                    stock_quantity: data.stock_quantity,
+                    price_override: data.price_override,
+                },
+            )
+            .await
+            .map_err(map_unique_violation(Some(Error::Conflict(
+                "A variant with this SKU, or this size and width, already exists".into(),
+            ))))
+    }
+
+    #[instrument(name = "inventory.list_variants", skip(self))]
+    pub async fn list_variants(&self, product_id: &Uuid) -> Result<Vec<ProductVariant>> {
+        if self
+            .repository
+            .get_product_gender(product_id)
+            .await?
+            .is_none()
+        {
+            return Err(Error::NotFound("Product not found".into()));
+        }
+
+        self.repository.list_variants(product_id).await
+    }
+
+    #[instrument(name = "inventory.adjust_stock", skip(self))]
+    pub async fn adjust_stock(
+        &self,
+        id: &Uuid,
+        adjustment: StockAdjustment,
+    ) -> Result<ProductVariant> {
+        if let Some(variant) = self.repository.adjust_stock(id, adjustment).await? {
+            return Ok(variant);
+        }
+
+        if self.repository.variant_exists(id).await? {
+            Err(Error::Conflict(format!(
+                "Stock can't go below 0 or above {STOCK_MAX}"
+            )))
+        } else {
+            Err(Error::NotFound("Variant not found".into()))
+        }
+    }
+
+    #[instrument(name = "inventory.set_price_override", skip(self))]
+    pub async fn set_price_override(
+        &self,
+        id: &Uuid,
+        price: Option<PriceCents>,
+    ) -> Result<ProductVariant> {
+        self.repository
+            .set_price_override(id, price.map(|p| p.cents()))
+            .await?
+            .ok_or(Error::NotFound("Variant not found".into()))
+    }
+
+    /// `product` comes from the public catalog, so archived products never
+    /// reach here.
+    #[instrument(name = "inventory.get_availability", skip_all, fields(slug = %product.slug))]
+    pub async fn get_availability(&self, product: Product) -> Result<ProductAvailability> {
+        let sizes = self.repository.list_available_sizes(&product.id).await?;
+
+        Ok(ProductAvailability {
+            product_id: product.id,
+            slug: product.slug,
+            currency: product.currency,
+            sizes,
+        })
+    }
+}
@@ -0,0 +1,74 @@
+use time::format_description::well_known::Rfc3339;
+
+use crate::features::{
+    catalog::domain::Product,
+    shared::{Column, FilterField, FilterKind, ListSpec, Listing, SortField},
+};
+
+/// `GET /api/v1/products`: latest releases first. Archived products are
+/// never listed.
+pub struct ProductListing;
+
+impl Listing for ProductListing {
+    const SPEC: &'static ListSpec = &ListSpec {
+        sorts: &[
+            SortField {
+                name: "release_date",
+                column: Column {
+                    name: "release_date",
+                    sql_type: "date",
+                },
+            },
+            SortField {
+                name: "price",
+                column: Column {
+                    name: "price_cents",
+                    sql_type: "int8",
+                },
+            },
+            SortField {
+                name: "created_at",
+                column: Column {
+                    name: "created_at",
+                    sql_type: "timestamptz",
+                },
+            },
+        ],
+        filters: &[
+            FilterField {
+                name: "gender",
+                column: "gender",
+                kind: FilterKind::Enum {
+                    sql_type: "product_gender",
+                    values: &["men", "women", "unisex", "kids"],
+                },
+            },
+            FilterField {
+                name: "brand",
+                column: "brand",
+                kind: FilterKind::Prefix,
+            },
+        ],
+        default_sort: "-release_date",
+        tiebreaker: Column {
+            name: "id",
+            sql_type: "uuid",
+        },
+        default_limit: 24,
+        max_limit: 100,
+    };
+
+    type Row = Product;
+
+    fn sort_value(row: &Product, sort: &SortField) -> String {
+        match sort.name {
+            "release_date" => row.release_date.to_string(),
+            "price" => row.price_cents.to_string(),
+            _ => row.created_at.format(&Rfc3339).unwrap_or_default(),
+        }
+    }
+
+    fn tiebreaker_value(row: &Product) -> String {
+        row.id.to_string()
+    }
+}
@@ -0,0 +1,92 @@
+use axum::{
+    Router, middleware,
+    routing::{get, patch, post},
+};
+use sqlx::PgPool;
+
+use crate::{
+    app::AppState,
+    features::catalog::repository::CatalogRepository,
+    middlewares::{RateLimitLayer, authenticate, identify},
+};
+
+mod domain;
+mod handlers;
+pub mod inventory;
+mod listing;
+mod repository;
+mod service;
+
+pub use domain::*;
+pub use handlers::{
+    CatalogAdminApi, CatalogApi, CreateProductRequest, ProductResponse, UpdateProductRequest,
+};
+pub use listing::ProductListing;
+pub use service::{CatalogService, CreateProductInput};
+
+use handlers::*;
+
+pub struct CatalogModule {
+    pub catalog_service: CatalogService,
+}
+
+impl CatalogModule {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            catalog_service: CatalogService::new(CatalogRepository::new(pool)),
+        }
+    }
+
+    /// Public routes, nested under `/api/v1`.
+    pub fn v1(state: AppState) -> Router<AppState> {
+        let limit = || {
+            RateLimitLayer::new(
+                state.redis.clone(),
+                state.settings.clone(),
+                "catalog",
+                |r| &r.catalog,
+            )
+        };
+
+        Router::new()
+            .route("/products", get(list_products_v1).layer(limit()))
+            .route("/products/{slug}", get(get_product_v1).layer(limit()))
+    }
+
+    /// Admin routes, merged with [`AdminModule::v1`](crate::features::admin::AdminModule::v1)
+    /// under `/api/v1/admin`.
+    pub fn admin_v1(state: AppState) -> Router<AppState> {
+        let limit = || {
+            RateLimitLayer::new(
+                state.redis.clone(),
+                state.settings.clone(),
+                "admin_catalog",
+                |r| &r.admin,
+            )
+        };
+        let identify = || middleware::from_fn_with_state(state.clone(), identify);
+
+        Router::new()
+            .route(
+                "/products",
+                post(create_product_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit())
+                    .layer(identify()),
+            )
+            .route(
+                "/products/{id}",
+                patch(update_product_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit())
+                    .layer(identify()),
+            )
+            .route(
+                "/products/{id}/archive",
+                post(archive_product_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit())
+                    .layer(identify()),
+            )
+    }
+}
@@ -0,0 +1,159 @@
+use sqlx::{PgPool, QueryBuilder, query_as};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    Result,
+    features::{
+        catalog::{
+            domain::{NewProduct, Product, ProductGender, UpdateProduct},
+            listing::ProductListing,
+        },
+        shared::PageQuery,
+    },
+};
+
+/// Columns of the dynamic listing query, which can't use `query_as!`.
+const PRODUCT_COLUMNS: &str = r#"
+    id, slug, sku, brand, model, colorway, description, gender, release_date,
+    price_cents, currency, archived_at, created_at, updated_at
+"#;
+
+#[derive(Debug, Clone)]
+pub struct CatalogRepository {
+    pool: PgPool,
+}
+
+impl CatalogRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    #[instrument(skip_all, name = "catalogrepository - create product")]
+    pub async fn create_product(&self, product: &NewProduct) -> Result<Product> {
+        let product = query_as!(
+            Product,
+            r#"
+                INSERT INTO products
+                    (slug, sku, brand, model, colorway, description, gender, release_date,
+                     price_cents, currency)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                RETURNING
+                    id, slug, sku, brand, model, colorway, description,
+                    gender as "gender: ProductGender", release_date, price_cents, currency,
+                    archived_at, created_at, updated_at
+            "#,
+            product.slug.as_str(),
+            product.sku.as_str(),
+            product.brand.as_ref(),
+            product.model.as_ref(),
+            product.colorway.as_ref(),
+            product.description.as_ref().map(AsRef::<str>::as_ref),
+            product.gender as ProductGender,
+            product.release_date.date(),
+            product.price.cents(),
+            product.currency.as_str()
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(product)
+    }
+
+    #[instrument(skip_all, name = "catalogrepository - update product")]
+    pub async fn update_product(
+        &self,
+        id: &Uuid,
+        product: UpdateProduct,
+    ) -> Result<Option<Product>> {
+        let product = query_as!(
+            Product,
+            r#"
+                UPDATE products
+                SET brand = COALESCE($2, brand),
+                    model = COALESCE($3, model),
+                    colorway = COALESCE($4, colorway),
+                    description = COALESCE($5, description),
+                    gender = COALESCE($6, gender),
+                    release_date = COALESCE($7, release_date),
+                    price_cents = COALESCE($8, price_cents),
+                    currency = COALESCE($9, currency),
+                    updated_at = NOW()
+                WHERE id = $1
+                RETURNING
+                    id, slug, sku, brand, model, colorway, description,
+                    gender as "gender: ProductGender", release_date, price_cents, currency,
+                    archived_at, created_at, updated_at
+            "#,
+            id,
+            product.brand.map(|s| s.into_inner()),
+            product.model.map(|s| s.into_inner()),
+            product.colorway.map(|s| s.into_inner()),
+            product.description.map(|s| s.into_inner()),
+            product.gender as Option<ProductGender>,
+            product.release_date.map(|d| d.date()),
+            product.price.map(|p| p.cents()),
+            product.currency.map(|c| c.as_str().to_owned())
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(product)
+    }
+
+    /// Archiving an archived product keeps its original `archived_at`.
+    #[instrument(skip_all, name = "catalogrepository - archive product")]
+    pub async fn archive_product(&self, id: &Uuid) -> Result<Option<Product>> {
+        let product = query_as!(
+            Product,
+            r#"
+                UPDATE products
+                SET archived_at = COALESCE(archived_at, NOW()),
+                    updated_at = NOW()
+                WHERE id = $1
+                RETURNING
+                    id, slug, sku, brand, model, colorway, description,
+                    gender as "gender: ProductGender", release_date, price_cents, currency,
+                    archived_at, created_at, updated_at
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(product)
+    }
+
+    #[instrument(skip_all, name = "catalogrepository - get active product by slug")]
+    pub async fn get_active_product_by_slug(&self, slug: &str) -> Result<Option<Product>> {
+        let product = query_as!(
+            Product,
+            r#"
+                SELECT
+                    id, slug, sku, brand, model, colorway, description,
+                    gender as "gender: ProductGender", release_date, price_cents, currency,
+                    archived_at, created_at, updated_at
+                FROM products
+                WHERE slug = $1 AND archived_at IS NULL
+            "#,
+            slug
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(product)
+    }
+
+    #[instrument(skip_all, name = "catalogrepository - list active products")]
+    pub async fn list_active_products(
+        &self,
+        page: &PageQuery<ProductListing>,
+    ) -> Result<Vec<Product>> {
+        let mut builder = QueryBuilder::new(format!(
+            "SELECT {PRODUCT_COLUMNS} FROM (SELECT * FROM products WHERE archived_at IS NULL) AS products"
+        ));
+        page.push_sql(&mut builder);
+
+        Ok(builder.build_query_as().fetch_all(&self.pool).await?)
+    }
+}
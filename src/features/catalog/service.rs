@@ -0,0 +1,94 @@
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    Error, Result,
+    features::{
+        catalog::{
+            domain::{
+                Brand, Colorway, Currency, NewProduct, PriceCents, Product, ProductDescription,
+                ProductGender, ProductModel, ProductSlug, ReleaseDate, Sku, UpdateProduct,
+            },
+            listing::ProductListing,
+            repository::CatalogRepository,
+        },
+        shared::{Page, PageQuery, map_unique_violation},
+    },
+};
+
+pub struct CatalogService {
+    repository: CatalogRepository,
+}
+
+pub struct CreateProductInput {
+    pub sku: Sku,
+    pub brand: Brand,
+    pub model: ProductModel,
+    pub colorway: Colorway,
+    pub description: Option<ProductDescription>,
+    pub gender: ProductGender,
+    pub release_date: ReleaseDate,
+    pub price: PriceCents,
+    pub currency: Currency,
+}
+
+impl CatalogService {
+    pub fn new(repository: CatalogRepository) -> Self {
+        Self { repository }
+    }
+
+    #[instrument(name = "catalog.list_products", skip_all, fields(limit = page.limit))]
+    pub async fn list_products(&self, page: &PageQuery<ProductListing>) -> Result<Page<Product>> {
+        let rows = self.repository.list_active_products(page).await?;
+
+        Ok(page.page(rows))
+    }
+
+    #[instrument(name = "catalog.get_product", skip(self))]
+    pub async fn get_product(&self, slug: &str) -> Result<Product> {
+        self.repository
+            .get_active_product_by_slug(slug)
+            .await?
+            .ok_or(Error::NotFound("Product not found".into()))
+    }
+
+    #[instrument(name = "catalog.create_product", skip_all, fields(sku = data.sku.as_str()))]
+    pub async fn create_product(&self, data: CreateProductInput) -> Result<Product> {
+        let slug = ProductSlug::generate(&data.brand, &data.model, &data.colorway, &data.sku);
+
+        self.repository
+            .create_product(&NewProduct {
+                slug,
+                sku: data.sku,
+                brand: data.brand,
+                model: data.model,
+                colorway: data.colorway,
+                description: data.description,
+                gender: data.gender,
+                release_date: data.release_date,
+                price: data.price,
+                currency: data.currency,
+            })
+            .await
+            .map_err(map_unique_violation(Some(Error::Conflict(
+                "A product with this SKU already exists".into(),
+            ))))
+    }
+
+    #[instrument(name = "catalog.update_product", skip(self, data))]
+    pub async fn update_product(&self, id: &Uuid, data: UpdateProduct) -> Result<Product> {
+        self.repository
+            .update_product(id, data)
+            .await?
+            .ok_or(Error::NotFound("Product not found".into()))
+    }
+
+    /// Hides the product from the public catalog without deleting it.
+    #[instrument(name = "catalog.archive_product", skip(self))]
+    pub async fn archive_product(&self, id: &Uuid) -> Result<Product> {
+        self.repository
+            .archive_product(id)
+            .await?
+            .ok_or(Error::NotFound("Product not found".into()))
+    }
+}
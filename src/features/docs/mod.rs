@@ -0,0 +1,135 @@
+use axum::{Router, http::header, routing::get};
+use utoipa::{
+    OpenApi,
+    openapi::{
+        self,
+        security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
+    },
+};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::{
+    app::AppState,
+    features::{
+        admin::AdminApi,
+        auth::AuthApi,
+        catalog::{
+            CatalogAdminApi, CatalogApi,
+            inventory::{InventoryAdminApi, InventoryApi},
+        },
+        health::HealthApi,
+        webhooks::WebhooksApi,
+    },
+    middlewares::CSRF_HEADER_NAME,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(description = "Metrics are served on a separate port and are not part of this document."),
+    nest(
+        (path = "/api/v1/auth", api = AuthApi),
+        (path = "/api/v1/admin", api = AdminApi),
+        (path = "/api/v1/admin", api = WebhooksApi),
+        (path = "/api/v1", api = CatalogApi),
+        (path = "/api/v1/admin", api = CatalogAdminApi),
+        (path = "/api/v1", api = InventoryApi),
+        (path = "/api/v1/admin", api = InventoryAdminApi),
+        (path = "/health", api = HealthApi),
+    ),
+)]
+struct ApiDoc;
+
+/// The OpenAPI 3.1 document for the API port. Security schemes are added
+/// here because the session cookie name comes from configuration.
+pub fn openapi(session_cookie_name: &str) -> openapi::OpenApi {
+    let mut doc = ApiDoc::openapi();
+    doc.info.license = None;
+
+    let components = doc.components.get_or_insert_with(Default::default);
+
+    components.add_security_scheme(
+        "session_cookie",
+        SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::with_description(
+            session_cookie_name,
+            "Signed session cookie set by sign-in",
+        ))),
+    );
+    components.add_security_scheme(
+        "csrf_token",
+        SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+            CSRF_HEADER_NAME,
+            "Token from `GET /api/v1/auth/csrf-token`; required alongside the session cookie on state-changing requests",
+        ))),
+    );
+    components.add_security_scheme(
+        "bearer",
+        SecurityScheme::Http(
+            HttpBuilder::new()
+                .scheme(HttpAuthScheme::Bearer)
+                .bearer_format("JWT")
+                .build(),
+        ),
+    );
+    components.add_security_scheme(
+        "api_key",
+        SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+            "Authorization",
+            "`ApiKey <key>`",
+        ))),
+    );
+
+    doc
+}
+
+pub struct DocsModule;
+
+impl DocsModule {
+    /// The document at `/api/openapi.json` and an interactive reference at `/api/docs`.
+    pub fn router(session_cookie_name: &str) -> Router<AppState> {
+        let doc = openapi(session_cookie_name);
+        let json = doc.to_json().expect("OpenAPI document is serializable");
+
+        Router::new()
+            .route(
+                "/api/openapi.json",
+                get(|| async move { ([(header::CONTENT_TYPE, "application/json")], json) }),
+            )
+            .merge(Scalar::with_url("/api/docs", doc))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, fs, path::Path};
+
+    use super::*;
+
+    /// Regenerate with `UPDATE_OPENAPI=1 cargo test openapi`.
+    #[test]
+    fn committed_openapi_document_matches_the_code() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("openapi.json");
+        let generated = openapi("session").to_pretty_json().unwrap() + "\n";
+
+        if env::var_os("UPDATE_OPENAPI").is_some() {
+            fs::write(&path, &generated).unwrap();
+            return;
+        }
+
+        let committed = fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            committed == generated,
+            "openapi.json is out of date; run `UPDATE_OPENAPI=1 cargo test openapi` and commit it"
+        );
+    }
+
+    #[test]
+    fn session_cookie_scheme_uses_configured_name() {
+        let doc = serde_json::to_value(openapi("kicks_session")).unwrap();
+
+        assert_eq!(doc["openapi"], "3.1.0");
+        assert_eq!(
+            doc["components"]["securitySchemes"]["session_cookie"]["name"],
+            "kicks_session"
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use axum::{Json, http::StatusCode, response::IntoResponse};
+
+use crate::{ApiResponse, features::health::HealthStatus};
+
+#[utoipa::path(
+    get,
+    path = "/live",
+    tag = "health",
+    responses((status = 200, description = "The process is serving requests", body = ApiResponse<HealthStatus>)),
+)]
+/// Liveness only proves the process is serving requests; it never checks
+/// dependencies so an outage does not get healthy instances restarted.
+pub async fn live() -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: HealthStatus::Ok,
+        }),
+    )
+}
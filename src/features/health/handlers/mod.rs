@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{OpenApi, ToSchema};
+
+mod live_handler;
+mod ready_handler;
+
+pub use live_handler::live;
+pub use ready_handler::ready;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(live_handler::live, ready_handler::ready),
+    tags((name = "health", description = "Liveness and readiness probes")),
+)]
+pub struct HealthApi;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Ok,
+    Failing,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DependencyReport {
+    pub name: String,
+    pub status: HealthStatus,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    /// `false` once graceful shutdown has started.
+    pub accepting_traffic: bool,
+    pub dependencies: Vec<DependencyReport>,
+}
+
+impl HealthReport {
+    pub fn new(accepting_traffic: bool, dependencies: Vec<DependencyReport>) -> Self {
+        let healthy = dependencies.iter().all(|d| d.status == HealthStatus::Ok);
+
+        Self {
+            status: if accepting_traffic && healthy {
+                HealthStatus::Ok
+            } else {
+                HealthStatus::Failing
+            },
+            accepting_traffic,
+            dependencies,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn dependency(status: HealthStatus) -> DependencyReport {
+        DependencyReport {
+            name: "postgres".into(),
+            status,
+            latency_ms: 1,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn report_is_ok_when_all_dependencies_are_ok() {
+        let report = HealthReport::new(true, vec![dependency(HealthStatus::Ok)]);
+        assert_eq!(report.status, HealthStatus::Ok);
+    }
+
+    #[test]
+    fn report_fails_when_any_dependency_fails() {
+        let report = HealthReport::new(
+            true,
+            vec![
+                dependency(HealthStatus::Ok),
+                dependency(HealthStatus::Failing),
+            ],
+        );
+        assert_eq!(report.status, HealthStatus::Failing);
+    }
+
+    #[test]
+    fn report_fails_while_draining() {
+        let report = HealthReport::new(false, vec![dependency(HealthStatus::Ok)]);
+        assert_eq!(report.status, HealthStatus::Failing);
+    }
+}
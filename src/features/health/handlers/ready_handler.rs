@@ -0,0 +1,27 @@
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+
+use crate::{
+    ApiResponse,
+    app::AppState,
+    features::health::{HealthReport, HealthStatus},
+};
+
+#[utoipa::path(
+    get,
+    path = "/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "Every dependency is reachable", body = ApiResponse<HealthReport>),
+        (status = 503, description = "A dependency is failing or the server is draining", body = ApiResponse<HealthReport>),
+    ),
+)]
+pub async fn ready(State(state): State<AppState>) -> impl IntoResponse {
+    let report = state.health_service.readiness().await;
+
+    let status = match report.status {
+        HealthStatus::Ok => StatusCode::OK,
+        HealthStatus::Failing => StatusCode::SERVICE_UNAVAILABLE,
+    };
+
+    (status, Json(ApiResponse { data: report }))
+}
@@ -0,0 +1,44 @@
+use std::{
+    sync::{Arc, atomic::AtomicBool},
+    time::Duration,
+};
+
+use axum::{Router, routing::get};
+use redis::aio::MultiplexedConnection;
+use sqlx::PgPool;
+
+use crate::{app::AppState, clients::email_client::EmailClient};
+
+mod handlers;
+mod service;
+
+pub use handlers::{DependencyReport, HealthApi, HealthReport, HealthStatus};
+pub use service::HealthService;
+
+use handlers::*;
+
+pub struct HealthModule {
+    pub health_service: HealthService,
+}
+
+impl HealthModule {
+    pub fn new(
+        pool: PgPool,
+        redis: MultiplexedConnection,
+        email_client: Arc<EmailClient>,
+        ready: Arc<AtomicBool>,
+        check_timeout: Duration,
+    ) -> Self {
+        Self {
+            health_service: HealthService::new(pool, redis, email_client, ready, check_timeout),
+        }
+    }
+
+    /// Probe routes are mounted outside the API middleware stack so load
+    /// balancer polling is neither rate limited nor logged per request.
+    pub fn router() -> Router<AppState> {
+        Router::new()
+            .route("/live", get(live))
+            .route("/ready", get(ready))
+    }
+}
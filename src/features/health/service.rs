@@ -0,0 +1,103 @@
+use std::{
+    future::Future,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use redis::{AsyncTypedCommands, aio::MultiplexedConnection};
+use sqlx::PgPool;
+use tokio::time::{Instant, timeout};
+use tracing::warn;
+
+use crate::{
+    Result,
+    clients::email_client::EmailClient,
+    features::health::{DependencyReport, HealthReport, HealthStatus},
+};
+
+pub struct HealthService {
+    pool: PgPool,
+    redis: MultiplexedConnection,
+    email_client: Arc<EmailClient>,
+    ready: Arc<AtomicBool>,
+    check_timeout: Duration,
+}
+
+impl HealthService {
+    pub fn new(
+        pool: PgPool,
+        redis: MultiplexedConnection,
+        email_client: Arc<EmailClient>,
+        ready: Arc<AtomicBool>,
+        check_timeout: Duration,
+    ) -> Self {
+        Self {
+            pool,
+            redis,
+            email_client,
+            ready,
+            check_timeout,
+        }
+    }
+
+    /// Checks every dependency concurrently, each bounded by the check timeout.
+    /// Reports failing while the server is draining, whatever the checks say.
+    pub async fn readiness(&self) -> HealthReport {
+        let (postgres, redis, email) = tokio::join!(
+            self.check("postgres", self.ping_postgres()),
+            self.check("redis", self.ping_redis()),
+            self.check("email", self.email_client.ping()),
+        );
+
+        HealthReport::new(
+            self.ready.load(Ordering::SeqCst),
+            vec![postgres, redis, email],
+        )
+    }
+
+    async fn ping_postgres(&self) -> Result<()> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+
+        Ok(())
+    }
+
+    async fn ping_redis(&self) -> Result<()> {
+        self.redis.clone().ping().await?;
+
+        Ok(())
+    }
+
+    async fn check(
+        &self,
+        name: &'static str,
+        probe: impl Future<Output = Result<()>>,
+    ) -> DependencyReport {
+        let start = Instant::now();
+
+        let error = match timeout(self.check_timeout, probe).await {
+            Ok(Ok(())) => None,
+            Ok(Err(err)) => {
+                warn!(?err, dependency = name, "Health check failed");
+                Some("unavailable".to_owned())
+            }
+            Err(_) => {
+                warn!(dependency = name, "Health check timed out");
+                Some("timed out".to_owned())
+            }
+        };
+
+        DependencyReport {
+            name: name.to_owned(),
+            status: if error.is_none() {
+                HealthStatus::Ok
+            } else {
+                HealthStatus::Failing
+            },
+            latency_ms: start.elapsed().as_millis() as u64,
+            error,
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use axum::{extract::State, http::header, response::IntoResponse};
+
+use crate::app::AppState;
+
+pub async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics_service.render().await,
+    )
+}
@@ -0,0 +1,3 @@
+mod metrics_handler;
+
+pub use metrics_handler::metrics;
@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+use axum::{Router, routing::get};
+use redis::aio::MultiplexedConnection;
+use sqlx::PgPool;
+
+use crate::app::AppState;
+
+mod handlers;
+mod recorder;
+mod service;
+
+pub use recorder::*;
+pub use service::MetricsService;
+
+use handlers::*;
+
+pub struct MetricsModule {
+    pub metrics_service: MetricsService,
+}
+
+impl MetricsModule {
+    pub fn new(pool: PgPool, redis: MultiplexedConnection, ping_timeout: Duration) -> Self {
+        Self {
+            metrics_service: MetricsService::new(pool, redis, install_recorder(), ping_timeout),
+        }
+    }
+
+    /// Served on the dedicated metrics listener, never on the API port.
+    pub fn router() -> Router<AppState> {
+        Router::new().route("/metrics", get(metrics))
+    }
+}
@@ -0,0 +1,192 @@
+use std::{sync::OnceLock, time::Duration};
+
+use axum::http::Method;
+use metrics::{counter, gauge, histogram};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+
+pub const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+pub const AUTH_SUCCESS_TOTAL: &str = "auth_success_total";
+pub const AUTH_FAILURE_TOTAL: &str = "auth_failure_total";
+pub const EMAILS_SENT_TOTAL: &str = "emails_sent_total";
+pub const JOBS_TOTAL: &str = "jobs_total";
+pub const DB_POOL_CONNECTIONS: &str = "db_pool_connections";
+pub const DB_POOL_MAX_CONNECTIONS: &str = "db_pool_max_connections";
+pub const REDIS_UP: &str = "redis_up";
+pub const REDIS_PING_LATENCY_SECONDS: &str = "redis_ping_latency_seconds";
+
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Installs the process-wide recorder on first use. Later calls return a
+/// handle to the same registry, so several applications can share a process.
+pub fn install_recorder() -> PrometheusHandle {
+    HANDLE
+        .get_or_init(|| {
+            builder()
+                .install_recorder()
+                .expect("Failed to install metrics recorder")
+        })
+        .clone()
+}
+
+fn builder() -> PrometheusBuilder {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full(HTTP_REQUEST_DURATION_SECONDS.into()),
+            LATENCY_BUCKETS,
+        )
+        .expect("Latency buckets must not be empty")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthFlow {
+    SignUp,
+    SignIn,
+    OAuth2,
+}
+
+impl AuthFlow {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthFlow::SignUp => "sign_up",
+            AuthFlow::SignIn => "sign_in",
+            AuthFlow::OAuth2 => "oauth2",
+        }
+    }
+}
+
+pub fn record_http_request(method: &Method, route: &str, status: u16, elapsed: Duration) {
+    let labels = [
+        ("method", method.to_string()),
+        ("route", route.to_owned()),
+        ("status", status.to_string()),
+    ];
+
+    counter!(HTTP_REQUESTS_TOTAL, &labels).increment(1);
+    histogram!(HTTP_REQUEST_DURATION_SECONDS, &labels).record(elapsed.as_secs_f64());
+}
+
+pub fn record_auth_success(flow: AuthFlow, provider: &'static str) {
+    counter!(AUTH_SUCCESS_TOTAL, "flow" => flow.as_str(), "provider" => provider).increment(1);
+}
+
+pub fn record_auth_failure(flow: AuthFlow, provider: &'static str, reason: &'static str) {
+    counter!(
+        AUTH_FAILURE_TOTAL,
+        "flow" => flow.as_str(),
+        "provider" => provider,
+        "reason" => reason
+    )
+    .increment(1);
+}
+
+pub fn record_email(kind: &'static str, sent: bool) {
+    let outcome = if sent { "sent" } else { "failed" };
+
+    counter!(EMAILS_SENT_TOTAL, "kind" => kind, "outcome" => outcome).increment(1);
+}
+
+/// `outcome` is `completed`, `retried` or `failed`.
+pub fn record_job(kind: &str, outcome: &'static str) {
+    counter!(JOBS_TOTAL, "kind" => kind.to_owned(), "outcome" => outcome).increment(1);
+}
+
+pub fn record_db_pool(size: u32, idle: usize, max: u32) {
+    let active = (size as usize).saturating_sub(idle);
+
+    gauge!(DB_POOL_CONNECTIONS, "state" => "active").set(active as f64);
+    gauge!(DB_POOL_CONNECTIONS, "state" => "idle").set(idle as f64);
+    gauge!(DB_POOL_MAX_CONNECTIONS).set(max);
+}
+
+/// `None` means the ping failed or timed out; the latency gauge then keeps
+/// its last successful value and `redis_up` drops to zero.
+pub fn record_redis_ping(latency: Option<Duration>) {
+    match latency {
+        Some(latency) => {
+            gauge!(REDIS_UP).set(1);
+            gauge!(REDIS_PING_LATENCY_SECONDS).set(latency.as_secs_f64());
+        }
+        None => gauge!(REDIS_UP).set(0),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn render(record: impl FnOnce()) -> String {
+        let recorder = builder().build_recorder();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, record);
+
+        handle.render()
+    }
+
+    #[test]
+    fn http_requests_are_labeled_by_route_and_status() {
+        let output = render(|| {
+            record_http_request(
+                &Method::POST,
+                "/api/v1/auth/sign-in",
+                200,
+                Duration::from_millis(20),
+            );
+        });
+
+        assert!(output.contains(
+            r#"http_requests_total{method="POST",route="/api/v1/auth/sign-in",status="200"} 1"#
+        ));
+        assert!(output.contains(r#"http_request_duration_seconds_bucket{method="POST",route="/api/v1/auth/sign-in",status="200",le="0.025"} 1"#));
+    }
+
+    #[test]
+    fn auth_failures_carry_reason() {
+        let output = render(|| {
+            record_auth_failure(AuthFlow::SignIn, "password", "wrong_password");
+            record_auth_success(AuthFlow::OAuth2, "google");
+        });
+
+        assert!(output.contains(
+            r#"auth_failure_total{flow="sign_in",provider="password",reason="wrong_password"} 1"#
+        ));
+        assert!(output.contains(r#"auth_success_total{flow="oauth2",provider="google"} 1"#));
+    }
+
+    #[test]
+    fn email_outcomes_are_counted_separately() {
+        let output = render(|| {
+            record_email("password_reset", true);
+            record_email("password_reset", false);
+            record_email("password_reset", false);
+        });
+
+        assert!(output.contains(r#"emails_sent_total{kind="password_reset",outcome="sent"} 1"#));
+        assert!(output.contains(r#"emails_sent_total{kind="password_reset",outcome="failed"} 2"#));
+    }
+
+    #[test]
+    fn failed_redis_ping_marks_redis_down() {
+        let output = render(|| {
+            record_redis_ping(Some(Duration::from_millis(2)));
+            record_redis_ping(None);
+        });
+
+        assert!(output.contains("redis_up 0"));
+        assert!(output.contains("redis_ping_latency_seconds 0.002"));
+    }
+
+    #[test]
+    fn db_pool_splits_active_and_idle() {
+        let output = render(|| record_db_pool(5, 3, 10));
+
+        assert!(output.contains(r#"db_pool_connections{state="active"} 2"#));
+        assert!(output.contains(r#"db_pool_connections{state="idle"} 3"#));
+        assert!(output.contains("db_pool_max_connections 10"));
+    }
+}
@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use metrics_exporter_prometheus::PrometheusHandle;
+use redis::{AsyncTypedCommands, aio::MultiplexedConnection};
+use sqlx::PgPool;
+use tokio::time::{Instant, timeout};
+
+use crate::features::metrics::{record_db_pool, record_redis_ping};
+
+pub struct MetricsService {
+    pool: PgPool,
+    redis: MultiplexedConnection,
+    handle: PrometheusHandle,
+    ping_timeout: Duration,
+}
+
+impl MetricsService {
+    pub fn new(
+        pool: PgPool,
+        redis: MultiplexedConnection,
+        handle: PrometheusHandle,
+        ping_timeout: Duration,
+    ) -> Self {
+        Self {
+            pool,
+            redis,
+            handle,
+            ping_timeout,
+        }
+    }
+
+    /// Samples the dependency gauges and renders the Prometheus text format.
+    pub async fn render(&self) -> String {
+        self.sample_gauges().await;
+        self.handle.run_upkeep();
+
+        self.handle.render()
+    }
+
+    async fn sample_gauges(&self) {
+        record_db_pool(
+            self.pool.size(),
+            self.pool.num_idle(),
+            self.pool.options().get_max_connections(),
+        );
+
+        let start = Instant::now();
+        let mut redis = self.redis.clone();
+        let ping = timeout(self.ping_timeout, redis.ping()).await;
+
+        record_redis_ping(matches!(ping, Ok(Ok(_))).then(|| start.elapsed()));
+    }
+}
@@ -1,2 +1,8 @@
+pub mod admin;
 pub mod auth;
+pub mod catalog;
+pub mod docs;
+pub mod health;
+pub mod metrics;
 pub mod shared;
+pub mod webhooks;
@@ -1,4 +1,14 @@
-use crate::Error;
+use std::time::Duration;
+
+use time::OffsetDateTime;
+
+use crate::{
+    Error, Result,
+    features::{
+        auth::{ApiKeyScope, UserRole},
+        shared::{AppUser, Credential},
+    },
+};
 
 pub fn map_unique_violation(custom_error: Option<Error>) -> impl FnOnce(Error) -> Error {
     move |error| match error {
@@ -12,3 +22,38 @@ pub fn map_unique_violation(custom_error: Option<Error>) -> impl FnOnce(Error) -
         _ => error,
     }
 }
+
+pub fn ensure_scope(credential: &Credential, scope: ApiKeyScope) -> Result<()> {
+    if credential.has_scope(scope) {
+        Ok(())
+    } else {
+        Err(Error::Forbidden)
+    }
+}
+
+pub fn ensure_interactive(credential: &Credential) -> Result<()> {
+    if credential.is_api_key() {
+        Err(Error::Forbidden)
+    } else {
+        Ok(())
+    }
+}
+
+pub fn ensure_admin(user: &AppUser) -> Result<()> {
+    if matches!(user.role, UserRole::Admin) {
+        Ok(())
+    } else {
+        Err(Error::Forbidden)
+    }
+}
+
+/// Guards sensitive actions behind a credential check no older than `max_age`:
+/// API key management and every admin mutation. Bans go through `kicksctl`,
+/// which runs without a credential and isn't covered.
+pub fn ensure_recent_authentication(credential: &Credential, max_age: Duration) -> Result<()> {
+    match credential.authenticated_at() {
+        Some(at) if at + max_age > OffsetDateTime::now_utc() => Ok(()),
+        Some(_) => Err(Error::ReauthenticationRequired),
+        None => Err(Error::Forbidden),
+    }
+}
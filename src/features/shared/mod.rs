@@ -1,5 +1,7 @@
 mod guards;
+mod pagination;
 mod types;
 
 pub use guards::*;
+pub use pagination::*;
 pub use types::*;
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+mod cursor;
+mod query;
+
+pub use query::PageQuery;
+
+/// One page of a listing.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Pass back as `cursor` to fetch the following page; absent on the last one.
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+/// What clients may sort and filter a listing by. Column names and SQL types
+/// are written into queries verbatim, so they must be literals; values from
+/// the request are only ever bound.
+#[derive(Debug)]
+pub struct ListSpec {
+    pub sorts: &'static [SortField],
+    pub filters: &'static [FilterField],
+    /// A sort field name, prefixed with `-` for descending order.
+    pub default_sort: &'static str,
+    /// A unique column ordering rows with equal sort values.
+    pub tiebreaker: Column,
+    pub default_limit: u32,
+    pub max_limit: u32,
+}
+
+#[derive(Debug)]
+pub struct Column {
+    pub name: &'static str,
+    pub sql_type: &'static str,
+}
+
+/// Sort columns must be `NOT NULL`; keyset comparisons skip null rows.
+#[derive(Debug)]
+pub struct SortField {
+    pub name: &'static str,
+    pub column: Column,
+}
+
+#[derive(Debug)]
+pub struct FilterField {
+    pub name: &'static str,
+    pub column: &'static str,
+    pub kind: FilterKind,
+}
+
+#[derive(Debug)]
+pub enum FilterKind {
+    Bool,
+    /// A Postgres enum; `values` lists the labels clients may pass.
+    Enum {
+        sql_type: &'static str,
+        values: &'static [&'static str],
+    },
+    /// Case-insensitive prefix match on a text column.
+    Prefix,
+}
+
+/// A paginated resource: its [`ListSpec`] and how to read a row's position.
+pub trait Listing {
+    const SPEC: &'static ListSpec;
+
+    type Row;
+
+    /// The row's value in `sort`'s column, as text Postgres casts back to
+    /// the column's type without losing precision.
+    fn sort_value(row: &Self::Row, sort: &SortField) -> String;
+
+    fn tiebreaker_value(row: &Self::Row) -> String;
+}
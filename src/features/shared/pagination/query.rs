@@ -0,0 +1,503 @@
+use std::{
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+};
+
+use axum::{
+    extract::{FromRef, FromRequestParts, Query},
+    http::request::Parts,
+};
+use cookie::Key;
+use sqlx::{Postgres, QueryBuilder};
+
+use super::{
+    FilterField, FilterKind, Listing, Page, SortField,
+    cursor::{CursorSigner, Position},
+};
+use crate::{Error, Result, i18n::Message};
+
+const MAX_PREFIX_LENGTH: usize = 255;
+
+/// `limit`, `cursor`, `sort` and filter parameters of a listing request,
+/// checked against `L`'s [`ListSpec`](super::ListSpec). Unknown parameters
+/// and values outside the spec are rejected rather than ignored.
+pub struct PageQuery<L: Listing> {
+    pub limit: u32,
+    sort: &'static SortField,
+    descending: bool,
+    filters: Vec<(&'static FilterField, String)>,
+    after: Option<Position>,
+    signer: CursorSigner,
+    listing: PhantomData<fn() -> L>,
+}
+
+impl<L: Listing> PageQuery<L> {
+    fn parse(params: Vec<(String, String)>, signer: CursorSigner) -> Result<Self> {
+        let spec = L::SPEC;
+        let (sort, descending) =
+            parse_sort::<L>(spec.default_sort).expect("default sort is a listed sort field");
+
+        let mut query = Self {
+            limit: spec.default_limit,
+            sort,
+            descending,
+            filters: Vec::new(),
+            after: None,
+            signer,
+            listing: PhantomData,
+        };
+        let mut cursor = None;
+        let mut seen = HashSet::new();
+        let mut errors: HashMap<String, Vec<Message>> = HashMap::new();
+
+        for (name, value) in params {
+            let result = if !seen.insert(name.clone()) {
+                Err(Message::new("pagination.duplicate_parameter"))
+            } else {
+                match name.as_str() {
+                    "limit" => parse_limit::<L>(&value).map(|limit| query.limit = limit),
+                    "sort" => parse_sort::<L>(&value).map(|(sort, descending)| {
+                        query.sort = sort;
+                        query.descending = descending;
+                    }),
+                    "cursor" => {
+                        cursor = Some(value);
+                        Ok(())
+                    }
+                    _ => match spec.filters.iter().find(|field| field.name == name) {
+                        Some(field) => parse_filter(field, value).map(|v| query.filters.push(v)),
+                        None => Err(Message::new("pagination.unknown_parameter")),
+                    },
+                }
+            };
+
+            if let Err(message) = result {
+                errors.entry(name).or_default().push(message);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(Error::ValidationErrors(errors));
+        }
+
+        if let Some(cursor) = cursor {
+            query.after = Some(
+                query
+                    .signer
+                    .verify(&query.scope(), &cursor)
+                    .ok_or_else(|| {
+                        Error::ValidationErrors(
+                            [(
+                                "cursor".into(),
+                                vec![Message::new("pagination.invalid_cursor")],
+                            )]
+                            .into(),
+                        )
+                    })?,
+            );
+        }
+
+        Ok(query)
+    }
+
+    /// The sort and filters a cursor is bound to, in a canonical order.
+    fn scope(&self) -> String {
+        let mut filters: Vec<String> = self
+            .filters
+            .iter()
+            .map(|(field, value)| format!("{}={}", field.name, value))
+            .collect();
+        filters.sort();
+
+        let direction = if self.descending { "-" } else { "" };
+        format!("{direction}{}&{}", self.sort.name, filters.join("&"))
+    }
+
+    /// Appends the filters, the position after the cursor, the order and
+    /// the limit to `builder`, which should hold a `SELECT ... FROM` with no
+    /// `WHERE`. One row beyond `limit` is fetched so [`page`](Self::page)
+    /// can tell whether another page follows.
+    pub fn push_sql(&self, builder: &mut QueryBuilder<'_, Postgres>) {
+        let mut conjunction = " WHERE ";
+
+        for (field, value) in &self.filters {
+            builder.push(conjunction).push(field.column);
+            conjunction = " AND ";
+
+            match field.kind {
+                FilterKind::Bool => builder.push(" = ").push_bind(value == "true"),
+                FilterKind::Enum { sql_type, .. } => builder
+                    .push(" = ")
+                    .push_bind(value.clone())
+                    .push("::")
+                    .push(sql_type),
+                FilterKind::Prefix => builder
+                    .push(" ILIKE ")
+                    .push_bind(format!("{}%", escape_like(value)))
+                    .push(" ESCAPE '\\'"),
+            };
+        }
+
+        let tiebreaker = &L::SPEC.tiebreaker;
+        let (operator, direction) = if self.descending {
+            (" < ", " DESC")
+        } else {
+            (" > ", " ASC")
+        };
+
+        if let Some(after) = &self.after {
+            builder
+                .push(conjunction)
+                .push(format_args!(
+                    "({}, {})",
+                    self.sort.column.name, tiebreaker.name
+                ))
+                .push(operator)
+                .push("(")
+                .push_bind(after.value.clone())
+                .push("::")
+                .push(self.sort.column.sql_type)
+                .push(", ")
+                .push_bind(after.id.clone())
+                .push("::")
+                .push(tiebreaker.sql_type)
+                .push(")");
+        }
+
+        builder
+            .push(" ORDER BY ")
+            .push(self.sort.column.name)
+            .push(direction)
+            .push(", ")
+            .push(tiebreaker.name)
+            .push(direction)
+            .push(" LIMIT ")
+            .push_bind(i64::from(self.limit) + 1);
+    }
+
+    /// Trims rows fetched with [`push_sql`](Self::push_sql) to `limit` and
+    /// signs a cursor for the next page when there is one.
+    pub fn page(&self, mut rows: Vec<L::Row>) -> Page<L::Row> {
+        let mut next_cursor = None;
+
+        if rows.len() > self.limit as usize {
+            rows.truncate(self.limit as usize);
+            next_cursor = rows.last().map(|row| {
+                let position = Position {
+                    value: L::sort_value(row, self.sort),
+                    id: L::tiebreaker_value(row),
+                };
+                self.signer.sign(&self.scope(), &position)
+            });
+        }
+
+        Page {
+            items: rows,
+            next_cursor,
+        }
+    }
+}
+
+impl<S, L> FromRequestParts<S> for PageQuery<L>
+where
+    S: Send + Sync,
+    Key: FromRef<S>,
+    L: Listing,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self> {
+        let Query(params) =
+            Query::<Vec<(String, String)>>::try_from_uri(&parts.uri).map_err(|_| {
+                Error::ValidationErrors(
+                    [(
+                        "query".into(),
+                        vec![Message::new("pagination.invalid_query")],
+                    )]
+                    .into(),
+                )
+            })?;
+
+        Self::parse(params, CursorSigner::new(Key::from_ref(state)))
+    }
+}
+
+fn parse_limit<L: Listing>(value: &str) -> std::result::Result<u32, Message> {
+    let max = L::SPEC.max_limit;
+
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|limit| (1..=max).contains(limit))
+        .ok_or_else(|| Message::new("pagination.limit_out_of_range").with("max", max))
+}
+
+fn parse_sort<L: Listing>(value: &str) -> std::result::Result<(&'static SortField, bool), Message> {
+    let (name, descending) = match value.strip_prefix('-') {
+        Some(name) => (name, true),
+        None => (value, false),
+    };
+
+    L::SPEC
+        .sorts
+        .iter()
+        .find(|sort| sort.name == name)
+        .map(|sort| (sort, descending))
+        .ok_or_else(|| {
+            let allowed: Vec<_> = L::SPEC.sorts.iter().map(|sort| sort.name).collect();
+            Message::new("pagination.unknown_sort").with("allowed", allowed.join(", "))
+        })
+}
+
+fn parse_filter(
+    field: &'static FilterField,
+    value: String,
+) -> std::result::Result<(&'static FilterField, String), Message> {
+    let allowed = match field.kind {
+        FilterKind::Bool => &["true", "false"][..],
+        FilterKind::Enum { values, .. } => values,
+        FilterKind::Prefix => {
+            let length = value.chars().count();
+            return match length {
+                0 => Err(Message::new("value.empty")),
+                _ if length > MAX_PREFIX_LENGTH => {
+                    Err(Message::new("value.too_long").with("max", MAX_PREFIX_LENGTH))
+                }
+                _ => Ok((field, value)),
+            };
+        }
+    };
+
+    if allowed.contains(&value.as_str()) {
+        Ok((field, value))
+    } else {
+        Err(Message::new("pagination.invalid_filter").with("allowed", allowed.join(", ")))
+    }
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod test {
+    use sqlx::Execute;
+
+    use super::*;
+    use crate::features::shared::pagination::{Column, ListSpec};
+
+    struct Widgets;
+
+    struct Widget {
+        id: u32,
+        name: String,
+    }
+
+    impl Listing for Widgets {
+        const SPEC: &'static ListSpec = &ListSpec {
+            sorts: &[
+                SortField {
+                    name: "created_at",
+                    column: Column {
+                        name: "created_at",
+                        sql_type: "timestamptz",
+                    },
+                },
+                SortField {
+                    name: "name",
+                    column: Column {
+                        name: "name",
+                        sql_type: "text",
+                    },
+                },
+            ],
+            filters: &[
+                FilterField {
+                    name: "kind",
+                    column: "kind",
+                    kind: FilterKind::Enum {
+                        sql_type: "widget_kind",
+                        values: &["gear", "bolt"],
+                    },
+                },
+                FilterField {
+                    name: "active",
+                    column: "is_active",
+                    kind: FilterKind::Bool,
+                },
+                FilterField {
+                    name: "name",
+                    column: "name",
+                    kind: FilterKind::Prefix,
+                },
+            ],
+            default_sort: "-created_at",
+            tiebreaker: Column {
+                name: "id",
+                sql_type: "int4",
+            },
+            default_limit: 2,
+            max_limit: 10,
+        };
+
+        type Row = Widget;
+
+        fn sort_value(row: &Widget, _: &SortField) -> String {
+            row.name.clone()
+        }
+
+        fn tiebreaker_value(row: &Widget) -> String {
+            row.id.to_string()
+        }
+    }
+
+    fn signer() -> CursorSigner {
+        CursorSigner::new(Key::from(&[7; 64][..]))
+    }
+
+    fn parse(params: &[(&str, &str)]) -> Result<PageQuery<Widgets>> {
+        PageQuery::parse(
+            params
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            signer(),
+        )
+    }
+
+    fn sql(query: &PageQuery<Widgets>) -> String {
+        let mut builder = QueryBuilder::new("SELECT * FROM widgets");
+        query.push_sql(&mut builder);
+        builder.build().sql().to_owned()
+    }
+
+    fn widgets(count: u32) -> Vec<Widget> {
+        (1..=count)
+            .map(|id| Widget {
+                id,
+                name: format!("w{id}"),
+            })
+            .collect()
+    }
+
+    fn error_fields(result: Result<PageQuery<Widgets>>) -> Vec<String> {
+        match result {
+            Err(Error::ValidationErrors(errors)) => {
+                let mut fields: Vec<_> = errors.into_keys().collect();
+                fields.sort();
+                fields
+            }
+            _ => panic!("expected validation errors"),
+        }
+    }
+
+    #[test]
+    fn defaults_apply_without_parameters() {
+        let query = parse(&[]).unwrap();
+
+        assert_eq!(query.limit, 2);
+        assert_eq!(
+            sql(&query),
+            "SELECT * FROM widgets ORDER BY created_at DESC, id DESC LIMIT $1"
+        );
+    }
+
+    #[test]
+    fn filters_and_sort_are_bound_not_interpolated() {
+        let query = parse(&[
+            ("kind", "gear"),
+            ("active", "true"),
+            ("name", "50%_off"),
+            ("sort", "name"),
+            ("limit", "5"),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            sql(&query),
+            "SELECT * FROM widgets WHERE kind = $1::widget_kind AND is_active = $2 \
+             AND name ILIKE $3 ESCAPE '\\' ORDER BY name ASC, id ASC LIMIT $4"
+        );
+        assert_eq!(escape_like("50%_off\\"), "50\\%\\_off\\\\");
+    }
+
+    #[test]
+    fn invalid_parameters_are_reported_per_field() {
+        let fields = error_fields(parse(&[
+            ("limit", "0"),
+            ("sort", "password"),
+            ("kind", "'; DROP TABLE widgets; --"),
+            ("active", "yes"),
+            ("secret", "1"),
+        ]));
+
+        assert_eq!(fields, ["active", "kind", "limit", "secret", "sort"]);
+        assert_eq!(
+            error_fields(parse(&[("limit", "11")])),
+            ["limit"],
+            "limit above max_limit"
+        );
+        assert_eq!(
+            error_fields(parse(&[("kind", "gear"), ("kind", "bolt")])),
+            ["kind"]
+        );
+    }
+
+    #[test]
+    fn last_page_has_no_cursor() {
+        let query = parse(&[]).unwrap();
+
+        let page = query.page(widgets(2));
+
+        assert_eq!(page.items.len(), 2);
+        assert!(page.next_cursor.is_none());
+    }
+
+    #[test]
+    fn cursor_continues_after_the_last_item() {
+        let query = parse(&[("sort", "name"), ("kind", "gear")]).unwrap();
+
+        let page = query.page(widgets(3));
+        let cursor = page.next_cursor.unwrap();
+
+        assert_eq!(page.items.len(), 2);
+        let next = parse(&[("kind", "gear"), ("sort", "name"), ("cursor", &cursor)]).unwrap();
+        assert_eq!(
+            next.after,
+            Some(Position {
+                value: "w2".into(),
+                id: "2".into()
+            })
+        );
+        assert_eq!(
+            sql(&next),
+            "SELECT * FROM widgets WHERE kind = $1::widget_kind \
+             AND (name, id) > ($2::text, $3::int4) ORDER BY name ASC, id ASC LIMIT $4"
+        );
+    }
+
+    #[test]
+    fn cursor_from_another_query_is_rejected() {
+        let cursor = parse(&[("sort", "name")])
+            .unwrap()
+            .page(widgets(3))
+            .next_cursor
+            .unwrap();
+
+        assert_eq!(
+            error_fields(parse(&[("sort", "-name"), ("cursor", &cursor)])),
+            ["cursor"]
+        );
+        assert_eq!(
+            error_fields(parse(&[
+                ("sort", "name"),
+                ("active", "true"),
+                ("cursor", &cursor)
+            ])),
+            ["cursor"]
+        );
+    }
+}
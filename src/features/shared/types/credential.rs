@@ -0,0 +1,34 @@
+use time::OffsetDateTime;
+
+use crate::features::auth::ApiKeyScope;
+
+/// How the current request was authenticated.
+#[derive(Debug, Clone)]
+pub enum Credential {
+    Session { authenticated_at: OffsetDateTime },
+    AccessToken { authenticated_at: OffsetDateTime },
+    ApiKey { scopes: Vec<ApiKeyScope> },
+}
+
+impl Credential {
+    /// Interactive credentials carry every scope; API keys only the ones they were issued with.
+    pub fn has_scope(&self, scope: ApiKeyScope) -> bool {
+        match self {
+            Credential::ApiKey { scopes } => scopes.contains(&scope),
+            _ => true,
+        }
+    }
+
+    pub fn is_api_key(&self) -> bool {
+        matches!(self, Credential::ApiKey { .. })
+    }
+
+    /// When the user last proved their credentials; API keys never do.
+    pub fn authenticated_at(&self) -> Option<OffsetDateTime> {
+        match self {
+            Credential::Session { authenticated_at }
+            | Credential::AccessToken { authenticated_at } => Some(*authenticated_at),
+            Credential::ApiKey { .. } => None,
+        }
+    }
+}
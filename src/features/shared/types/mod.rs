@@ -1,13 +1,17 @@
 mod app_user;
+mod client_ip;
+mod credential;
 mod non_empty_string;
 mod trimmed_string;
 
 pub use app_user::*;
+pub use client_ip::*;
+pub use credential::*;
 pub use non_empty_string::*;
 pub use trimmed_string::*;
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::{Error, Result};
+use crate::{Error, Result, i18n::Message};
 
 fn validate_string(value: &str, min: usize, max: usize) -> Result<()> {
     let char_count = value.graphemes(true).count();
@@ -15,15 +19,15 @@ fn validate_string(value: &str, min: usize, max: usize) -> Result<()> {
     let mut errors = Vec::new();
 
     if char_count == 0 {
-        errors.push("Value cannot be empty.".into());
+        errors.push(Message::new("value.empty"));
     }
 
     if char_count < min {
-        errors.push(format!("Value must be at least {} characters.", min));
+        errors.push(Message::new("value.too_short").with("min", min));
     }
 
     if char_count > max {
-        errors.push(format!("Value must be at most {} characters.", max));
+        errors.push(Message::new("value.too_long").with("max", max));
     }
 
     if !errors.is_empty() {
@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::json;
+use sqlx::PgPool;
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    Result,
+    events::{Delivery, EventBus, Subscriber},
+    features::{
+        admin::UserBanned,
+        auth::{UserSignedUp, UserVerified},
+        webhooks::{WebhookEventType, repository::WebhookRepository},
+    },
+};
+
+const SUBSCRIBER_NAME: &str = "webhooks";
+
+/// Turns domain events into a delivery per subscribed endpoint. Subscribed
+/// with persistent delivery, so events published by `kicksctl` reach
+/// endpoints through the server's workers too.
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    repository: WebhookRepository,
+}
+
+impl WebhookDispatcher {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            repository: WebhookRepository::new(pool),
+        }
+    }
+
+    pub fn subscribe(self, events: &EventBus) {
+        events
+            .subscribe::<UserSignedUp>(SUBSCRIBER_NAME, Delivery::Persistent, self.clone())
+            .subscribe::<UserVerified>(SUBSCRIBER_NAME, Delivery::Persistent, self.clone())
+            .subscribe::<UserBanned>(SUBSCRIBER_NAME, Delivery::Persistent, self);
+    }
+
+    /// `event_id` is the published event's id, so a retried dispatch finds
+    /// the deliveries it already created and receivers can deduplicate.
+    async fn dispatch(
+        &self,
+        event_id: Uuid,
+        event_type: WebhookEventType,
+        data: &impl Serialize,
+    ) -> Result<()> {
+        let payload = json!({
+            "id": event_id,
+            "type": event_type,
+            "created_at": OffsetDateTime::now_utc().format(&Rfc3339).unwrap_or_default(),
+            "data": data,
+        });
+
+        let deliveries = self
+            .repository
+            .create_deliveries(event_type, &event_id, &payload)
+            .await?;
+
+        if !deliveries.is_empty() {
+            info!(%event_type, %event_id, endpoints = deliveries.len(), "Queued webhook deliveries");
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Subscriber<UserSignedUp> for WebhookDispatcher {
+    async fn handle(&self, id: Uuid, event: UserSignedUp) -> Result<()> {
+        self.dispatch(id, WebhookEventType::UserSignedUp, &event)
+            .await
+    }
+}
+
+#[async_trait]
+impl Subscriber<UserVerified> for WebhookDispatcher {
+    async fn handle(&self, id: Uuid, event: UserVerified) -> Result<()> {
+        self.dispatch(id, WebhookEventType::UserVerified, &event)
+            .await
+    }
+}
+
+#[async_trait]
+impl Subscriber<UserBanned> for WebhookDispatcher {
+    async fn handle(&self, id: Uuid, event: UserBanned) -> Result<()> {
+        self.dispatch(id, WebhookEventType::UserBanned, &event)
+            .await
+    }
+}
@@ -0,0 +1,268 @@
+use std::fmt::Display;
+
+use hmac::{Hmac, Mac};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    Error, Result, common::generate_secure_random_string, features::shared::NonEmptyString,
+    i18n::Message,
+};
+
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-kicks-signature";
+pub const WEBHOOK_EVENT_HEADER: &str = "x-kicks-event";
+pub const WEBHOOK_DELIVERY_HEADER: &str = "x-kicks-delivery";
+const WEBHOOK_SECRET_PREFIX: &str = "whsec";
+const WEBHOOK_SECRET_LENGTH: usize = 40;
+
+pub type WebhookDescription = NonEmptyString<1, 200>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum WebhookEventType {
+    #[serde(rename = "user.signed_up")]
+    UserSignedUp,
+    #[serde(rename = "user.verified")]
+    UserVerified,
+    #[serde(rename = "user.banned")]
+    UserBanned,
+}
+
+impl WebhookEventType {
+    pub const ALL: [WebhookEventType; 3] = [
+        WebhookEventType::UserSignedUp,
+        WebhookEventType::UserVerified,
+        WebhookEventType::UserBanned,
+    ];
+
+    pub fn parse(value: String) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|event_type| event_type.as_str() == value.trim())
+            .ok_or_else(|| {
+                Error::DomainValidationError(vec![
+                    Message::new("webhook.unknown_event_type").with("event_type", value),
+                ])
+            })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventType::UserSignedUp => "user.signed_up",
+            WebhookEventType::UserVerified => "user.verified",
+            WebhookEventType::UserBanned => "user.banned",
+        }
+    }
+}
+
+impl Display for WebhookEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// At least one event type, without duplicates.
+#[derive(Debug, Clone)]
+pub struct WebhookEventTypes(Vec<WebhookEventType>);
+
+impl WebhookEventTypes {
+    pub fn parse(values: Vec<String>) -> Result<Self> {
+        let mut event_types = Vec::new();
+        for value in values {
+            let event_type = WebhookEventType::parse(value)?;
+            if !event_types.contains(&event_type) {
+                event_types.push(event_type);
+            }
+        }
+
+        if event_types.is_empty() {
+            return Err(Error::DomainValidationError(vec![Message::new(
+                "webhook.no_event_types",
+            )]));
+        }
+
+        Ok(Self(event_types))
+    }
+
+    pub fn as_slice(&self) -> &[WebhookEventType] {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookUrl(Url);
+
+impl WebhookUrl {
+    pub fn parse(value: String) -> Result<Self> {
+        match Url::parse(value.trim()) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => Ok(Self(url)),
+            _ => Err(Error::DomainValidationError(vec![Message::new(
+                "webhook.invalid_url",
+            )])),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub fn as_url(&self) -> &Url {
+        &self.0
+    }
+}
+
+/// Shared with the receiver, which recomputes the signature to check a
+/// request came from us. Unlike API keys it is stored as is, since signing
+/// needs the plain value.
+#[derive(Debug, Clone)]
+pub struct WebhookSecret(String);
+
+impl WebhookSecret {
+    pub fn generate() -> Self {
+        Self(format!(
+            "{}_{}",
+            WEBHOOK_SECRET_PREFIX,
+            generate_secure_random_string(WEBHOOK_SECRET_LENGTH)
+        ))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// The `x-kicks-signature` value for `body` sent at `timestamp`:
+/// `t=<unix seconds>,v1=<hex HMAC-SHA256 of "<t>.<body>">`. Covering the
+/// timestamp lets receivers reject replays of old requests.
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+
+    format!(
+        "t={timestamp},v1={}",
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize, ToSchema)]
+#[sqlx(type_name = "webhook_delivery_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    /// The last attempt failed and another one is scheduled.
+    Retrying,
+    Succeeded,
+    /// Every attempt failed; only a replay sends it again.
+    Failed,
+}
+
+#[derive(Debug)]
+pub struct WebhookEndpoint {
+    pub id: Uuid,
+    pub url: String,
+    pub description: Option<String>,
+    pub event_types: Vec<WebhookEventType>,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct NewWebhookEndpoint {
+    pub url: WebhookUrl,
+    pub description: Option<WebhookDescription>,
+    pub event_types: WebhookEventTypes,
+    pub secret: WebhookSecret,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub payload: Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub replay_of: Option<Uuid>,
+    pub created_at: OffsetDateTime,
+    pub last_attempt_at: Option<OffsetDateTime>,
+    pub delivered_at: Option<OffsetDateTime>,
+}
+
+/// What a delivery attempt needs from its delivery and endpoint.
+#[derive(Debug)]
+pub struct PendingDelivery {
+    pub id: Uuid,
+    pub event_type: String,
+    pub payload: Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn signature_covers_timestamp_and_body() {
+        let signature = sign_payload("whsec_test", 1_700_000_000, br#"{"id":1}"#);
+
+        assert_eq!(
+            signature,
+            "t=1700000000,v1=2f441ba4b3b2d50d28a9ab9d9fd8880376ecd1eb5d0435401553f5d8d0a5dcf8"
+        );
+        assert_ne!(
+            signature,
+            sign_payload("whsec_test", 1_700_000_001, br#"{"id":1}"#)
+        );
+        assert_ne!(
+            signature,
+            sign_payload("whsec_other", 1_700_000_000, br#"{"id":1}"#)
+        );
+    }
+
+    #[test]
+    fn event_types_are_deduplicated_and_required() {
+        let event_types = WebhookEventTypes::parse(vec![
+            "user.verified".into(),
+            " user.verified ".into(),
+            "user.banned".into(),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            event_types.as_slice(),
+            [WebhookEventType::UserVerified, WebhookEventType::UserBanned]
+        );
+        assert!(WebhookEventTypes::parse(vec![]).is_err());
+        assert!(WebhookEventTypes::parse(vec!["user.deleted".into()]).is_err());
+    }
+
+    #[test]
+    fn only_absolute_http_urls_are_accepted() {
+        assert!(WebhookUrl::parse("https://crm.example.com/hooks".into()).is_ok());
+        assert!(WebhookUrl::parse("http://127.0.0.1:8080/".into()).is_ok());
+
+        for url in ["", "/hooks", "ftp://example.com", "mailto:a@b.com"] {
+            assert!(WebhookUrl::parse(url.into()).is_err(), "{url}");
+        }
+    }
+
+    #[test]
+    fn secrets_are_prefixed_and_random() {
+        let secret = WebhookSecret::generate();
+
+        assert!(secret.as_str().starts_with("whsec_"));
+        assert_ne!(secret.as_str(), WebhookSecret::generate().as_str());
+    }
+}
@@ -0,0 +1,108 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    ApiResponse, Error, Problem, Result,
+    app::AppState,
+    features::{
+        shared::{
+            AppUser, Credential, Page, PageQuery, ensure_admin, ensure_interactive,
+            ensure_recent_authentication,
+        },
+        webhooks::{handlers::WebhookDeliveryResponse, listing::DeliveryListing},
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}/deliveries",
+    tag = "webhooks",
+    security(("session_cookie" = []), ("bearer" = [])),
+    params(
+        ("id" = Uuid, Path, description = "Webhook endpoint id"),
+        ("limit" = Option<u32>, Query, description = "Page size, 1 to 100; defaults to 20"),
+        ("cursor" = Option<String>, Query, description = "`next_cursor` from the previous page, with the same sort and filters"),
+        ("sort" = Option<String>, Query, description = "`created_at`, prefixed with `-` for descending; defaults to `-created_at`"),
+        ("status" = Option<String>, Query, description = "`pending`, `retrying`, `succeeded` or `failed`"),
+        ("event_type" = Option<String>, Query, description = "`user.signed_up`, `user.verified` or `user.banned`"),
+    ),
+    responses(
+        (status = 200, description = "A page of the endpoint's deliveries", body = ApiResponse<Page<WebhookDeliveryResponse>>),
+        (status = 400, description = "Invalid query parameters or cursor", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not an admin, or signed in with an API key", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "No such endpoint", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn list_deliveries_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    Path(id): Path<String>,
+    page: Result<PageQuery<DeliveryListing>>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    ensure_admin(&user)?;
+    let id =
+        Uuid::parse_str(&id).map_err(|_| Error::NotFound("Webhook endpoint not found".into()))?;
+
+    let page = state.webhook_service.list_deliveries(&id, &page?).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: page.map(WebhookDeliveryResponse::from),
+        }),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks/{id}/deliveries/{delivery_id}/replay",
+    tag = "webhooks",
+    params(
+        ("id" = Uuid, Path, description = "Webhook endpoint id"),
+        ("delivery_id" = Uuid, Path, description = "Delivery to send again"),
+    ),
+    security(("session_cookie" = [], "csrf_token" = []), ("bearer" = [])),
+    responses(
+        (status = 202, description = "A new delivery of the same payload, queued", body = ApiResponse<WebhookDeliveryResponse>),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not an admin, signed in with an API key, CSRF token missing, or recent authentication required", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "No such endpoint or delivery", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn replay_delivery_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    Path((id, delivery_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    ensure_admin(&user)?;
+    ensure_recent_authentication(&credential, state.auth_service.reauthentication_max_age())?;
+    let not_found = |_| Error::NotFound("Webhook delivery not found".into());
+    let id = Uuid::parse_str(&id).map_err(not_found)?;
+    let delivery_id = Uuid::parse_str(&delivery_id).map_err(not_found)?;
+
+    let delivery = state
+        .webhook_service
+        .replay_delivery(&id, &delivery_id)
+        .await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ApiResponse {
+            data: WebhookDeliveryResponse::from(delivery),
+        }),
+    ))
+}
@@ -0,0 +1,159 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use axum_extra::extract::WithRejection;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    ApiResponse, Error, Problem, Result,
+    app::AppState,
+    features::{
+        shared::{
+            AppUser, Credential, ensure_admin, ensure_interactive, ensure_recent_authentication,
+        },
+        webhooks::{
+            WebhookDescription, WebhookEventType, WebhookEventTypes, WebhookUrl,
+            handlers::{CreatedWebhookEndpointResponse, WebhookEndpointResponse},
+            service::CreateWebhookEndpointInput,
+        },
+    },
+    validate_and_parse,
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateWebhookEndpointRequest {
+    pub url: String,
+    pub description: Option<String>,
+    #[schema(value_type = Vec<WebhookEventType>)]
+    pub event_types: Vec<String>,
+}
+
+impl TryFrom<CreateWebhookEndpointRequest> for CreateWebhookEndpointInput {
+    type Error = Error;
+
+    fn try_from(value: CreateWebhookEndpointRequest) -> std::result::Result<Self, Self::Error> {
+        let (url, description, event_types) = validate_and_parse!(
+            url => WebhookUrl::parse(value.url),
+            description => value.description.map(WebhookDescription::parse).transpose(),
+            event_types => WebhookEventTypes::parse(value.event_types),
+        );
+
+        Ok(CreateWebhookEndpointInput {
+            url,
+            description,
+            event_types,
+        })
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhookEndpointRequest,
+    security(("session_cookie" = [], "csrf_token" = []), ("bearer" = [])),
+    responses(
+        (status = 201, description = "The new endpoint; `secret` is only ever returned here", body = ApiResponse<CreatedWebhookEndpointResponse>),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not an admin, signed in with an API key, CSRF token missing, or recent authentication required", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn create_endpoint_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    WithRejection(Json(data), _): WithRejection<Json<CreateWebhookEndpointRequest>, Error>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    ensure_admin(&user)?;
+    ensure_recent_authentication(&credential, state.auth_service.reauthentication_max_age())?;
+
+    let (endpoint, secret) = state
+        .webhook_service
+        .create_endpoint(data.try_into()?)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: CreatedWebhookEndpointResponse {
+                endpoint: endpoint.into(),
+                secret,
+            },
+        }),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "webhooks",
+    security(("session_cookie" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "Every endpoint, oldest first", body = ApiResponse<Vec<WebhookEndpointResponse>>),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not an admin, or signed in with an API key", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn list_endpoints_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    ensure_admin(&user)?;
+
+    let endpoints = state.webhook_service.list_endpoints().await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            data: endpoints
+                .into_iter()
+                .map(WebhookEndpointResponse::from)
+                .collect::<Vec<_>>(),
+        }),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "Webhook endpoint id")),
+    security(("session_cookie" = [], "csrf_token" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "Endpoint deleted with its deliveries", body = ApiResponse<String>),
+        (status = 401, description = "Not signed in", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Not an admin, signed in with an API key, CSRF token missing, or recent authentication required", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "No such endpoint", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many requests", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+pub async fn delete_endpoint_v1(
+    State(state): State<AppState>,
+    Extension(user): Extension<Option<AppUser>>,
+    Extension(credential): Extension<Credential>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse> {
+    ensure_interactive(&credential)?;
+    let user = user.ok_or(Error::Unauthorized)?;
+    ensure_admin(&user)?;
+    ensure_recent_authentication(&credential, state.auth_service.reauthentication_max_age())?;
+    let id =
+        Uuid::parse_str(&id).map_err(|_| Error::NotFound("Webhook endpoint not found".into()))?;
+
+    state.webhook_service.delete_endpoint(&id).await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse { data: "Success" })))
+}
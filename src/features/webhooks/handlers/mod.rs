@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::OffsetDateTime;
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+use crate::features::webhooks::domain::{
+    WebhookDelivery, WebhookDeliveryStatus, WebhookEndpoint, WebhookEventType,
+};
+
+mod deliveries_handler;
+mod endpoints_handler;
+
+pub use deliveries_handler::{list_deliveries_v1, replay_delivery_v1};
+pub use endpoints_handler::{
+    CreateWebhookEndpointRequest, create_endpoint_v1, delete_endpoint_v1, list_endpoints_v1,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        endpoints_handler::create_endpoint_v1,
+        endpoints_handler::list_endpoints_v1,
+        endpoints_handler::delete_endpoint_v1,
+        deliveries_handler::list_deliveries_v1,
+        deliveries_handler::replay_delivery_v1,
+    ),
+    tags((name = "webhooks", description = "Outgoing webhooks for user lifecycle events. Requests carry `x-kicks-event`, `x-kicks-delivery` and `x-kicks-signature: t=<unix seconds>,v1=<hex HMAC-SHA256 of \"<t>.<body>\" keyed with the endpoint secret>`")),
+)]
+pub struct WebhooksApi;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct WebhookEndpointResponse {
+    pub id: Uuid,
+    pub url: String,
+    pub description: Option<String>,
+    pub event_types: Vec<WebhookEventType>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<WebhookEndpoint> for WebhookEndpointResponse {
+    fn from(value: WebhookEndpoint) -> Self {
+        Self {
+            id: value.id,
+            url: value.url,
+            description: value.description,
+            event_types: value.event_types,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct CreatedWebhookEndpointResponse {
+    #[serde(flatten)]
+    pub endpoint: WebhookEndpointResponse,
+    pub secret: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct WebhookDeliveryResponse {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    /// Shared by replays of the same event.
+    pub event_id: Uuid,
+    pub event_type: String,
+    /// The request body sent to the endpoint.
+    #[schema(value_type = Object)]
+    pub payload: Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    /// HTTP status of the last attempt, if the endpoint answered.
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    /// The delivery this one replays.
+    pub replay_of: Option<Uuid>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_attempt_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub delivered_at: Option<OffsetDateTime>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(value: WebhookDelivery) -> Self {
+        Self {
+            id: value.id,
+            endpoint_id: value.endpoint_id,
+            event_id: value.event_id,
+            event_type: value.event_type,
+            payload: value.payload,
+            status: value.status,
+            attempts: value.attempts,
+            response_status: value.response_status,
+            last_error: value.last_error,
+            replay_of: value.replay_of,
+            created_at: value.created_at,
+            last_attempt_at: value.last_attempt_at,
+            delivered_at: value.delivered_at,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{Result, app::AppState, jobs::Job};
+
+/// One attempt per run; failures are retried with the queue's back-off.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeliverWebhook {
+    pub delivery_id: Uuid,
+}
+
+#[async_trait]
+impl Job for DeliverWebhook {
+    const KIND: &'static str = "webhooks.deliver";
+    const MAX_ATTEMPTS: i32 = 8;
+
+    async fn run(self, state: &AppState) -> Result<()> {
+        state.webhook_service.deliver(&self.delivery_id).await
+    }
+}
@@ -0,0 +1,56 @@
+use time::format_description::well_known::Rfc3339;
+
+use crate::features::{
+    shared::{Column, FilterField, FilterKind, ListSpec, Listing, SortField},
+    webhooks::domain::WebhookDelivery,
+};
+
+/// `GET /api/v1/admin/webhooks/{id}/deliveries`: latest deliveries first.
+pub struct DeliveryListing;
+
+impl Listing for DeliveryListing {
+    const SPEC: &'static ListSpec = &ListSpec {
+        sorts: &[SortField {
+            name: "created_at",
+            column: Column {
+                name: "created_at",
+                sql_type: "timestamptz",
+            },
+        }],
+        filters: &[
+            FilterField {
+                name: "status",
+                column: "status",
+                kind: FilterKind::Enum {
+                    sql_type: "webhook_delivery_status",
+                    values: &["pending", "retrying", "succeeded", "failed"],
+                },
+            },
+            FilterField {
+                name: "event_type",
+                column: "event_type",
+                kind: FilterKind::Enum {
+                    sql_type: "text",
+                    values: &["user.signed_up", "user.verified", "user.banned"],
+                },
+            },
+        ],
+        default_sort: "-created_at",
+        tiebreaker: Column {
+            name: "id",
+            sql_type: "uuid",
+        },
+        default_limit: 20,
+        max_limit: 100,
+    };
+
+    type Row = WebhookDelivery;
+
+    fn sort_value(row: &WebhookDelivery, _: &SortField) -> String {
+        row.created_at.format(&Rfc3339).unwrap_or_default()
+    }
+
+    fn tiebreaker_value(row: &WebhookDelivery) -> String {
+        row.id.to_string()
+    }
+}
@@ -0,0 +1,93 @@
+use axum::{
+    Router, middleware,
+    routing::{delete, get, post},
+};
+use reqwest::Client;
+use sqlx::PgPool;
+
+use crate::{
+    app::AppState,
+    features::webhooks::repository::WebhookRepository,
+    middlewares::{RateLimitLayer, authenticate, identify},
+};
+
+mod dispatcher;
+mod domain;
+mod handlers;
+mod jobs;
+mod listing;
+mod repository;
+mod service;
+
+pub use dispatcher::WebhookDispatcher;
+pub use domain::*;
+pub use handlers::{
+    CreateWebhookEndpointRequest, CreatedWebhookEndpointResponse, WebhookDeliveryResponse,
+    WebhookEndpointResponse, WebhooksApi,
+};
+pub use jobs::DeliverWebhook;
+pub use listing::DeliveryListing;
+pub use service::{CreateWebhookEndpointInput, WebhookService};
+
+use handlers::*;
+
+pub struct WebhooksModule {
+    pub webhook_service: WebhookService,
+}
+
+impl WebhooksModule {
+    pub fn new(pool: PgPool, http_client: Client, allow_private_targets: bool) -> Self {
+        Self {
+            webhook_service: WebhookService::new(
+                WebhookRepository::new(pool),
+                http_client,
+                allow_private_targets,
+            ),
+        }
+    }
+
+    /// Admin routes, merged with [`AdminModule::v1`](crate::features::admin::AdminModule::v1)
+    /// under `/api/v1/admin`.
+    pub fn v1(state: AppState) -> Router<AppState> {
+        let limit = || {
+            RateLimitLayer::new(
+                state.redis.clone(),
+                state.settings.clone(),
+                "admin_webhooks",
+                |r| &r.admin,
+            )
+        };
+        let identify = || middleware::from_fn_with_state(state.clone(), identify);
+
+        Router::new()
+            .route(
+                "/webhooks",
+                get(list_endpoints_v1)
+                    .post(create_endpoint_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit())
+                    .layer(identify()),
+            )
+            .route(
+                "/webhooks/{id}",
+                delete(delete_endpoint_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit())
+                    .layer(identify()),
+            )
+            .route(
+                "/webhooks/{id}/deliveries",
+                get(list_deliveries_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit())
+                    .layer(identify()),
+            )
+            .route(
+                "/webhooks/{id}/deliveries/{delivery_id}/replay",
+                post(replay_delivery_v1)
+                    .route_layer(middleware::from_fn(authenticate))
+                    .layer(limit())
+                    .layer(identify()),
+            )
+    }
+}
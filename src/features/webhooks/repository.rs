@@ -0,0 +1,267 @@
+use serde_json::Value;
+use sqlx::{PgPool, QueryBuilder, query, query_as};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    Result,
+    features::{
+        shared::PageQuery,
+        webhooks::{
+            DeliverWebhook,
+            domain::{
+                NewWebhookEndpoint, PendingDelivery, WebhookDelivery, WebhookDeliveryStatus,
+                WebhookEndpoint, WebhookEventType,
+            },
+            listing::DeliveryListing,
+        },
+    },
+    jobs::JobQueue,
+};
+
+const DELIVERY_COLUMNS: &str = r#"
+    id, endpoint_id, event_id, event_type, payload, status, attempts,
+    response_status, last_error, replay_of, created_at, last_attempt_at, delivered_at
+"#;
+
+#[derive(Debug, Clone)]
+pub struct WebhookRepository {
+    pool: PgPool,
+}
+
+impl WebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    #[instrument(skip_all, name = "webhookrepository - create endpoint")]
+    pub async fn create_endpoint(&self, endpoint: &NewWebhookEndpoint) -> Result<WebhookEndpoint> {
+        let event_types: Vec<String> = endpoint
+            .event_types
+            .as_slice()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        let record = query!(
+            r#"
+                INSERT INTO webhook_endpoints (url, description, event_types, secret)
+                VALUES ($1, $2, $3, $4)
+                RETURNING id, url, description, event_types, created_at
+            "#,
+            endpoint.url.as_str(),
+            endpoint.description.as_ref().map(AsRef::<str>::as_ref),
+            &event_types,
+            endpoint.secret.as_str()
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(WebhookEndpoint {
+            id: record.id,
+            url: record.url,
+            description: record.description,
+            event_types: parse_event_types(record.event_types)?,
+            created_at: record.created_at,
+        })
+    }
+
+    #[instrument(skip_all, name = "webhookrepository - list endpoints")]
+    pub async fn list_endpoints(&self) -> Result<Vec<WebhookEndpoint>> {
+        let records = query!(
+            r#"
+                SELECT id, url, description, event_types, created_at
+                FROM webhook_endpoints
+                ORDER BY created_at, id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        records
+            .into_iter()
+            .map(|record| {
+                Ok(WebhookEndpoint {
+                    id: record.id,
+                    url: record.url,
+                    description: record.description,
+                    event_types: parse_event_types(record.event_types)?,
+                    created_at: record.created_at,
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(skip_all, name = "webhookrepository - delete endpoint")]
+    pub async fn delete_endpoint(&self, id: &Uuid) -> Result<bool> {
+        let result = query!("DELETE FROM webhook_endpoints WHERE id = $1", id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[instrument(skip_all, name = "webhookrepository - endpoint exists")]
+    pub async fn endpoint_exists(&self, id: &Uuid) -> Result<bool> {
+        let record = query!(
+            r#"SELECT EXISTS (SELECT 1 FROM webhook_endpoints WHERE id = $1) AS "exists!""#,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(record.exists)
+    }
+
+    /// Creates a pending delivery of `payload` for every endpoint subscribed
+    /// to `event_type`, and a job to send each, in one transaction. Endpoints
+    /// that already have a delivery of `event_id` are skipped, so a retried
+    /// dispatch doesn't send an event twice. Returns the new deliveries' ids.
+    #[instrument(skip_all, name = "webhookrepository - create deliveries")]
+    pub async fn create_deliveries(
+        &self,
+        event_type: WebhookEventType,
+        event_id: &Uuid,
+        payload: &Value,
+    ) -> Result<Vec<Uuid>> {
+        let mut tx = self.pool.begin().await?;
+
+        let records = query!(
+            r#"
+                INSERT INTO webhook_deliveries (endpoint_id, event_id, event_type, payload)
+                SELECT id, $2, $1, $3
+                FROM webhook_endpoints
+                WHERE $1 = ANY (event_types)
+                ON CONFLICT (endpoint_id, event_id) WHERE replay_of IS NULL DO NOTHING
+                RETURNING id
+            "#,
+            event_type.as_str(),
+            event_id,
+            payload
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let deliveries: Vec<Uuid> = records.into_iter().map(|r| r.id).collect();
+        for delivery_id in &deliveries {
+            JobQueue::enqueue_in(
+                &mut tx,
+                &DeliverWebhook {
+                    delivery_id: *delivery_id,
+                },
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(deliveries)
+    }
+
+    /// Copies a delivery of `endpoint_id` into a new pending one, with a job
+    /// to send it, in one transaction.
+    #[instrument(skip_all, name = "webhookrepository - replay delivery")]
+    pub async fn replay_delivery(
+        &self,
+        endpoint_id: &Uuid,
+        id: &Uuid,
+    ) -> Result<Option<WebhookDelivery>> {
+        let mut tx = self.pool.begin().await?;
+
+        let delivery: Option<WebhookDelivery> = query_as(&format!(
+            r#"
+                INSERT INTO webhook_deliveries (endpoint_id, event_id, event_type, payload, replay_of)
+                SELECT endpoint_id, event_id, event_type, payload, id
+                FROM webhook_deliveries
+                WHERE id = $1 AND endpoint_id = $2
+                RETURNING {DELIVERY_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(endpoint_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(delivery) = &delivery {
+            JobQueue::enqueue_in(
+                &mut tx,
+                &DeliverWebhook {
+                    delivery_id: delivery.id,
+                },
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(delivery)
+    }
+
+    #[instrument(skip_all, name = "webhookrepository - list deliveries")]
+    pub async fn list_deliveries(
+        &self,
+        endpoint_id: &Uuid,
+        page: &PageQuery<DeliveryListing>,
+    ) -> Result<Vec<WebhookDelivery>> {
+        let mut builder = QueryBuilder::new(format!(
+            "SELECT {DELIVERY_COLUMNS} FROM (SELECT * FROM webhook_deliveries WHERE endpoint_id = "
+        ));
+        builder.push_bind(endpoint_id).push(") AS deliveries");
+        page.push_sql(&mut builder);
+
+        Ok(builder.build_query_as().fetch_all(&self.pool).await?)
+    }
+
+    #[instrument(skip_all, name = "webhookrepository - get pending delivery")]
+    pub async fn get_pending_delivery(&self, id: &Uuid) -> Result<Option<PendingDelivery>> {
+        let delivery = query_as!(
+            PendingDelivery,
+            r#"
+                SELECT d.id, d.event_type, d.payload, d.status AS "status: WebhookDeliveryStatus",
+                    d.attempts, e.url, e.secret
+                FROM webhook_deliveries d
+                JOIN webhook_endpoints e ON e.id = d.endpoint_id
+                WHERE d.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(delivery)
+    }
+
+    #[instrument(skip_all, name = "webhookrepository - record attempt")]
+    pub async fn record_attempt(
+        &self,
+        id: &Uuid,
+        status: WebhookDeliveryStatus,
+        response_status: Option<i32>,
+        error: Option<&str>,
+    ) -> Result<()> {
+        query!(
+            r#"
+                UPDATE webhook_deliveries
+                SET status = $2::webhook_delivery_status,
+                    attempts = attempts + 1,
+                    response_status = $3,
+                    last_error = $4,
+                    last_attempt_at = NOW(),
+                    delivered_at = CASE WHEN $2::webhook_delivery_status = 'succeeded' THEN NOW() END
+                WHERE id = $1
+            "#,
+            id,
+            status as WebhookDeliveryStatus,
+            response_status,
+            error
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn parse_event_types(values: Vec<String>) -> Result<Vec<WebhookEventType>> {
+    values.into_iter().map(WebhookEventType::parse).collect()
+}
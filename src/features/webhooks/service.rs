@@ -0,0 +1,208 @@
+use reqwest::{Client, Url, header::CONTENT_TYPE};
+use time::OffsetDateTime;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    Error, Result,
+    clients::http_client::{PropagateTrace, is_public_host},
+    features::{
+        shared::{Page, PageQuery},
+        webhooks::{
+            DeliverWebhook,
+            domain::{
+                NewWebhookEndpoint, WEBHOOK_DELIVERY_HEADER, WEBHOOK_EVENT_HEADER,
+                WEBHOOK_SIGNATURE_HEADER, WebhookDelivery, WebhookDeliveryStatus,
+                WebhookDescription, WebhookEndpoint, WebhookEventTypes, WebhookSecret, WebhookUrl,
+                sign_payload,
+            },
+            listing::DeliveryListing,
+            repository::WebhookRepository,
+        },
+    },
+    i18n::Message,
+    jobs::Job,
+    validate_and_parse,
+};
+
+pub struct WebhookService {
+    repository: WebhookRepository,
+    http_client: Client,
+    allow_private_targets: bool,
+}
+
+pub struct CreateWebhookEndpointInput {
+    pub url: WebhookUrl,
+    pub description: Option<WebhookDescription>,
+    pub event_types: WebhookEventTypes,
+}
+
+impl WebhookService {
+    pub fn new(
+        repository: WebhookRepository,
+        http_client: Client,
+        allow_private_targets: bool,
+    ) -> Self {
+        Self {
+            repository,
+            http_client,
+            allow_private_targets,
+        }
+    }
+
+    /// Whether requests to `url` may be sent. Unless private targets are
+    /// allowed, the host must resolve to public addresses only.
+    async fn is_allowed_target(&self, url: &Url) -> bool {
+        self.allow_private_targets || is_public_host(url).await
+    }
+
+    /// Returns the endpoint with its signing secret, which is only ever
+    /// shown here.
+    #[instrument(name = "webhooks.create_endpoint", skip_all, fields(url = data.url.as_str()))]
+    pub async fn create_endpoint(
+        &self,
+        data: CreateWebhookEndpointInput,
+    ) -> Result<(WebhookEndpoint, String)> {
+        let url = validate_and_parse!(
+            url => match self.is_allowed_target(data.url.as_url()).await {
+                true => Ok(data.url),
+                false => Err(Error::DomainValidationError(vec![Message::new(
+                    "webhook.private_url",
+                )])),
+            }
+        );
+
+        let secret = WebhookSecret::generate();
+        let endpoint = self
+            .repository
+            .create_endpoint(&NewWebhookEndpoint {
+                url,
+                description: data.description,
+                event_types: data.event_types,
+                secret: secret.clone(),
+            })
+            .await?;
+
+        Ok((endpoint, secret.as_str().to_owned()))
+    }
+
+    #[instrument(name = "webhooks.list_endpoints", skip_all)]
+    pub async fn list_endpoints(&self) -> Result<Vec<WebhookEndpoint>> {
+        self.repository.list_endpoints().await
+    }
+
+    /// Deliveries still queued for the endpoint are dropped with it.
+    #[instrument(name = "webhooks.delete_endpoint", skip(self))]
+    pub async fn delete_endpoint(&self, id: &Uuid) -> Result<()> {
+        if !self.repository.delete_endpoint(id).await? {
+            return Err(Error::NotFound("Webhook endpoint not found".into()));
+        }
+
+        Ok(())
+    }
+
+    #[instrument(name = "webhooks.list_deliveries", skip(self, page), fields(limit = page.limit))]
+    pub async fn list_deliveries(
+        &self,
+        endpoint_id: &Uuid,
+        page: &PageQuery<DeliveryListing>,
+    ) -> Result<Page<WebhookDelivery>> {
+        if !self.repository.endpoint_exists(endpoint_id).await? {
+            return Err(Error::NotFound("Webhook endpoint not found".into()));
+        }
+
+        let rows = self.repository.list_deliveries(endpoint_id, page).await?;
+
+        Ok(page.page(rows))
+    }
+
+    /// Sends a past delivery's payload again as a new delivery, keeping the
+    /// event id so receivers can tell it apart from a new event.
+    #[instrument(name = "webhooks.replay_delivery", skip(self))]
+    pub async fn replay_delivery(&self, endpoint_id: &Uuid, id: &Uuid) -> Result<WebhookDelivery> {
+        let delivery = self
+            .repository
+            .replay_delivery(endpoint_id, id)
+            .await?
+            .ok_or(Error::NotFound("Webhook delivery not found".into()))?;
+
+        Ok(delivery)
+    }
+
+    /// Makes one attempt and records its outcome. Errors when the receiver
+    /// can't be reached or doesn't answer with a 2xx, so the job retries.
+    #[instrument(name = "webhooks.deliver", skip(self))]
+    pub async fn deliver(&self, id: &Uuid) -> Result<()> {
+        // Gone with its endpoint, or already through on an earlier run.
+        let Some(delivery) = self.repository.get_pending_delivery(id).await? else {
+            return Ok(());
+        };
+        if delivery.status == WebhookDeliveryStatus::Succeeded {
+            return Ok(());
+        }
+
+        // Hosts are checked again on every attempt, as the webhook client's
+        // resolver doesn't see IP literals.
+        let allowed = match Url::parse(&delivery.url) {
+            Ok(url) => self.is_allowed_target(&url).await,
+            Err(_) => false,
+        };
+        if !allowed {
+            self.repository
+                .record_attempt(
+                    id,
+                    WebhookDeliveryStatus::Failed,
+                    None,
+                    Some("receiver address is not public"),
+                )
+                .await?;
+            return Ok(());
+        }
+
+        let body = serde_json::to_vec(&delivery.payload)?;
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+
+        let response = self
+            .http_client
+            .post(&delivery.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(
+                WEBHOOK_SIGNATURE_HEADER,
+                sign_payload(&delivery.secret, timestamp, &body),
+            )
+            .header(WEBHOOK_EVENT_HEADER, &delivery.event_type)
+            .header(WEBHOOK_DELIVERY_HEADER, delivery.id.to_string())
+            .body(body)
+            .propagate_trace()
+            .send()
+            .await;
+
+        let (response_status, error) = match response {
+            Ok(response) if response.status().is_success() => {
+                (Some(response.status().as_u16().into()), None)
+            }
+            Ok(response) => (
+                Some(response.status().as_u16().into()),
+                Some(format!("receiver responded with {}", response.status())),
+            ),
+            Err(err) => (None, Some(err.without_url().to_string())),
+        };
+
+        let status = match &error {
+            None => WebhookDeliveryStatus::Succeeded,
+            Some(_) if delivery.attempts + 1 >= DeliverWebhook::MAX_ATTEMPTS => {
+                WebhookDeliveryStatus::Failed
+            }
+            Some(_) => WebhookDeliveryStatus::Retrying,
+        };
+
+        self.repository
+            .record_attempt(id, status, response_status, error.as_deref())
+            .await?;
+
+        match error {
+            None => Ok(()),
+            Some(error) => Err(Error::Internal(error)),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::LazyLock,
+};
+
+/// A language with a bundled catalog under `src/i18n/locales`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::De];
+
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+        }
+    }
+
+    /// Matches on the primary subtag, so `de-AT` is served German.
+    fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split('-').next().unwrap_or_default();
+
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.tag().eq_ignore_ascii_case(primary))
+    }
+
+    /// Picks the supported language the client weights highest in an
+    /// `Accept-Language` header, falling back to English.
+    pub fn negotiate(accept_language: Option<&str>) -> Self {
+        let Some(header) = accept_language else {
+            return Self::default();
+        };
+
+        let mut best: Option<(Self, f32)> = None;
+
+        for range in header.split(',') {
+            let mut parts = range.split(';');
+            let tag = parts.next().unwrap_or_default().trim();
+            let quality = parts
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .and_then(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            if let Some(locale) = Self::from_tag(tag)
+                && quality > 0.0
+                && best.is_none_or(|(_, q)| quality > q)
+            {
+                best = Some((locale, quality));
+            }
+        }
+
+        best.map(|(locale, _)| locale).unwrap_or_default()
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Locale::En => include_str!("locales/en.json"),
+            Locale::De => include_str!("locales/de.json"),
+        }
+    }
+}
+
+static CATALOGS: LazyLock<HashMap<Locale, HashMap<String, String>>> = LazyLock::new(|| {
+    Locale::ALL
+        .into_iter()
+        .map(|locale| {
+            let catalog = serde_json::from_str(locale.source())
+                .unwrap_or_else(|e| panic!("invalid {} catalog: {e}", locale.tag()));
+            (locale, catalog)
+        })
+        .collect()
+});
+
+/// A translatable message: a catalog key and the values for its `{placeholders}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub key: &'static str,
+    pub params: BTreeMap<&'static str, String>,
+}
+
+impl Message {
+    pub fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            params: BTreeMap::new(),
+        }
+    }
+
+    pub fn with(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.params.insert(name, value.to_string());
+        self
+    }
+
+    pub fn translate(&self, locale: Locale) -> String {
+        translate(locale, self.key, &self.params)
+    }
+}
+
+/// Renders `key` in `locale`, falling back to English and then to the key itself.
+pub fn translate(locale: Locale, key: &str, params: &BTreeMap<&'static str, String>) -> String {
+    let template = [locale, Locale::En]
+        .iter()
+        .find_map(|l| CATALOGS[l].get(key))
+        .map(String::as_str)
+        .unwrap_or(key);
+
+    params
+        .iter()
+        .fold(template.to_owned(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    fn placeholders(template: &str) -> BTreeSet<&str> {
+        template
+            .split('{')
+            .skip(1)
+            .filter_map(|s| s.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn catalogs_have_the_same_keys_and_placeholders() {
+        let english = &CATALOGS[&Locale::En];
+
+        for locale in Locale::ALL {
+            let catalog = &CATALOGS[&locale];
+            assert_eq!(
+                english.keys().collect::<BTreeSet<_>>(),
+                catalog.keys().collect::<BTreeSet<_>>(),
+                "{} catalog keys differ from English",
+                locale.tag()
+            );
+
+            for (key, template) in catalog {
+                assert_eq!(
+                    placeholders(&english[key]),
+                    placeholders(template),
+                    "{} placeholders differ for {key}",
+                    locale.tag()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn params_are_substituted() {
+        let message = Message::new("value.too_short").with("min", 8);
+
+        assert_eq!(
+            message.translate(Locale::En),
+            "Value must be at least 8 characters."
+        );
+        assert_eq!(
+            message.translate(Locale::De),
+            "Der Wert muss mindestens 8 Zeichen lang sein."
+        );
+    }
+
+    #[test]
+    fn unknown_key_falls_back_to_the_key() {
+        assert_eq!(Message::new("nope").translate(Locale::De), "nope");
+    }
+
+    #[test]
+    fn accept_language_is_negotiated_by_quality() {
+        assert_eq!(Locale::negotiate(None), Locale::En);
+        assert_eq!(Locale::negotiate(Some("de-AT")), Locale::De);
+        assert_eq!(
+            Locale::negotiate(Some("fr, de;q=0.8, en;q=0.5")),
+            Locale::De
+        );
+        assert_eq!(Locale::negotiate(Some("de;q=0.3, en-GB;q=0.9")), Locale::En);
+        assert_eq!(Locale::negotiate(Some("de;q=0, fr")), Locale::En);
+    }
+}
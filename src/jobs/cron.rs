@@ -0,0 +1,239 @@
+use std::str::FromStr;
+
+use time::{Date, Duration, Month, OffsetDateTime, Time};
+
+/// A five-field cron expression (`minute hour day-of-month month
+/// day-of-week`) evaluated in UTC. Fields take `*`, values, ranges, lists
+/// and `/` steps; day of week runs from 0 (Sunday) to 6, with 7 also
+/// meaning Sunday. `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly`
+/// are accepted as shorthands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether day of month and day of week were both restricted; cron then
+    /// matches days satisfying either.
+    either_day: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, &'static str> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" => "0 0 1 1 *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err("expected five fields: minute hour day-of-month month day-of-week");
+        };
+
+        let mut weekdays = parse_field(weekday, 0, 7)?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days: parse_field(day, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            weekdays,
+            either_day: day != "*" && weekday != "*",
+        })
+    }
+
+    /// The first matching minute strictly after `after`, or `None` if the
+    /// expression can never match (such as the 30th of February).
+    pub fn next_after(&self, after: OffsetDateTime) -> Option<OffsetDateTime> {
+        let after = after.to_offset(time::UtcOffset::UTC);
+        let mut candidate = after
+            .replace_time(Time::from_hms(after.hour(), after.minute(), 0).ok()?)
+            + Duration::minutes(1);
+        let give_up = candidate + Duration::days(366 * 5);
+
+        while candidate < give_up {
+            if !contains(self.months, u8::from(candidate.month())) {
+                let (year, month) = match candidate.month() {
+                    Month::December => (candidate.year() + 1, Month::January),
+                    month => (candidate.year(), month.next()),
+                };
+                candidate = Date::from_calendar_date(year, month, 1)
+                    .ok()?
+                    .midnight()
+                    .assume_utc();
+            } else if !self.matches_day(candidate.date()) {
+                candidate = candidate.date().next_day()?.midnight().assume_utc();
+            } else if !contains(self.hours, candidate.hour()) {
+                candidate = candidate.replace_time(Time::from_hms(candidate.hour(), 0, 0).ok()?)
+                    + Duration::hours(1);
+            } else if !contains(self.minutes, candidate.minute()) {
+                candidate += Duration::minutes(1);
+            } else {
+                return Some(candidate);
+            }
+        }
+
+        None
+    }
+
+    fn matches_day(&self, date: Date) -> bool {
+        let day = contains(self.days, date.day());
+        let weekday = contains(self.weekdays, date.weekday().number_days_from_sunday());
+
+        if self.either_day {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn contains(set: u64, value: u8) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Parses one field into a bit set of the values it matches.
+fn parse_field(field: &str, min: u8, max: u8) -> Result<u64, &'static str> {
+    let mut set = 0;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u8>().map_err(|_| "invalid step")?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err("step must be positive");
+        }
+
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (parse_value(start, min, max)?, parse_value(end, min, max)?),
+                None => {
+                    let value = parse_value(range, min, max)?;
+                    // `5/15` means from 5 to the end of the field in steps of 15.
+                    (value, if part.contains('/') { max } else { value })
+                }
+            },
+        };
+        if start > end {
+            return Err("range start is after its end");
+        }
+
+        for value in (start..=end).step_by(step.into()) {
+            set |= 1 << value;
+        }
+    }
+
+    Ok(set)
+}
+
+fn parse_value(value: &str, min: u8, max: u8) -> Result<u8, &'static str> {
+    value
+        .parse::<u8>()
+        .ok()
+        .filter(|value| (min..=max).contains(value))
+        .ok_or("value out of range")
+}
+
+#[cfg(test)]
+mod test {
+    use time::format_description::well_known::Rfc3339;
+
+    use super::*;
+
+    fn at(timestamp: &str) -> OffsetDateTime {
+        OffsetDateTime::parse(timestamp, &Rfc3339).unwrap()
+    }
+
+    fn next(expression: &str, after: OffsetDateTime) -> OffsetDateTime {
+        CronSchedule::parse(expression)
+            .unwrap()
+            .next_after(after)
+            .unwrap()
+    }
+
+    #[test]
+    fn next_tick_is_strictly_after() {
+        let now = at("2026-03-01T10:17:00Z");
+
+        assert_eq!(next("17 * * * *", now), at("2026-03-01T11:17:00Z"));
+        assert_eq!(
+            next("* * * * *", at("2026-03-01T10:17:42Z")),
+            at("2026-03-01T10:18:00Z")
+        );
+    }
+
+    #[test]
+    fn steps_ranges_and_lists() {
+        let now = at("2026-03-01T10:17:00Z");
+
+        assert_eq!(next("*/15 * * * *", now), at("2026-03-01T10:30:00Z"));
+        assert_eq!(next("0 9-17/4 * * *", now), at("2026-03-01T13:00:00Z"));
+        assert_eq!(next("0 0 1,15 * *", now), at("2026-03-15T00:00:00Z"));
+        assert_eq!(next("5/20 * * * *", now), at("2026-03-01T10:25:00Z"));
+    }
+
+    #[test]
+    fn rolls_over_months_and_years() {
+        assert_eq!(
+            next("@yearly", at("2026-03-01T10:17:00Z")),
+            at("2027-01-01T00:00:00Z")
+        );
+        assert_eq!(
+            next("30 4 31 * *", at("2026-04-01T00:00:00Z")),
+            at("2026-05-31T04:30:00Z")
+        );
+    }
+
+    #[test]
+    fn restricted_day_fields_match_either() {
+        // 2026-03-01 is a Sunday, so Friday the 6th comes before the 10th.
+        assert_eq!(
+            next("0 0 10 * 5", at("2026-03-01T10:00:00Z")),
+            at("2026-03-06T00:00:00Z")
+        );
+        assert_eq!(
+            next("0 0 * * 7", at("2026-03-01T10:00:00Z")),
+            at("2026-03-08T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn impossible_dates_never_match() {
+        let schedule = CronSchedule::parse("0 0 30 2 *").unwrap();
+
+        assert_eq!(schedule.next_after(at("2026-03-01T10:00:00Z")), None);
+    }
+
+    #[test]
+    fn invalid_expressions_are_rejected() {
+        for expression in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+        ] {
+            assert!(CronSchedule::parse(expression).is_err(), "{expression}");
+        }
+    }
+}
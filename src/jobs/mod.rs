@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{Result, app::AppState};
+
+mod cron;
+mod queue;
+mod runner;
+
+pub use cron::CronSchedule;
+pub use queue::JobQueue;
+pub use runner::JobRunner;
+
+/// Deferred work, stored as JSON under [`KIND`](Job::KIND) until a worker
+/// runs it. Failed runs are retried with backoff until `MAX_ATTEMPTS` is
+/// reached, so `run` must be safe to repeat.
+#[async_trait]
+pub trait Job: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Stored with every queued job; renaming a kind orphans queued jobs.
+    const KIND: &'static str;
+    const MAX_ATTEMPTS: i32 = 5;
+
+    async fn run(self, state: &AppState) -> Result<()>;
+}
@@ -0,0 +1,199 @@
+use std::time::Duration;
+
+use serde_json::Value;
+use sqlx::{PgConnection, PgExecutor, PgPool, query, query_as};
+use time::OffsetDateTime;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{Result, jobs::Job};
+
+/// A job claimed by a worker.
+#[derive(Debug)]
+pub(super) struct ClaimedJob {
+    pub id: Uuid,
+    pub kind: String,
+    pub payload: Value,
+    pub attempts: i32,
+    pub max_attempts: i32,
+}
+
+/// Enqueues jobs into the `jobs` table, where any instance's
+/// [`JobRunner`](super::JobRunner) picks them up.
+#[derive(Debug, Clone)]
+pub struct JobQueue {
+    pool: PgPool,
+}
+
+impl JobQueue {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn enqueue<J: Job>(&self, job: &J) -> Result<Uuid> {
+        self.schedule(job, OffsetDateTime::now_utc()).await
+    }
+
+    /// Enqueues `job` on `conn`, so it's only queued if the transaction
+    /// `conn` belongs to commits.
+    pub async fn enqueue_in<J: Job>(conn: &mut PgConnection, job: &J) -> Result<Uuid> {
+        let id = insert(
+            conn,
+            J::KIND,
+            serde_json::to_value(job)?,
+            J::MAX_ATTEMPTS,
+            OffsetDateTime::now_utc(),
+            None,
+        )
+        .await?;
+
+        Ok(id.expect("jobs without a unique key are always inserted"))
+    }
+
+    /// Enqueues `job` to run no earlier than `run_at`.
+    #[instrument(name = "jobs.schedule", skip(self, job), fields(kind = J::KIND))]
+    pub async fn schedule<J: Job>(&self, job: &J, run_at: OffsetDateTime) -> Result<Uuid> {
+        let id = self
+            .insert(
+                J::KIND,
+                serde_json::to_value(job)?,
+                J::MAX_ATTEMPTS,
+                run_at,
+                None,
+            )
+            .await?;
+
+        Ok(id.expect("jobs without a unique key are always inserted"))
+    }
+
+    /// Returns `None` when a job with `unique_key` already exists.
+    pub(super) async fn insert(
+        &self,
+        kind: &str,
+        payload: Value,
+        max_attempts: i32,
+        run_at: OffsetDateTime,
+        unique_key: Option<&str>,
+    ) -> Result<Option<Uuid>> {
+        insert(&self.pool, kind, payload, max_attempts, run_at, unique_key).await
+    }
+
+    /// Takes the oldest due job, or one whose worker stopped holding it for
+    /// longer than `lock_timeout`. `SKIP LOCKED` lets workers claim
+    /// concurrently without waiting on each other.
+    pub(super) async fn claim(&self, lock_timeout: Duration) -> Result<Option<ClaimedJob>> {
+        let job = query_as!(
+            ClaimedJob,
+            r#"
+                UPDATE jobs
+                SET status = 'running', attempts = attempts + 1, locked_at = NOW()
+                WHERE id = (
+                    SELECT id FROM jobs
+                    WHERE (status = 'pending' AND run_at <= NOW())
+                        OR (status = 'running' AND locked_at < NOW() - make_interval(secs => $1))
+                    ORDER BY run_at
+                    FOR UPDATE SKIP LOCKED
+                    LIMIT 1
+                )
+                RETURNING id, kind, payload, attempts, max_attempts
+            "#,
+            lock_timeout.as_secs_f64()
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    pub(super) async fn complete(&self, id: Uuid) -> Result<()> {
+        query!(
+            r#"
+                UPDATE jobs
+                SET status = 'completed', finished_at = NOW(), locked_at = NULL, last_error = NULL
+                WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn retry(&self, id: Uuid, delay: Duration, error: &str) -> Result<()> {
+        query!(
+            r#"
+                UPDATE jobs
+                SET status = 'pending', run_at = NOW() + make_interval(secs => $2),
+                    locked_at = NULL, last_error = $3
+                WHERE id = $1
+            "#,
+            id,
+            delay.as_secs_f64(),
+            error
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn fail(&self, id: Uuid, error: &str) -> Result<()> {
+        query!(
+            r#"
+                UPDATE jobs
+                SET status = 'failed', finished_at = NOW(), locked_at = NULL, last_error = $2
+                WHERE id = $1
+            "#,
+            id,
+            error
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Deletes completed and failed jobs finished more than `retention` ago.
+    pub(super) async fn prune(&self, retention: Duration) -> Result<u64> {
+        let result = query!(
+            r#"
+                DELETE FROM jobs
+                WHERE status IN ('completed', 'failed')
+                    AND finished_at < NOW() - make_interval(secs => $1)
+            "#,
+            retention.as_secs_f64()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+async fn insert(
+    executor: impl PgExecutor<'_>,
+    kind: &str,
+    payload: Value,
+    max_attempts: i32,
+    run_at: OffsetDateTime,
+    unique_key: Option<&str>,
+) -> Result<Option<Uuid>> {
+    let record = query!(
+        r#"
+            INSERT INTO jobs (kind, payload, max_attempts, run_at, unique_key)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (unique_key) DO NOTHING
+            RETURNING id
+        "#,
+        kind,
+        payload,
+        max_attempts,
+        run_at,
+        unique_key
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(record.map(|r| r.id))
+}
@@ -0,0 +1,262 @@
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::OffsetDateTime;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, error, info, info_span, warn};
+
+use crate::{
+    Result,
+    app::AppState,
+    configuration::jobs_config::JobsConfig,
+    features::metrics::record_job,
+    jobs::{CronSchedule, Job, queue::ClaimedJob},
+};
+
+const BASE_RETRY_DELAY: Duration = Duration::from_secs(10);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+
+type Handler =
+    Arc<dyn Fn(AppState, Value) -> Pin<Box<dyn Future<Output = Outcome> + Send>> + Send + Sync>;
+
+enum Outcome {
+    Completed,
+    /// Worth another attempt, if any are left.
+    Failed(String),
+    /// Would fail the same way again, such as a payload that doesn't decode.
+    Rejected(String),
+}
+
+struct Recurring {
+    name: &'static str,
+    schedule: CronSchedule,
+    kind: &'static str,
+    payload: Value,
+    max_attempts: i32,
+}
+
+/// Runs queued jobs on `workers` tasks and enqueues recurring ones on their
+/// schedule. Started by [`Application::run`](crate::app::Application::run),
+/// which cancels it after the HTTP server has drained; workers finish the
+/// job in hand before stopping.
+pub struct JobRunner {
+    state: AppState,
+    config: JobsConfig,
+    handlers: HashMap<&'static str, Handler>,
+    recurring: Vec<Recurring>,
+}
+
+impl JobRunner {
+    /// Comes with a recurring job that prunes finished jobs past `retention_hours`.
+    pub fn new(state: AppState, config: JobsConfig) -> Self {
+        let prune = PruneJobs {
+            retention_hours: config.retention_hours,
+        };
+
+        Self {
+            state,
+            config,
+            handlers: HashMap::new(),
+            recurring: Vec::new(),
+        }
+        .recurring("prune_jobs", CronSchedule::parse("@hourly").unwrap(), prune)
+    }
+
+    pub fn register<J: Job>(mut self) -> Self {
+        let handler: Handler = Arc::new(|state, payload| {
+            Box::pin(async move {
+                let job: J = match serde_json::from_value(payload) {
+                    Ok(job) => job,
+                    Err(err) => return Outcome::Rejected(format!("invalid payload: {err}")),
+                };
+
+                match job.run(&state).await {
+                    Ok(()) => Outcome::Completed,
+                    Err(err) => Outcome::Failed(format!("{err:?}")),
+                }
+            })
+        });
+        self.handlers.insert(J::KIND, handler);
+        self
+    }
+
+    /// Registers `J` and enqueues `job` at every tick of `schedule`. Ticks
+    /// are keyed by `name`, so each is enqueued once however many instances run.
+    pub fn recurring<J: Job>(mut self, name: &'static str, schedule: CronSchedule, job: J) -> Self {
+        self.recurring.push(Recurring {
+            name,
+            schedule,
+            kind: J::KIND,
+            payload: serde_json::to_value(&job).expect("jobs serialize to JSON"),
+            max_attempts: J::MAX_ATTEMPTS,
+        });
+        self.register::<J>()
+    }
+
+    pub async fn run(self, token: CancellationToken) {
+        let runner = Arc::new(self);
+        let mut tasks = JoinSet::new();
+
+        for _ in 0..runner.config.workers {
+            tasks.spawn(runner.clone().work(token.clone()));
+        }
+        for index in 0..runner.recurring.len() {
+            tasks.spawn(runner.clone().enqueue_recurring(index, token.clone()));
+        }
+
+        while tasks.join_next().await.is_some() {}
+    }
+
+    async fn work(self: Arc<Self>, token: CancellationToken) {
+        let poll_interval = Duration::from_millis(self.config.poll_interval_ms);
+        let lock_timeout = Duration::from_secs(self.config.lock_timeout_seconds);
+
+        while !token.is_cancelled() {
+            match self.state.jobs.claim(lock_timeout).await {
+                Ok(Some(job)) => {
+                    if let Err(err) = self.execute(job).await {
+                        error!(?err, "Failed to record job outcome");
+                    }
+                    continue;
+                }
+                Ok(None) => {}
+                Err(err) => error!(?err, "Failed to claim job"),
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(poll_interval) => {}
+                _ = token.cancelled() => {}
+            }
+        }
+    }
+
+    async fn execute(&self, job: ClaimedJob) -> Result<()> {
+        let span = info_span!("job", id = %job.id, kind = %job.kind, attempt = job.attempts);
+        let queue = &self.state.jobs;
+
+        let outcome = if job.attempts > job.max_attempts {
+            // Reclaimed after its worker stopped while running the last attempt.
+            Outcome::Rejected("abandoned by its worker".into())
+        } else if let Some(handler) = self.handlers.get(job.kind.as_str()) {
+            // A task of its own, so a panicking job is caught like a failing one.
+            let run =
+                tokio::spawn(handler(self.state.clone(), job.payload).instrument(span.clone()));
+            run.await
+                .unwrap_or_else(|err| Outcome::Failed(format!("panicked: {err}")))
+        } else {
+            Outcome::Rejected("no handler registered for this kind".into())
+        };
+
+        async {
+            match outcome {
+                Outcome::Completed => {
+                    record_job(&job.kind, "completed");
+                    queue.complete(job.id).await
+                }
+                Outcome::Failed(reason) if job.attempts < job.max_attempts => {
+                    let delay = retry_delay(job.attempts);
+                    warn!(%reason, retry_in_seconds = delay.as_secs(), "Job failed, retrying");
+                    record_job(&job.kind, "retried");
+                    queue.retry(job.id, delay, &reason).await
+                }
+                Outcome::Failed(reason) | Outcome::Rejected(reason) => {
+                    error!(%reason, "Job failed permanently");
+                    record_job(&job.kind, "failed");
+                    queue.fail(job.id, &reason).await
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn enqueue_recurring(self: Arc<Self>, index: usize, token: CancellationToken) {
+        let recurring = &self.recurring[index];
+
+        loop {
+            let now = OffsetDateTime::now_utc();
+            let Some(tick) = recurring.schedule.next_after(now) else {
+                warn!(
+                    name = recurring.name,
+                    "Recurring job schedule never matches"
+                );
+                return;
+            };
+
+            tokio::select! {
+                _ = tokio::time::sleep((tick - now).unsigned_abs()) => {}
+                _ = token.cancelled() => return,
+            }
+
+            let key = format!("{}@{}", recurring.name, tick.unix_timestamp());
+            let inserted = self
+                .state
+                .jobs
+                .insert(
+                    recurring.kind,
+                    recurring.payload.clone(),
+                    recurring.max_attempts,
+                    tick,
+                    Some(&key),
+                )
+                .await;
+
+            match inserted {
+                Ok(Some(id)) => info!(name = recurring.name, %id, "Enqueued recurring job"),
+                Ok(None) => {}
+                Err(err) => error!(
+                    ?err,
+                    name = recurring.name,
+                    "Failed to enqueue recurring job"
+                ),
+            }
+        }
+    }
+}
+
+/// Doubles from [`BASE_RETRY_DELAY`] after each failed attempt, up to an hour.
+fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.clamp(1, 16) as u32 - 1;
+
+    BASE_RETRY_DELAY
+        .saturating_mul(2u32.pow(exponent))
+        .min(MAX_RETRY_DELAY)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PruneJobs {
+    retention_hours: u64,
+}
+
+#[async_trait]
+impl Job for PruneJobs {
+    const KIND: &'static str = "jobs.prune";
+    const MAX_ATTEMPTS: i32 = 1;
+
+    async fn run(self, state: &AppState) -> Result<()> {
+        let pruned = state
+            .jobs
+            .prune(Duration::from_secs(self.retention_hours * 60 * 60))
+            .await?;
+        info!(pruned, "Pruned finished jobs");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn retry_delay_doubles_up_to_an_hour() {
+        assert_eq!(retry_delay(1), Duration::from_secs(10));
+        assert_eq!(retry_delay(2), Duration::from_secs(20));
+        assert_eq!(retry_delay(4), Duration::from_secs(80));
+        assert_eq!(retry_delay(12), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(i32::MAX), MAX_RETRY_DELAY);
+    }
+}
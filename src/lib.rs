@@ -3,14 +3,18 @@ pub mod clients;
 pub mod common;
 pub mod configuration;
 pub mod error;
+pub mod events;
 pub mod features;
+pub mod i18n;
+pub mod jobs;
 pub mod middlewares;
 pub mod telemetry;
 
 pub use error::*;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ApiResponse<T: Serialize> {
-    data: T,
+    pub data: T,
 }
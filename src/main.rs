@@ -1,25 +1,51 @@
+use std::{path::PathBuf, process};
+
+use clap::Parser;
 use kicksapi::{
     self,
     app::Application,
-    configuration::Configuration,
+    configuration::{Configuration, config_dir, environment, reload::SettingsReloader},
     error::Result,
     telemetry::{create_subscriber, init_subscriber},
 };
 use tokio_util::sync::CancellationToken;
 
+#[derive(Debug, Parser)]
+#[command(name = "kicksapi", version)]
+struct Args {
+    /// Directory holding `<environment>.yaml`.
+    #[arg(long)]
+    config_dir: Option<PathBuf>,
+    /// Print the effective configuration with secrets redacted, then exit.
+    #[arg(long)]
+    print_config: bool,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    let config = Configuration::new();
+    let args = Args::parse();
 
-    let (subscriber, _guard) = create_subscriber(
-        config.application.pretty_log,
-        config.application.log_level.as_str(),
-    );
+    let config_dir = args.config_dir.unwrap_or_else(config_dir);
+    let config = Configuration::load_from(&config_dir, &environment()).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1);
+    });
+
+    if args.print_config {
+        println!("{:#}", config.redacted());
+        return Ok(());
+    }
+
+    let (subscriber, _guard, log_filter) = create_subscriber(&config.application);
     init_subscriber(subscriber);
 
     let app = Application::build(&config).await?;
     let token = CancellationToken::new();
 
+    // SIGHUP re-reads the config; rate limits, TTLs and the log level apply live.
+    let reloader = SettingsReloader::new(app.settings(), log_filter, config_dir, environment());
+    tokio::spawn(reloader.run(token.child_token()));
+
     app.run(token).await?;
 
     Ok(())
@@ -1,31 +1,137 @@
 use axum::{
     extract::{Request, State},
+    http::{HeaderMap, header},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
 use axum_extra::extract::SignedCookieJar;
 
-use crate::{Error, Result, app::AppState, features::auth::generate_session_cookie};
+use crate::{
+    Error, Result,
+    app::AppState,
+    features::{
+        auth::{Session, generate_session_cookie},
+        shared::{AppUser, ClientIp, Credential},
+    },
+    middlewares::{RotatedCookies, cookie_keys::sets_cookie},
+};
 
-pub async fn authenticate(
+/// Resolves the caller from a `Bearer` access token, an `ApiKey` or, when the
+/// `Authorization` header is absent, from the signed session cookie. Anonymous
+/// requests pass through with no user so that layers in between, such as
+/// per-user rate limits, can see who is calling; [`authenticate`] rejects them.
+/// Session expiry is enforced server-side, so the cookie is left untouched
+/// unless it was signed with a previous key and needs reissuing.
+pub async fn identify(
     State(state): State<AppState>,
+    client_ip: ClientIp,
     jar: SignedCookieJar,
     mut req: Request,
     next: Next,
-) -> Result<(SignedCookieJar, Response)> {
-    let session_id = jar
-        .get(&state.config.session_cookie_name)
-        .ok_or(Error::Unauthorized)?;
+) -> Result<Response> {
+    let mut session = None;
+
+    match resolve_credential(&state, &jar, req.headers(), client_ip).await {
+        Ok(Some((user, credential, resolved))) => {
+            req.extensions_mut().insert(Some(user));
+            req.extensions_mut().insert(credential);
+            session = resolved;
+        }
+        Ok(None) | Err(Error::Unauthorized) => {
+            req.extensions_mut().insert(None::<AppUser>);
+        }
+        Err(err) => return Err(err),
+    }
+
+    let cookie_name = &state.config.session_cookie_name;
+    let rotated = req
+        .extensions()
+        .get::<RotatedCookies>()
+        .is_some_and(|rotated| rotated.contains(cookie_name));
+
+    let resp = next.run(req).await;
+
+    match (session, jar.get(cookie_name)) {
+        (Some(session), Some(session_id))
+            if rotated && !sets_cookie(resp.headers(), cookie_name) =>
+        {
+            let cookie = generate_session_cookie(
+                session_id.value().to_owned(),
+                session.cookie_expires_at(),
+                &state.config,
+            );
 
-    let user = state.auth_service.authenticate(session_id.value()).await?;
+            Ok((jar.add(cookie), resp).into_response())
+        }
+        _ => Ok(resp),
+    }
+}
+
+/// Rejects requests that [`identify`] could not authenticate.
+pub async fn authenticate(req: Request, next: Next) -> Result<Response> {
+    if req.extensions().get::<Credential>().is_none() {
+        return Err(Error::Unauthorized);
+    }
+
+    Ok(next.run(req).await)
+}
 
-    req.extensions_mut().insert(Some(user));
-    let jar = jar.add(generate_session_cookie(
-        session_id.value().to_string(),
-        &state.config,
-    ));
+async fn resolve_credential(
+    state: &AppState,
+    jar: &SignedCookieJar,
+    headers: &HeaderMap,
+    ClientIp(client_ip): ClientIp,
+) -> Result<Option<(AppUser, Credential, Option<Session>)>> {
+    if let Some(access_token) = authorization_value(headers, "Bearer") {
+        let (user, authenticated_at) = state
+            .auth_service
+            .authenticate_access_token(access_token)
+            .await?;
 
-    let response = next.run(req).await;
+        return Ok(Some((
+            user,
+            Credential::AccessToken { authenticated_at },
+            None,
+        )));
+    }
+
+    if let Some(api_key) = authorization_value(headers, "ApiKey") {
+        let (user, scopes) = state
+            .auth_service
+            .authenticate_api_key(api_key, &client_ip)
+            .await?;
+
+        return Ok(Some((user, Credential::ApiKey { scopes }, None)));
+    }
+
+    let Some(session_id) = jar.get(&state.config.session_cookie_name) else {
+        return Ok(None);
+    };
+
+    let (user, session) = state.auth_service.authenticate(session_id.value()).await?;
+
+    Ok(Some((
+        user,
+        Credential::Session {
+            authenticated_at: session.authenticated_at,
+        },
+        Some(session),
+    )))
+}
+
+/// Whether [`identify`] takes the caller from the `Authorization` header
+/// rather than the session cookie. Other schemes fall back to the cookie.
+pub fn has_header_credential(headers: &HeaderMap) -> bool {
+    authorization_value(headers, "Bearer").is_some()
+        || authorization_value(headers, "ApiKey").is_some()
+}
 
-    Ok((jar, response))
+fn authorization_value<'a>(headers: &'a HeaderMap, scheme: &str) -> Option<&'a str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix(scheme))
+        .and_then(|v| v.strip_prefix(' '))
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
 }
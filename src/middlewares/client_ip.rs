@@ -0,0 +1,239 @@
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, header},
+    middleware::Next,
+    response::Response,
+};
+use ipnet::IpNet;
+
+use crate::{
+    app::AppState, configuration::app_config::ForwardedHeader, features::shared::ClientIp,
+};
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// Resolves the originating client address and stores it as [`ClientIp`].
+/// The configured forwarding header is only honoured when the peer is a
+/// trusted proxy.
+pub async fn client_ip(State(state): State<AppState>, mut req: Request, next: Next) -> Response {
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+
+    if let Some(peer) = peer {
+        let ip = resolve_client_ip(
+            peer,
+            req.headers(),
+            &state.config.trusted_proxies,
+            state.config.forwarded_header,
+        );
+        req.extensions_mut().insert(ClientIp(ip));
+    }
+
+    next.run(req).await
+}
+
+/// Walks the forwarding chain from the nearest hop outwards and returns the
+/// first address that is not a trusted proxy. Only `source` is read; the
+/// other forwarding header is whatever the client sent and is ignored.
+pub fn resolve_client_ip(
+    peer: IpAddr,
+    headers: &HeaderMap,
+    trusted: &[IpNet],
+    source: ForwardedHeader,
+) -> IpAddr {
+    let is_trusted = |ip: &IpAddr| trusted.iter().any(|net| net.contains(ip));
+
+    if !is_trusted(&peer) {
+        return peer;
+    }
+
+    let chain = match source {
+        ForwardedHeader::Forwarded => forwarded_chain(headers),
+        ForwardedHeader::XForwardedFor => x_forwarded_for_chain(headers),
+    };
+
+    let mut client = peer;
+
+    for hop in chain.iter().rev() {
+        // An unparsable hop ends the part of the chain we can vouch for.
+        let Some(ip) = hop else {
+            break;
+        };
+
+        client = *ip;
+
+        if !is_trusted(ip) {
+            break;
+        }
+    }
+
+    client
+}
+
+fn forwarded_chain(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    headers
+        .get_all(header::FORWARDED)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|element| {
+            element
+                .split(';')
+                .filter_map(|pair| pair.trim().split_once('='))
+                .find(|(name, _)| name.eq_ignore_ascii_case("for"))
+                .and_then(|(_, value)| parse_node(value))
+        })
+        .collect()
+}
+
+fn x_forwarded_for_chain(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    headers
+        .get_all(X_FORWARDED_FOR)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(parse_node)
+        .collect()
+}
+
+/// Parses `192.0.2.1`, `192.0.2.1:8080`, `2001:db8::1` and `"[2001:db8::1]:8080"`.
+fn parse_node(value: &str) -> Option<IpAddr> {
+    let value = value.trim().trim_matches('"');
+
+    if let Some(rest) = value.strip_prefix('[') {
+        return rest.split_once(']')?.0.parse().ok();
+    }
+
+    value
+        .parse()
+        .ok()
+        .or_else(|| value.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn trusted() -> Vec<IpNet> {
+        vec!["10.0.0.0/8".parse().unwrap()]
+    }
+
+    fn headers(name: &'static str, value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_static(value));
+        headers
+    }
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn untrusted_peer_cannot_spoof_forwarded_for() {
+        let headers = headers(X_FORWARDED_FOR, "203.0.113.7");
+
+        let result = resolve_client_ip(
+            ip("198.51.100.1"),
+            &headers,
+            &trusted(),
+            ForwardedHeader::XForwardedFor,
+        );
+        assert_eq!(result, ip("198.51.100.1"));
+    }
+
+    #[test]
+    fn trusted_peer_uses_nearest_untrusted_hop() {
+        let headers = headers(X_FORWARDED_FOR, "192.0.2.1, 203.0.113.7, 10.0.0.2");
+
+        let result = resolve_client_ip(
+            ip("10.0.0.1"),
+            &headers,
+            &trusted(),
+            ForwardedHeader::XForwardedFor,
+        );
+        assert_eq!(result, ip("203.0.113.7"));
+    }
+
+    #[test]
+    fn all_trusted_chain_returns_leftmost_hop() {
+        let headers = headers(X_FORWARDED_FOR, "10.0.0.3, 10.0.0.2");
+
+        let result = resolve_client_ip(
+            ip("10.0.0.1"),
+            &headers,
+            &trusted(),
+            ForwardedHeader::XForwardedFor,
+        );
+        assert_eq!(result, ip("10.0.0.3"));
+    }
+
+    #[test]
+    fn unparsable_hop_stops_the_walk() {
+        let headers = headers(X_FORWARDED_FOR, "203.0.113.7, garbage, 10.0.0.2");
+
+        let result = resolve_client_ip(
+            ip("10.0.0.1"),
+            &headers,
+            &trusted(),
+            ForwardedHeader::XForwardedFor,
+        );
+        assert_eq!(result, ip("10.0.0.2"));
+    }
+
+    #[test]
+    fn forwarded_header_is_read_when_configured() {
+        let mut headers = headers(
+            "forwarded",
+            r#"for=192.0.2.60;proto=https, for="[2001:db8::1]:4711""#,
+        );
+        headers.insert(X_FORWARDED_FOR, HeaderValue::from_static("203.0.113.7"));
+
+        let result = resolve_client_ip(
+            ip("10.0.0.1"),
+            &headers,
+            &trusted(),
+            ForwardedHeader::Forwarded,
+        );
+        assert_eq!(result, ip("2001:db8::1"));
+    }
+
+    #[test]
+    fn client_sent_forwarded_is_ignored_behind_an_x_forwarded_for_proxy() {
+        // The client claims to be 192.0.2.99; the proxy passes that through
+        // and appends the real address to X-Forwarded-For.
+        let mut headers = headers("forwarded", "for=192.0.2.99");
+        headers.insert(X_FORWARDED_FOR, HeaderValue::from_static("203.0.113.7"));
+
+        let result = resolve_client_ip(
+            ip("10.0.0.1"),
+            &headers,
+            &trusted(),
+            ForwardedHeader::XForwardedFor,
+        );
+        assert_eq!(result, ip("203.0.113.7"));
+    }
+
+    #[test]
+    fn parse_node_accepts_ports_and_brackets() {
+        assert_eq!(parse_node("192.0.2.1:8080"), Some(ip("192.0.2.1")));
+        assert_eq!(parse_node("2001:db8::1"), Some(ip("2001:db8::1")));
+        assert_eq!(parse_node(r#""[2001:db8::1]:80""#), Some(ip("2001:db8::1")));
+        assert_eq!(parse_node("unknown"), None);
+    }
+
+    #[test]
+    fn missing_headers_fall_back_to_peer() {
+        let result = resolve_client_ip(
+            ip("10.0.0.1"),
+            &HeaderMap::new(),
+            &trusted(),
+            ForwardedHeader::XForwardedFor,
+        );
+        assert_eq!(result, ip("10.0.0.1"));
+    }
+}
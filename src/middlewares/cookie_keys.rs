@@ -0,0 +1,192 @@
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+use cookie::{Cookie, CookieJar, Key};
+
+use crate::{app::AppState, features::auth::generate_csrf_cookie};
+
+/// Names of the request cookies that were signed with a previous key and
+/// have been re-signed with the current one.
+#[derive(Debug, Clone, Default)]
+pub struct RotatedCookies(Vec<String>);
+
+impl RotatedCookies {
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|rotated| rotated == name)
+    }
+}
+
+/// Lets cookies signed with a previous `cookie_secret` through by re-signing
+/// them with the current key before any `SignedCookieJar` reads them. The
+/// CSRF cookie is reissued here; [`identify`](super::identify) reissues the
+/// session cookie since only it knows the expiry, and OAuth state cookies
+/// are short-lived enough to simply age out.
+pub async fn rotate_cookie_keys(
+    State(state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    if state.previous_keys.is_empty() {
+        return next.run(req).await;
+    }
+
+    let (jar, rotated) = resign(req.headers(), &state.key, &state.previous_keys);
+
+    if rotated.0.is_empty() {
+        return next.run(req).await;
+    }
+
+    req.headers_mut().remove(header::COOKIE);
+    if let Ok(value) = HeaderValue::from_str(&cookie_header(&jar)) {
+        req.headers_mut().insert(header::COOKIE, value);
+    }
+    req.extensions_mut().insert(rotated.clone());
+
+    let mut resp = next.run(req).await;
+
+    let csrf_name = &state.config.csrf_cookie_name;
+    if rotated.contains(csrf_name)
+        && !sets_cookie(resp.headers(), csrf_name)
+        && let Some(token) = jar.signed(&state.key).get(csrf_name)
+    {
+        let mut reissued = CookieJar::new();
+        reissued.signed_mut(&state.key).add(generate_csrf_cookie(
+            token.value().to_owned(),
+            &state.config,
+        ));
+
+        for cookie in reissued.delta() {
+            if let Ok(value) = HeaderValue::from_str(&cookie.encoded().to_string()) {
+                resp.headers_mut().append(header::SET_COOKIE, value);
+            }
+        }
+    }
+
+    resp
+}
+
+/// Re-signs with `current` every cookie that only verifies with one of `previous`.
+fn resign(headers: &HeaderMap, current: &Key, previous: &[Key]) -> (CookieJar, RotatedCookies) {
+    let mut jar = CookieJar::new();
+
+    for value in headers.get_all(header::COOKIE) {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+
+        for cookie in Cookie::split_parse_encoded(value.to_owned()).flatten() {
+            jar.add_original(cookie);
+        }
+    }
+
+    let names: Vec<String> = jar.iter().map(|c| c.name().to_owned()).collect();
+    let mut rotated = Vec::new();
+
+    for name in names {
+        if jar.signed(current).get(&name).is_some() {
+            continue;
+        }
+
+        if let Some(cookie) = previous.iter().find_map(|key| jar.signed(key).get(&name)) {
+            jar.signed_mut(current).add(cookie);
+            rotated.push(name);
+        }
+    }
+
+    (jar, RotatedCookies(rotated))
+}
+
+fn cookie_header(jar: &CookieJar) -> String {
+    jar.iter()
+        .map(|cookie| cookie.stripped().encoded().to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Whether the handler already set (or removed) the cookie itself.
+pub(crate) fn sets_cookie(headers: &HeaderMap, name: &str) -> bool {
+    headers
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .any(|v| v.split_once('=').is_some_and(|(n, _)| n.trim() == name))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key(byte: u8) -> Key {
+        Key::from(&[byte; 64][..])
+    }
+
+    fn signed(name: &str, value: &str, key: &Key) -> String {
+        let mut jar = CookieJar::new();
+        jar.signed_mut(key)
+            .add(Cookie::new(name.to_owned(), value.to_owned()));
+
+        jar.get(name).unwrap().stripped().encoded().to_string()
+    }
+
+    fn headers(cookies: &[String]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_str(&cookies.join("; ")).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn cookie_signed_with_previous_key_is_resigned() {
+        let (current, old) = (key(1), key(2));
+        let headers = headers(&[signed("session", "abc", &old)]);
+
+        let (jar, rotated) = resign(&headers, &current, &[old]);
+
+        assert!(rotated.contains("session"));
+        assert_eq!(jar.signed(&current).get("session").unwrap().value(), "abc");
+    }
+
+    #[test]
+    fn cookie_signed_with_current_key_is_untouched() {
+        let current = key(1);
+        let original = signed("session", "abc", &current);
+
+        let (jar, rotated) = resign(
+            &headers(std::slice::from_ref(&original)),
+            &current,
+            &[key(2)],
+        );
+
+        assert!(rotated.0.is_empty());
+        assert_eq!(cookie_header(&jar), original);
+    }
+
+    #[test]
+    fn cookie_signed_with_unknown_key_is_not_accepted() {
+        let current = key(1);
+        let headers = headers(&[signed("session", "abc", &key(3)), "theme=dark".into()]);
+
+        let (jar, rotated) = resign(&headers, &current, &[key(2)]);
+
+        assert!(rotated.0.is_empty());
+        assert!(jar.signed(&current).get("session").is_none());
+        assert_eq!(jar.get("theme").unwrap().value(), "dark");
+    }
+
+    #[test]
+    fn reissue_is_skipped_when_the_handler_sets_the_cookie() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            header::SET_COOKIE,
+            HeaderValue::from_static("csrf=abc; Path=/"),
+        );
+
+        assert!(sets_cookie(&headers, "csrf"));
+        assert!(!sets_cookie(&headers, "session"));
+    }
+}
@@ -0,0 +1,76 @@
+use axum::{
+    extract::{Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+use axum_extra::extract::SignedCookieJar;
+
+use crate::{Error, Result, app::AppState, middlewares::has_header_credential};
+
+pub const CSRF_HEADER_NAME: &str = "x-csrf-token";
+
+/// Double-submit CSRF check for cookie-authenticated, state-changing requests.
+/// The token is issued in a signed cookie and must be echoed back in the
+/// `x-csrf-token` header. Requests that authenticate with a `Bearer` or
+/// `ApiKey` header are not vulnerable to CSRF and are skipped, as are the
+/// configured `csrf_exempt_paths`.
+pub async fn csrf_protection(
+    State(state): State<AppState>,
+    jar: SignedCookieJar,
+    req: Request,
+    next: Next,
+) -> Result<Response> {
+    let is_safe_method = matches!(
+        *req.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+    );
+    let is_cookie_authenticated = !has_header_credential(req.headers())
+        && jar.get(&state.config.session_cookie_name).is_some();
+    let is_exempt = state
+        .config
+        .csrf_exempt_paths
+        .iter()
+        .any(|path| path == req.uri().path());
+
+    if is_safe_method || !is_cookie_authenticated || is_exempt {
+        return Ok(next.run(req).await);
+    }
+
+    let cookie_token = jar
+        .get(&state.config.csrf_cookie_name)
+        .ok_or(Error::Forbidden)?;
+
+    let header_token = req
+        .headers()
+        .get(CSRF_HEADER_NAME)
+        .and_then(|v| v.to_str().ok())
+        .ok_or(Error::Forbidden)?;
+
+    if !constant_time_eq(cookie_token.value().as_bytes(), header_token.as_bytes()) {
+        return Err(Error::Forbidden);
+    }
+
+    Ok(next.run(req).await)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equal_tokens_match() {
+        assert!(constant_time_eq(b"token", b"token"));
+    }
+
+    #[test]
+    fn test_different_tokens_do_not_match() {
+        assert!(!constant_time_eq(b"token", b"tokem"));
+        assert!(!constant_time_eq(b"token", b"token2"));
+        assert!(!constant_time_eq(b"", b"token"));
+    }
+}
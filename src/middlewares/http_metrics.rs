@@ -0,0 +1,26 @@
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use tokio::time::Instant;
+
+use crate::features::metrics::record_http_request;
+
+/// Records request count and latency per matched route. Installed as a route
+/// layer so the label is the route template rather than the raw path.
+pub async fn http_metrics(req: Request, next: Next) -> Response {
+    let start = Instant::now();
+    let method = req.method().clone();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_owned())
+        .unwrap_or_else(|| "unmatched".into());
+
+    let response = next.run(req).await;
+
+    record_http_request(&method, &route, response.status().as_u16(), start.elapsed());
+
+    response
+}
@@ -0,0 +1,271 @@
+use std::time::Duration;
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::{Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::Response,
+};
+use redis::{AsyncCommands, ExistenceCheck, SetExpiry, SetOptions, aio::MultiplexedConnection};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use crate::{
+    Error, Result,
+    app::AppState,
+    common::hash_token,
+    features::shared::{AppUser, ClientIp},
+};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+pub const REDIS_IDEMPOTENCY_PREFIX: &str = "idempotency:";
+
+const MAX_KEY_LENGTH: usize = 255;
+
+/// Idempotent routes take small JSON bodies; larger ones are rejected
+/// rather than buffered.
+const MAX_BUFFERED_BODY_BYTES: usize = 64 * 1024;
+
+/// How long a key stays locked while its first request runs. Well above the
+/// request timeout, so a crashed request doesn't block retries for long.
+const IN_PROGRESS_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Record {
+    fingerprint: String,
+    response: Option<StoredResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+/// Honors an `Idempotency-Key` header: the first response to a key is stored
+/// for `idempotency_ttl_minutes` and replayed to retries with the same body.
+/// Keys are scoped to the caller (the user, or the client IP when anonymous)
+/// and the path. Failed requests are not stored, so a retry runs again.
+/// Cookies are never replayed. Fails open when Redis errors.
+pub async fn idempotency(
+    State(state): State<AppState>,
+    client_ip: ClientIp,
+    req: Request,
+    next: Next,
+) -> Result<Response> {
+    let Some(key) = req.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(next.run(req).await);
+    };
+    let key = parse_key(key)?;
+
+    let scope = req
+        .extensions()
+        .get::<Option<AppUser>>()
+        .and_then(|user| user.as_ref())
+        .map(|user| format!("user:{}", user.id))
+        .unwrap_or_else(|| format!("ip:{client_ip}"));
+    let redis_key = format!(
+        "{}{}:{}:{}",
+        REDIS_IDEMPOTENCY_PREFIX,
+        scope,
+        req.uri().path(),
+        hash_token(key)
+    );
+
+    let (parts, body) = req.into_parts();
+    let bytes = to_bytes(body, MAX_BUFFERED_BODY_BYTES)
+        .await
+        .map_err(|_| Error::PayloadTooLarge)?;
+    let fingerprint = hex::encode(Sha256::digest(&bytes));
+    let req = Request::from_parts(parts, Body::from(bytes));
+
+    let mut redis = state.redis.clone();
+
+    match claim(&mut redis, &redis_key, &fingerprint).await {
+        Ok(None) => {}
+        Ok(Some(record)) => return replay(record, &fingerprint),
+        Err(err) => {
+            warn!(?err, "Idempotency store unavailable, failing open");
+            return Ok(next.run(req).await);
+        }
+    }
+
+    let resp = next.run(req).await;
+
+    if !(resp.status().is_success() || resp.status().is_redirection()) {
+        if let Err(err) = redis.del::<_, ()>(&redis_key).await {
+            warn!(?err, "Failed to release idempotency key");
+        }
+        return Ok(resp);
+    }
+
+    let ttl = Duration::from_secs(state.settings.current().idempotency_ttl_minutes * 60);
+    let (parts, body) = resp.into_parts();
+    let bytes = to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| Error::Internal(e.to_string()))?;
+
+    let record = Record {
+        fingerprint,
+        response: Some(StoredResponse {
+            status: parts.status.as_u16(),
+            headers: stored_headers(&parts.headers),
+            body: hex::encode(&bytes),
+        }),
+    };
+    let stored: redis::RedisResult<()> = redis
+        .set_ex(&redis_key, serde_json::to_string(&record)?, ttl.as_secs())
+        .await;
+    if let Err(err) = stored {
+        warn!(?err, "Failed to store idempotent response");
+    }
+
+    Ok(Response::from_parts(parts, Body::from(bytes)))
+}
+
+fn parse_key(value: &HeaderValue) -> Result<&str> {
+    value
+        .to_str()
+        .ok()
+        .filter(|key| (1..=MAX_KEY_LENGTH).contains(&key.len()))
+        .filter(|key| key.bytes().all(|b| b.is_ascii_graphic()))
+        .ok_or(Error::InvalidIdempotencyKey)
+}
+
+/// Locks the key for this request, or returns the record already holding it.
+async fn claim(
+    redis: &mut MultiplexedConnection,
+    key: &str,
+    fingerprint: &str,
+) -> redis::RedisResult<Option<Record>> {
+    let pending = Record {
+        fingerprint: fingerprint.to_owned(),
+        response: None,
+    };
+    let options = SetOptions::default()
+        .conditional_set(ExistenceCheck::NX)
+        .with_expiration(SetExpiry::EX(IN_PROGRESS_TTL.as_secs()));
+
+    let claimed: Option<String> = redis
+        .set_options(
+            key,
+            serde_json::to_string(&pending).unwrap_or_default(),
+            options,
+        )
+        .await?;
+    if claimed.is_some() {
+        return Ok(None);
+    }
+
+    let existing: Option<String> = redis.get(key).await?;
+
+    // The holder expired between the two calls; treat it as in progress
+    // rather than racing another retry for the key.
+    Ok(Some(
+        existing
+            .and_then(|value| serde_json::from_str(&value).ok())
+            .unwrap_or(pending),
+    ))
+}
+
+fn replay(record: Record, fingerprint: &str) -> Result<Response> {
+    if record.fingerprint != fingerprint {
+        return Err(Error::IdempotencyKeyReused);
+    }
+
+    let Some(stored) = record.response else {
+        return Err(Error::IdempotentRequestInProgress);
+    };
+
+    let body = hex::decode(&stored.body).map_err(|e| Error::Internal(e.to_string()))?;
+    let mut resp = Response::new(Body::from(body));
+    *resp.status_mut() =
+        StatusCode::from_u16(stored.status).map_err(|e| Error::Internal(e.to_string()))?;
+
+    for (name, value) in stored.headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            resp.headers_mut().append(name, value);
+        }
+    }
+    resp.headers_mut()
+        .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+
+    Ok(resp)
+}
+
+/// Headers worth replaying: cookies belong to the original caller and the
+/// body length is recomputed.
+fn stored_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter(|(name, _)| ![header::SET_COOKIE, header::CONTENT_LENGTH].contains(name))
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn record(fingerprint: &str, response: Option<StoredResponse>) -> Record {
+        Record {
+            fingerprint: fingerprint.into(),
+            response,
+        }
+    }
+
+    #[test]
+    fn keys_must_be_short_visible_ascii() {
+        assert!(parse_key(&HeaderValue::from_static("3f1c-retry_1")).is_ok());
+        assert!(parse_key(&HeaderValue::from_static("")).is_err());
+        assert!(parse_key(&HeaderValue::from_static("has space")).is_err());
+        assert!(parse_key(&HeaderValue::from_str(&"k".repeat(256)).unwrap()).is_err());
+    }
+
+    #[test]
+    fn completed_request_is_replayed() {
+        let stored = StoredResponse {
+            status: 201,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: hex::encode(b"{}"),
+        };
+
+        let resp = replay(record("abc", Some(stored)), "abc").unwrap();
+
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        assert_eq!(resp.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(resp.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+    }
+
+    #[test]
+    fn different_body_is_rejected() {
+        assert!(matches!(
+            replay(record("abc", None), "def"),
+            Err(Error::IdempotencyKeyReused)
+        ));
+    }
+
+    #[test]
+    fn pending_request_is_rejected() {
+        assert!(matches!(
+            replay(record("abc", None), "abc"),
+            Err(Error::IdempotentRequestInProgress)
+        ));
+    }
+
+    #[test]
+    fn cookies_are_not_stored() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::SET_COOKIE, HeaderValue::from_static("session=abc"));
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+
+        assert_eq!(
+            stored_headers(&headers),
+            vec![("content-type".to_owned(), "text/plain".to_owned())]
+        );
+    }
+}
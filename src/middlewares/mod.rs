@@ -1,7 +1,21 @@
 pub mod authenticate;
+pub mod client_ip;
+pub mod cookie_keys;
+pub mod csrf;
 pub mod error_logging;
+pub mod http_metrics;
+pub mod idempotency;
+pub mod problem_details;
+pub mod rate_limit;
 pub mod request_logging;
 
 pub use authenticate::*;
+pub use client_ip::*;
+pub use cookie_keys::*;
+pub use csrf::*;
 pub use error_logging::*;
+pub use http_metrics::*;
+pub use idempotency::*;
+pub use problem_details::*;
+pub use rate_limit::*;
 pub use request_logging::*;
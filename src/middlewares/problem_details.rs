@@ -0,0 +1,151 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use uuid::Uuid;
+
+use crate::{
+    Problem, app::AppState, configuration::app_config::ErrorFormat, error::PROBLEM_JSON,
+    i18n::Locale,
+};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Finishes every error rendered from an [`Error`](crate::Error): stamps the
+/// request ID into the problem, translates it for `Accept-Language` and, in
+/// `legacy` mode, swaps it for the English v1 body. Sits outermost so
+/// responses from other middlewares are covered, and assigns the request ID
+/// that `request_logging` then picks up.
+pub async fn problem_details(
+    State(state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let request_id = match req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        Some(id) => id.to_owned(),
+        None => {
+            let id = Uuid::new_v4().to_string();
+            req.headers_mut()
+                .insert(REQUEST_ID_HEADER, HeaderValue::from_str(&id).unwrap());
+            id
+        }
+    };
+
+    let legacy =
+        state.config.error_format == ErrorFormat::Legacy && !accepts_problem(req.headers());
+    let locale = Locale::negotiate(
+        req.headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|v| v.to_str().ok()),
+    );
+
+    let mut resp = next.run(req).await;
+
+    if !resp.headers().contains_key(REQUEST_ID_HEADER)
+        && let Ok(value) = HeaderValue::from_str(&request_id)
+    {
+        resp.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    let Some(mut problem) = resp.extensions_mut().remove::<Problem>() else {
+        return resp;
+    };
+    problem.request_id = Some(request_id);
+
+    let rendered = if legacy {
+        problem.into_legacy_response()
+    } else {
+        problem.localize(locale);
+        let mut rendered = problem.into_response();
+        rendered.headers_mut().insert(
+            header::CONTENT_LANGUAGE,
+            HeaderValue::from_static(locale.tag()),
+        );
+        rendered
+    };
+
+    // The body is replaced wholesale, so anything describing the old one goes.
+    let (mut parts, _) = resp.into_parts();
+    let (rendered, body) = rendered.into_parts();
+    parts.status = rendered.status;
+    for name in [header::CONTENT_ENCODING, header::CONTENT_LENGTH] {
+        parts.headers.remove(name);
+    }
+    parts.headers.extend(rendered.headers);
+
+    Response::from_parts(parts, Body::new(body))
+}
+
+fn accepts_problem(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|media| media.split(';').next().unwrap_or_default().trim() == PROBLEM_JSON)
+}
+
+#[cfg(test)]
+mod test {
+    use axum::body::to_bytes;
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{Error, i18n::Message};
+
+    async fn body(resp: Response) -> Value {
+        serde_json::from_slice(&to_bytes(resp.into_body(), usize::MAX).await.unwrap()).unwrap()
+    }
+
+    #[test]
+    fn problem_accept_header_is_detected() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::ACCEPT,
+            HeaderValue::from_static("application/json, application/problem+json;q=0.9"),
+        );
+
+        assert!(accepts_problem(&headers));
+        assert!(!accepts_problem(&HeaderMap::new()));
+    }
+
+    #[tokio::test]
+    async fn legacy_shape_keeps_v1_bodies_and_statuses() {
+        let resp = Error::Conflict("Record already exists".into())
+            .problem()
+            .into_legacy_response();
+        assert_eq!(resp.status(), 400);
+        assert_eq!(
+            body(resp).await,
+            json!({ "error": "Record already exists" })
+        );
+
+        let resp =
+            Error::ValidationErrors([("email".into(), vec![Message::new("email.invalid")])].into())
+                .problem()
+                .into_legacy_response();
+        assert_eq!(resp.status(), 400);
+        assert_eq!(
+            body(resp).await,
+            json!({ "errors": { "email": ["Invalid email address"] } })
+        );
+
+        let resp = Error::DomainValidationError(vec![
+            Message::new("value.empty"),
+            Message::new("value.too_short").with("min", 8),
+        ])
+        .problem()
+        .into_legacy_response();
+        assert_eq!(
+            body(resp).await,
+            json!({ "error": "Value cannot be empty.\nValue must be at least 8 characters." })
+        );
+    }
+}
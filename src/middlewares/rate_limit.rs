@@ -0,0 +1,391 @@
+use std::{
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::{ConnectInfo, Request},
+    http::{HeaderMap, HeaderValue, header},
+    response::{IntoResponse, Response},
+};
+use redis::{Script, aio::MultiplexedConnection};
+use tower::{Layer, Service};
+use tracing::warn;
+
+use crate::{
+    Error, Result,
+    common::hash_token,
+    configuration::{
+        ratelimit_config::{RateLimitConfig, RateLimitKey, RateLimitRule},
+        reload::SettingsHandle,
+    },
+    features::shared::{AppUser, ClientIp},
+};
+
+pub const REDIS_RATE_LIMIT_PREFIX: &str = "ratelimit:";
+
+/// Bodies of email-keyed routes are buffered to read the email; anything
+/// larger than this is not a credential form and is rejected.
+const MAX_BUFFERED_BODY_BYTES: usize = 16 * 1024;
+
+/// Sliding-window counter: the previous fixed window is weighted by how much
+/// of it still overlaps the sliding window. Only admitted requests are counted.
+const SLIDING_WINDOW_SCRIPT: &str = r#"
+local current = tonumber(redis.call('GET', KEYS[1]) or '0')
+local previous = tonumber(redis.call('GET', KEYS[2]) or '0')
+local limit = tonumber(ARGV[1])
+local weight = tonumber(ARGV[2])
+
+if math.floor(previous * weight) + current >= limit then
+    return {0, current, previous}
+end
+
+current = redis.call('INCR', KEYS[1])
+if current == 1 then
+    redis.call('PEXPIRE', KEYS[1], ARGV[3])
+end
+
+return {1, current, previous}
+"#;
+
+/// Redis-backed sliding-window rate limit for a single route, shared by
+/// every replica talking to the same Redis. Fails open when Redis errors.
+/// The rule is looked up per request so reloaded limits apply immediately.
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    redis: MultiplexedConnection,
+    settings: SettingsHandle,
+    name: &'static str,
+    rule: fn(&RateLimitConfig) -> &RateLimitRule,
+}
+
+impl RateLimitLayer {
+    pub fn new(
+        redis: MultiplexedConnection,
+        settings: SettingsHandle,
+        name: &'static str,
+        rule: fn(&RateLimitConfig) -> &RateLimitRule,
+    ) -> Self {
+        Self {
+            redis,
+            settings,
+            name,
+            rule,
+        }
+    }
+
+    fn rule(&self) -> RateLimitRule {
+        (self.rule)(&self.settings.current().ratelimit).clone()
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    layer: RateLimitLayer,
+}
+
+impl<S> Service<Request> for RateLimitService<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = std::result::Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        // Take the service that was polled ready and leave a fresh clone behind.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+        let rule = layer.rule();
+
+        Box::pin(async move {
+            let (req, key) = match rate_limit_key(req, rule.key).await {
+                Ok(result) => result,
+                Err(response) => return Ok(response),
+            };
+
+            let decision = match check(&layer, &rule, &key).await {
+                Ok(decision) => decision,
+                Err(err) => {
+                    warn!(
+                        ?err,
+                        route = layer.name,
+                        "Rate limiter unavailable, failing open"
+                    );
+                    return inner.call(req).await;
+                }
+            };
+
+            if !decision.allowed {
+                let mut response = Error::TooManyRequests.into_response();
+                decision.write_headers(response.headers_mut());
+                return Ok(response);
+            }
+
+            let mut response = inner.call(req).await?;
+            decision.write_headers(response.headers_mut());
+
+            Ok(response)
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Decision {
+    allowed: bool,
+    limit: u32,
+    remaining: u32,
+    reset: Duration,
+}
+
+impl Decision {
+    fn write_headers(&self, headers: &mut HeaderMap) {
+        let reset = ceil_secs(self.reset);
+
+        headers.insert("ratelimit-limit", HeaderValue::from(self.limit));
+        headers.insert("ratelimit-remaining", HeaderValue::from(self.remaining));
+        headers.insert("ratelimit-reset", HeaderValue::from(reset));
+
+        if !self.allowed {
+            headers.insert(header::RETRY_AFTER, HeaderValue::from(reset));
+        }
+    }
+}
+
+async fn check(layer: &RateLimitLayer, rule: &RateLimitRule, key: &str) -> Result<Decision> {
+    let window = Duration::from_secs(rule.window_seconds);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let (index, elapsed) = window_position(now, window);
+    let weight = previous_weight(elapsed, window);
+
+    let base = format!("{}{}:{}", REDIS_RATE_LIMIT_PREFIX, layer.name, key);
+    let mut redis = layer.redis.clone();
+
+    let (allowed, current, previous): (u8, u64, u64) = Script::new(SLIDING_WINDOW_SCRIPT)
+        .key(format!("{base}:{index}"))
+        .key(format!("{base}:{}", index.saturating_sub(1)))
+        .arg(rule.requests)
+        .arg(weight)
+        .arg((window * 2).as_millis() as u64)
+        .invoke_async(&mut redis)
+        .await?;
+
+    Ok(decide(
+        allowed == 1,
+        rule.requests,
+        current,
+        previous,
+        elapsed,
+        window,
+    ))
+}
+
+fn window_position(now: Duration, window: Duration) -> (u64, Duration) {
+    let window_ms = window.as_millis().max(1);
+    let now_ms = now.as_millis();
+
+    (
+        (now_ms / window_ms) as u64,
+        Duration::from_millis((now_ms % window_ms) as u64),
+    )
+}
+
+fn previous_weight(elapsed: Duration, window: Duration) -> f64 {
+    1.0 - elapsed.as_secs_f64() / window.as_secs_f64()
+}
+
+fn decide(
+    allowed: bool,
+    limit: u32,
+    current: u64,
+    previous: u64,
+    elapsed: Duration,
+    window: Duration,
+) -> Decision {
+    let weight = previous_weight(elapsed, window);
+    let used = (previous as f64 * weight).floor() as u64 + current;
+
+    Decision {
+        allowed,
+        limit,
+        remaining: (limit as u64).saturating_sub(used) as u32,
+        reset: if allowed {
+            window.saturating_sub(elapsed)
+        } else {
+            retry_after(limit, current, previous, elapsed, window)
+        },
+    }
+}
+
+/// Time until the weighted count drops below `limit` again.
+fn retry_after(
+    limit: u32,
+    current: u64,
+    previous: u64,
+    elapsed: Duration,
+    window: Duration,
+) -> Duration {
+    let limit = limit as f64;
+    let window_secs = window.as_secs_f64();
+
+    if (current as f64) < limit {
+        // Wait for enough of the previous window to slide out.
+        let free = (limit - current as f64) / previous.max(1) as f64;
+        let wait = window_secs * (1.0 - free) - elapsed.as_secs_f64();
+
+        return Duration::from_secs_f64(wait.max(0.0));
+    }
+
+    // The current window alone is full: it has to roll over and then slide out.
+    let slide = window_secs * (1.0 - limit / current as f64);
+    window.saturating_sub(elapsed) + Duration::from_secs_f64(slide.max(0.0))
+}
+
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_millis().div_ceil(1000).max(1) as u64
+}
+
+async fn rate_limit_key(
+    req: Request,
+    key: RateLimitKey,
+) -> std::result::Result<(Request, String), Response> {
+    match key {
+        RateLimitKey::Ip => {
+            let ip = client_ip(&req);
+            Ok((req, format!("ip:{ip}")))
+        }
+        RateLimitKey::User => {
+            let user = req
+                .extensions()
+                .get::<Option<AppUser>>()
+                .and_then(|user| user.as_ref())
+                .map(|user| format!("user:{}", user.id));
+            let key = user.unwrap_or_else(|| format!("ip:{}", client_ip(&req)));
+
+            Ok((req, key))
+        }
+        RateLimitKey::Email => {
+            let (parts, body) = req.into_parts();
+            let bytes = to_bytes(body, MAX_BUFFERED_BODY_BYTES)
+                .await
+                .map_err(|_| payload_too_large())?;
+
+            let email = serde_json::from_slice::<serde_json::Value>(&bytes)
+                .ok()
+                .and_then(|body| body.get("email")?.as_str().map(normalize_email))
+                .filter(|email| !email.is_empty());
+
+            let req = Request::from_parts(parts, Body::from(bytes));
+            let key = match email {
+                Some(email) => format!("email:{}", hash_token(&email)),
+                None => format!("ip:{}", client_ip(&req)),
+            };
+
+            Ok((req, key))
+        }
+    }
+}
+
+fn client_ip(req: &Request) -> String {
+    req.extensions()
+        .get::<ClientIp>()
+        .map(|ip| ip.to_string())
+        .or_else(|| {
+            req.extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip().to_string())
+        })
+        .unwrap_or_else(|| "unknown".into())
+}
+
+fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+fn payload_too_large() -> Response {
+    Error::PayloadTooLarge.into_response()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn window_position_splits_index_and_offset() {
+        let (index, elapsed) = window_position(Duration::from_secs(125), MINUTE);
+
+        assert_eq!(index, 2);
+        assert_eq!(elapsed, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn previous_window_is_weighted_by_overlap() {
+        let decision = decide(true, 10, 2, 10, Duration::from_secs(30), MINUTE);
+
+        // 10 * 0.5 + 2
+        assert_eq!(decision.remaining, 3);
+        assert_eq!(decision.reset, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn retry_after_waits_for_previous_window_to_slide_out() {
+        // 10 * 0.75 + 3 = 10.5, needs previous weight below 0.7
+        let wait = retry_after(10, 3, 10, Duration::from_secs(15), MINUTE);
+
+        assert_eq!(ceil_secs(wait), 3);
+    }
+
+    #[test]
+    fn retry_after_waits_for_rollover_when_current_window_is_full() {
+        let wait = retry_after(10, 10, 0, Duration::from_secs(45), MINUTE);
+
+        assert_eq!(ceil_secs(wait), 15);
+    }
+
+    #[test]
+    fn rejected_decision_sets_retry_after() {
+        let decision = decide(false, 10, 10, 0, Duration::from_secs(45), MINUTE);
+        let mut headers = HeaderMap::new();
+        decision.write_headers(&mut headers);
+
+        assert_eq!(headers["ratelimit-remaining"], "0");
+        assert_eq!(headers[header::RETRY_AFTER], "15");
+    }
+
+    #[test]
+    fn allowed_decision_has_no_retry_after() {
+        let decision = decide(true, 10, 1, 0, Duration::ZERO, MINUTE);
+        let mut headers = HeaderMap::new();
+        decision.write_headers(&mut headers);
+
+        assert_eq!(headers["ratelimit-limit"], "10");
+        assert_eq!(headers["ratelimit-remaining"], "9");
+        assert!(!headers.contains_key(header::RETRY_AFTER));
+    }
+}
@@ -1,9 +1,24 @@
-use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response};
+use axum::{
+    extract::Request,
+    http::{HeaderMap, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use opentelemetry::{
+    Context,
+    propagation::TextMapPropagator,
+    trace::{TraceContextExt, TraceId},
+};
+use opentelemetry_http::HeaderExtractor;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
 use tokio::time::Instant;
-use tracing::{Instrument, field, info, info_span};
+use tracing::{Instrument, Span, field, info, info_span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
 use uuid::Uuid;
 
-pub async fn request_logging(req: Request, next: Next) -> Response {
+use crate::features::shared::ClientIp;
+
+pub async fn request_logging(client_ip: ClientIp, req: Request, next: Next) -> Response {
     let start = Instant::now();
 
     let method = req.method();
@@ -15,7 +30,23 @@ pub async fn request_logging(req: Request, next: Next) -> Response {
         .map(|s| s.to_owned())
         .unwrap_or(Uuid::new_v4().to_string());
 
-    let span = info_span!("request", %method, %uri, req_id = %req_id, user = field::Empty);
+    let span = info_span!(
+        "request",
+        %method,
+        %uri,
+        req_id = %req_id,
+        client_ip = %client_ip,
+        trace_id = field::Empty,
+        user = field::Empty
+    );
+
+    // Continue the caller's trace when it sent a W3C `traceparent`.
+    let parent = extract_parent(req.headers());
+    let _ = span.set_parent(parent.clone());
+
+    if let Some(trace_id) = trace_id(&span, &parent) {
+        span.record("trace_id", field::display(trace_id));
+    }
 
     async {
             let mut resp = next.run(req).await;
@@ -30,3 +61,63 @@ pub async fn request_logging(req: Request, next: Next) -> Response {
             resp
         }.instrument(span).await
 }
+
+fn extract_parent(headers: &HeaderMap) -> Context {
+    TraceContextPropagator::new().extract(&HeaderExtractor(headers))
+}
+
+/// The exported span's trace when tracing is enabled, otherwise the caller's.
+fn trace_id(span: &Span, parent: &Context) -> Option<TraceId> {
+    [span.context(), parent.clone()]
+        .iter()
+        .map(|cx| cx.span().span_context().trace_id())
+        .find(|id| *id != TraceId::INVALID)
+}
+
+#[cfg(test)]
+mod test {
+    use opentelemetry::trace::TracerProvider;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tracing_subscriber::{Registry, layer::SubscriberExt};
+
+    use super::*;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    fn headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("traceparent", HeaderValue::from_static(TRACEPARENT));
+        headers
+    }
+
+    #[test]
+    fn incoming_traceparent_is_continued() {
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = Registry::default()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let parent = extract_parent(&headers());
+            let span = info_span!("request");
+            span.set_parent(parent.clone()).unwrap();
+
+            let trace_id = trace_id(&span, &parent).unwrap();
+            assert_eq!(trace_id.to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        });
+    }
+
+    #[test]
+    fn caller_trace_is_logged_when_export_is_disabled() {
+        let parent = extract_parent(&headers());
+
+        let trace_id = trace_id(&Span::none(), &parent).unwrap();
+        assert_eq!(trace_id.to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+    }
+
+    #[test]
+    fn missing_traceparent_without_export_has_no_trace_id() {
+        let parent = extract_parent(&HeaderMap::new());
+
+        assert_eq!(trace_id(&Span::none(), &parent), None);
+    }
+}
@@ -1,46 +1,227 @@
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    Resource,
+    trace::{Sampler, SdkTracerProvider},
+};
 use tracing::Subscriber;
 use tracing::subscriber::set_global_default;
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{EnvFilter, Registry, fmt, layer::SubscriberExt};
+use tracing_subscriber::{EnvFilter, Registry, fmt, layer::SubscriberExt, reload};
+
+use crate::configuration::app_config::{ApplicationConfig, LogLevel};
+
+/// Keeps the log writer alive and flushes pending spans when dropped.
+pub struct TelemetryGuard {
+    _worker: WorkerGuard,
+    tracer_provider: Option<SdkTracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.tracer_provider.take()
+            && let Err(err) = provider.shutdown()
+        {
+            eprintln!("Failed to flush spans: {err}");
+        }
+    }
+}
+
+/// Swaps the level of the installed log filter. Does nothing when `RUST_LOG`
+/// is set, since it takes precedence over the configured level.
+#[derive(Clone)]
+pub struct LogFilter(Option<reload::Handle<EnvFilter, Registry>>);
+
+impl LogFilter {
+    pub fn disabled() -> Self {
+        Self(None)
+    }
+
+    pub fn set_level(&self, level: &LogLevel) -> Result<(), reload::Error> {
+        match &self.0 {
+            Some(handle) => handle.reload(EnvFilter::new(level.as_str())),
+            None => Ok(()),
+        }
+    }
+}
 
 pub fn create_subscriber(
-    pretty_log: bool,
-    log_level: &str,
-) -> (Box<dyn Subscriber + Sync + Send>, WorkerGuard) {
-    let (non_blocking, guard) = tracing_appender::non_blocking(std::io::stdout());
-    let env_filter = EnvFilter::try_from_default_env()
-        .unwrap_or(EnvFilter::try_new(log_level).expect("Failed to create env filter"));
-
-    match pretty_log {
+    config: &ApplicationConfig,
+) -> (Box<dyn Subscriber + Sync + Send>, TelemetryGuard, LogFilter) {
+    let (non_blocking, worker) = tracing_appender::non_blocking(std::io::stdout());
+    let from_env = EnvFilter::try_from_default_env().ok();
+    let configured = from_env.is_none();
+    let (env_filter, handle) = reload::Layer::new(from_env.unwrap_or_else(|| {
+        EnvFilter::try_new(config.log_level.as_str()).expect("Failed to create env filter")
+    }));
+    let log_filter = LogFilter(configured.then_some(handle));
+
+    let tracer_provider = config.otlp_endpoint.as_deref().map(|endpoint| {
+        build_tracer_provider(
+            endpoint,
+            &config.otlp_service_name,
+            config.trace_sample_ratio,
+        )
+    });
+    let otel_layer = tracer_provider
+        .as_ref()
+        .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer("kicksapi")));
+
+    let guard = TelemetryGuard {
+        _worker: worker,
+        tracer_provider,
+    };
+    let registry = Registry::default().with(env_filter).with(otel_layer);
+
+    match config.pretty_log {
         true => {
-            let fmt_layer = fmt::layer::<Registry>()
+            let fmt_layer = fmt::layer()
                 .with_writer(non_blocking)
                 .with_target(false)
                 .with_file(true)
                 .with_line_number(true)
                 .pretty();
 
-            (
-                Box::new(Registry::default().with(fmt_layer).with(env_filter)),
-                guard,
-            )
+            (Box::new(registry.with(fmt_layer)), guard, log_filter)
         }
         false => {
-            let fmt_layer = fmt::layer::<Registry>()
+            let fmt_layer = fmt::layer()
                 .with_writer(non_blocking)
                 .with_target(false)
                 .with_file(true)
                 .with_line_number(true)
                 .json();
 
-            (
-                Box::new(Registry::default().with(fmt_layer).with(env_filter)),
-                guard,
-            )
+            (Box::new(registry.with(fmt_layer)), guard, log_filter)
         }
     }
 }
 
+/// Batches spans to an OTLP/HTTP collector. New traces are sampled at
+/// `sample_ratio`; requests that arrive with a `traceparent` follow the caller.
+pub fn build_tracer_provider(
+    endpoint: &str,
+    service_name: &str,
+    sample_ratio: f64,
+) -> SdkTracerProvider {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()
+        .expect("Failed to build OTLP span exporter");
+
+    SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            sample_ratio,
+        ))))
+        .with_resource(
+            Resource::builder()
+                .with_service_name(service_name.to_owned())
+                .build(),
+        )
+        .build()
+}
+
 pub fn init_subscriber(subscriber: impl tracing::Subscriber + Send + Sync) {
     set_global_default(subscriber).expect("Failed to set subscriber");
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{Router, body::Bytes, extract::State, routing::post};
+    use tokio::net::TcpListener;
+    use tracing::info_span;
+
+    use super::*;
+
+    type Received = Arc<Mutex<Vec<Bytes>>>;
+
+    async fn collect(State(received): State<Received>, body: Bytes) {
+        received.lock().unwrap().push(body);
+    }
+
+    /// Minimal OTLP/HTTP collector that records every export request body.
+    async fn start_collector() -> (String, Received) {
+        let received = Received::default();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = Router::new()
+            .route("/v1/traces", post(collect))
+            .with_state(received.clone());
+
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        (format!("http://{addr}/v1/traces"), received)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn spans_are_exported_to_the_collector() {
+        let (endpoint, received) = start_collector().await;
+
+        let provider = tokio::task::spawn_blocking(move || {
+            build_tracer_provider(&endpoint, "kicksapi-test", 1.0)
+        })
+        .await
+        .unwrap();
+
+        let subscriber = Registry::default()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        tracing::subscriber::with_default(subscriber, || {
+            let _span = info_span!("collector_test_span").entered();
+        });
+
+        tokio::task::spawn_blocking(move || provider.shutdown())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert!(
+            received[0]
+                .windows(b"collector_test_span".len())
+                .any(|w| w == b"collector_test_span")
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn unsampled_traces_are_not_exported() {
+        let (endpoint, received) = start_collector().await;
+
+        let provider = tokio::task::spawn_blocking(move || {
+            build_tracer_provider(&endpoint, "kicksapi-test", 0.0)
+        })
+        .await
+        .unwrap();
+
+        let subscriber = Registry::default()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        tracing::subscriber::with_default(subscriber, || {
+            let _span = info_span!("collector_test_span").entered();
+        });
+
+        tokio::task::spawn_blocking(move || provider.shutdown())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(received.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn log_level_is_swapped_live() {
+        let (env_filter, handle) = reload::Layer::new(EnvFilter::new("info"));
+        let log_filter = LogFilter(Some(handle));
+
+        tracing::subscriber::with_default(Registry::default().with(env_filter), || {
+            assert!(!tracing::enabled!(tracing::Level::DEBUG));
+
+            log_filter.set_level(&LogLevel::Debug).unwrap();
+
+            assert!(tracing::enabled!(tracing::Level::DEBUG));
+        });
+    }
+}
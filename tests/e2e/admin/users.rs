@@ -0,0 +1,135 @@
+use kicksapi::{
+    ApiResponse,
+    features::{admin::AdminUserResponse, auth::PASSWORD_MIN_LENGTH, shared::Page},
+};
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+
+use crate::e2e::testapp::{TestApp, setup};
+
+fn user(email: &str) -> Value {
+    json!({
+        "email": email,
+        "password": "s".repeat(PASSWORD_MIN_LENGTH),
+    })
+}
+
+/// Registers `others`, then signs in as an admin.
+async fn sign_in_as_admin(app: &mut TestApp, others: &[&str]) {
+    for email in others {
+        app.create_and_verify(&user(email)).await;
+    }
+
+    let admin = user("admin@gmail.com");
+    app.create_and_verify(&admin).await;
+    app.promote_to_admin("admin@gmail.com").await;
+
+    let response = app.sign_in(&admin).await;
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+fn emails(page: &Page<AdminUserResponse>) -> Vec<&str> {
+    page.items.iter().map(|user| user.email.as_str()).collect()
+}
+
+#[tokio::test]
+async fn pages_through_users_with_cursor() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app, &["carol@gmail.com", "bob@gmail.com"]).await;
+
+        let response = app.list_users(&[("sort", "email"), ("limit", "2")]).await;
+        assert_eq!(StatusCode::OK, response.status());
+        let first = response
+            .json::<ApiResponse<Page<AdminUserResponse>>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(emails(&first), ["admin@gmail.com", "bob@gmail.com"]);
+
+        let cursor = first.next_cursor.expect("a second page");
+        let response = app
+            .list_users(&[("sort", "email"), ("limit", "2"), ("cursor", &cursor)])
+            .await;
+        assert_eq!(StatusCode::OK, response.status());
+        let second = response
+            .json::<ApiResponse<Page<AdminUserResponse>>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(emails(&second), ["carol@gmail.com"]);
+        assert!(second.next_cursor.is_none());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn filters_users() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app, &["bob@gmail.com", "bobby@gmail.com"]).await;
+        app.ban_user("bobby@gmail.com").await;
+
+        let response = app.list_users(&[("role", "admin")]).await;
+        let page = response
+            .json::<ApiResponse<Page<AdminUserResponse>>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(emails(&page), ["admin@gmail.com"]);
+
+        let response = app
+            .list_users(&[("email", "BOB"), ("is_banned", "false")])
+            .await;
+        let page = response
+            .json::<ApiResponse<Page<AdminUserResponse>>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(emails(&page), ["bob@gmail.com"]);
+    })
+    .await
+}
+
+#[tokio::test]
+async fn returns_400_when_query_is_invalid() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app, &[]).await;
+
+        let response = app
+            .list_users(&[("sort", "password"), ("limit", "1000"), ("name", "x")])
+            .await;
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        let body = response.json::<Value>().await.unwrap();
+        let mut fields: Vec<_> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["field"].as_str().unwrap().to_owned())
+            .collect();
+        fields.sort();
+        assert_eq!(fields, ["limit", "name", "sort"]);
+
+        let response = app.list_users(&[("cursor", "forged.cursor")]).await;
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn returns_403_when_user_is_not_admin() {
+    setup(async |mut app: TestApp| {
+        app.create_and_sign_in(&user("bob@gmail.com")).await;
+
+        let response = app.list_users(&[("sort", "password")]).await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn returns_401_when_user_is_not_authorized() {
+    setup(async |app: TestApp| {
+        let response = app.list_users(&[]).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    })
+    .await
+}
@@ -0,0 +1,200 @@
+use kicksapi::{
+    ApiResponse,
+    features::auth::{ApiKeyResponse, CreatedApiKeyResponse, PASSWORD_MIN_LENGTH},
+};
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::e2e::testapp::{TestApp, setup};
+
+async fn sign_in_and_create_api_key(
+    app: &mut TestApp,
+    body: serde_json::Value,
+) -> CreatedApiKeyResponse {
+    let data = json!({
+        "email": "test@gmail.com",
+        "password": "s".repeat(PASSWORD_MIN_LENGTH),
+    });
+    app.create_and_sign_in(&data).await;
+
+    let response = app.create_api_key(&body).await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    response
+        .json::<ApiResponse<CreatedApiKeyResponse>>()
+        .await
+        .expect("Failed to parse api key response")
+        .data
+}
+
+#[tokio::test]
+pub async fn returns_201_with_key_when_request_is_valid() {
+    setup(async |mut app: TestApp| {
+        let created = sign_in_and_create_api_key(
+            &mut app,
+            json!({ "name": "ci", "scopes": ["profile:read"], "expires_in_days": 30 }),
+        )
+        .await;
+
+        assert!(created.key.starts_with(&created.api_key.prefix));
+        assert!(created.api_key.expires_at.is_some());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn returns_400_when_request_is_invalid() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_sign_in(&data).await;
+
+        let test_cases = vec![
+            (json!({ "name": "" }), "empty name"),
+            (
+                json!({ "name": "ci", "scopes": ["unknown"] }),
+                "unknown scope",
+            ),
+            (json!({ "name": "ci", "expires_in_days": 0 }), "zero expiry"),
+            (
+                json!({ "name": "ci", "expires_in_days": 10000 }),
+                "expiry too long",
+            ),
+        ];
+
+        for (body, description) in test_cases {
+            let response = app.create_api_key(&body).await;
+            assert_eq!(
+                StatusCode::BAD_REQUEST,
+                response.status(),
+                "Test case failed: {}",
+                description
+            );
+        }
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn listed_keys_do_not_expose_the_secret() {
+    setup(async |mut app: TestApp| {
+        let created =
+            sign_in_and_create_api_key(&mut app, json!({ "name": "ci", "scopes": [] })).await;
+
+        let response = app.list_api_keys().await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let body = response.text().await.unwrap();
+        assert!(!body.contains(&created.key));
+
+        let keys = serde_json::from_str::<ApiResponse<Vec<ApiKeyResponse>>>(&body)
+            .unwrap()
+            .data;
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].id, created.api_key.id);
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn api_key_authenticates_requests_within_its_scopes() {
+    setup(async |mut app: TestApp| {
+        let created = sign_in_and_create_api_key(
+            &mut app,
+            json!({ "name": "ci", "scopes": ["profile:read"] }),
+        )
+        .await;
+
+        let response = app.get_me_with_api_key(&created.key).await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let keys = app
+            .list_api_keys()
+            .await
+            .json::<ApiResponse<Vec<ApiKeyResponse>>>()
+            .await
+            .unwrap()
+            .data;
+        assert!(keys[0].last_used_at.is_some());
+        assert!(keys[0].last_used_ip.is_some());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn returns_403_when_api_key_lacks_scope() {
+    setup(async |mut app: TestApp| {
+        let created =
+            sign_in_and_create_api_key(&mut app, json!({ "name": "ci", "scopes": [] })).await;
+
+        let response = app.get_me_with_api_key(&created.key).await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn returns_403_when_api_key_manages_api_keys() {
+    setup(async |mut app: TestApp| {
+        let created = sign_in_and_create_api_key(
+            &mut app,
+            json!({ "name": "ci", "scopes": ["profile:read"] }),
+        )
+        .await;
+
+        let response = app
+            .create_api_key_with_api_key(&created.key, &json!({ "name": "other" }))
+            .await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn revoked_api_key_is_rejected() {
+    setup(async |mut app: TestApp| {
+        let created = sign_in_and_create_api_key(
+            &mut app,
+            json!({ "name": "ci", "scopes": ["profile:read"] }),
+        )
+        .await;
+
+        let response = app.revoke_api_key(&created.api_key.id.to_string()).await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let response = app.get_me_with_api_key(&created.key).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn returns_404_when_revoking_unknown_key() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_sign_in(&data).await;
+
+        let response = app
+            .revoke_api_key("00000000-0000-0000-0000-000000000000")
+            .await;
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn returns_401_when_user_is_not_authorized() {
+    setup(async |app: TestApp| {
+        let response = app.list_api_keys().await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+        let response = app.get_me_with_api_key("kicks_invalid_key").await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    })
+    .await
+}
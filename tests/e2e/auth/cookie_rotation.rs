@@ -0,0 +1,90 @@
+use cookie::{Cookie, CookieJar, Key};
+use kicksapi::features::auth::PASSWORD_MIN_LENGTH;
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::e2e::testapp::{PREVIOUS_COOKIE_SECRET, TestApp, setup};
+
+/// Signs in and returns the plain session id from the session cookie.
+async fn sign_in_session_id(app: &mut TestApp) -> String {
+    let data = json!({
+        "email": "test@gmail.com",
+        "password": "s".repeat(PASSWORD_MIN_LENGTH),
+    });
+    app.create_and_verify(&data).await;
+
+    let response = app.sign_in(&data).await;
+    let name = &app.application_config.session_cookie_name;
+    let cookie = response.cookies().find(|c| c.name() == name).unwrap();
+
+    verify(name, cookie.value(), &current_key(app)).unwrap()
+}
+
+fn current_key(app: &TestApp) -> Key {
+    Key::from(app.application_config.cookie_secret.as_bytes())
+}
+
+fn sign(name: &str, value: &str, key: &Key) -> String {
+    let mut jar = CookieJar::new();
+    jar.signed_mut(key)
+        .add(Cookie::new(name.to_owned(), value.to_owned()));
+
+    jar.get(name).unwrap().value().to_owned()
+}
+
+fn verify(name: &str, signed: &str, key: &Key) -> Option<String> {
+    let mut jar = CookieJar::new();
+    jar.add_original(Cookie::new(name.to_owned(), signed.to_owned()));
+
+    jar.signed(key).get(name).map(|c| c.value().to_owned())
+}
+
+#[tokio::test]
+async fn session_signed_with_previous_key_is_accepted_and_reissued() {
+    setup(async |mut app: TestApp| {
+        let session_id = sign_in_session_id(&mut app).await;
+        let name = app.application_config.session_cookie_name.clone();
+        let old = sign(
+            &name,
+            &session_id,
+            &Key::from(PREVIOUS_COOKIE_SECRET.as_bytes()),
+        );
+
+        let response = app.get_me_with_cookie(&format!("{name}={old}")).await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let reissued = response.cookies().find(|c| c.name() == name).unwrap();
+        assert_eq!(
+            verify(&name, reissued.value(), &current_key(&app)),
+            Some(session_id)
+        );
+    })
+    .await
+}
+
+#[tokio::test]
+async fn session_signed_with_current_key_is_not_reissued() {
+    setup(async |mut app: TestApp| {
+        let session_id = sign_in_session_id(&mut app).await;
+        let name = app.application_config.session_cookie_name.clone();
+        let current = sign(&name, &session_id, &current_key(&app));
+
+        let response = app.get_me_with_cookie(&format!("{name}={current}")).await;
+        assert_eq!(StatusCode::OK, response.status());
+        assert!(response.cookies().all(|c| c.name() != name));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn session_signed_with_unknown_key_is_rejected() {
+    setup(async |mut app: TestApp| {
+        let session_id = sign_in_session_id(&mut app).await;
+        let name = app.application_config.session_cookie_name.clone();
+        let forged = sign(&name, &session_id, &Key::from(&[7; 64][..]));
+
+        let response = app.get_me_with_cookie(&format!("{name}={forged}")).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    })
+    .await
+}
@@ -0,0 +1,129 @@
+use kicksapi::features::auth::PASSWORD_MIN_LENGTH;
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::e2e::testapp::{TestApp, setup, setup_with};
+
+#[tokio::test]
+pub async fn returns_200_when_csrf_token_matches() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_sign_in(&data).await;
+
+        let csrf_token = app.csrf_token().await;
+
+        let response = app.logout_with_csrf_token(Some(&csrf_token)).await;
+        assert_eq!(StatusCode::OK, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn csrf_token_is_stable_across_requests() {
+    setup(async |app: TestApp| {
+        let first = app.csrf_token().await;
+        let second = app.csrf_token().await;
+
+        assert_eq!(first, second);
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn returns_403_when_csrf_token_is_missing() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_sign_in(&data).await;
+
+        app.csrf_token().await;
+
+        let response = app.logout_with_csrf_token(None).await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn returns_403_when_csrf_token_does_not_match() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_sign_in(&data).await;
+
+        app.csrf_token().await;
+
+        let response = app.logout_with_csrf_token(Some("forged-token")).await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn returns_403_when_csrf_cookie_was_never_issued() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_sign_in(&data).await;
+
+        let response = app.logout_with_csrf_token(Some("some-token")).await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn requests_without_session_cookie_are_not_checked() {
+    setup(async |app: TestApp| {
+        let response = app.logout_with_csrf_token(None).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn other_authorization_schemes_do_not_skip_the_check() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_sign_in(&data).await;
+
+        app.csrf_token().await;
+
+        // `identify` falls back to the session cookie for unknown schemes.
+        let response = app
+            .logout_with_headers(&[("Authorization", "Basic dXNlcjpwYXNz")])
+            .await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn exempt_paths_are_not_checked() {
+    setup_with(
+        |config| config.application.csrf_exempt_paths = vec!["/api/v1/auth/logout".into()],
+        async |mut app: TestApp| {
+            let data = json!({
+                "email": "test@gmail.com",
+                "password": "s".repeat(PASSWORD_MIN_LENGTH),
+            });
+            app.create_and_sign_in(&data).await;
+
+            let response = app.logout_with_csrf_token(None).await;
+            assert_eq!(StatusCode::OK, response.status());
+        },
+    )
+    .await
+}
@@ -65,7 +65,7 @@ async fn returns_429_when_too_many_requests() {
         let mut requests = JoinSet::new();
         let app = Arc::new(app);
 
-        for _ in 0..app.ratelimit_config.forgot_password {
+        for _ in 0..app.ratelimit_config.forgot_password.requests {
             let app = app.clone();
             requests.spawn(async move {
                 let data = json!({
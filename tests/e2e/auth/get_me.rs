@@ -61,7 +61,7 @@ async fn returns_429_when_too_many_requests() {
         let mut requests = JoinSet::new();
         let app = Arc::new(app);
 
-        for _ in 0..app.ratelimit_config.get_me {
+        for _ in 0..app.ratelimit_config.get_me.requests {
             let app = app.clone();
             requests.spawn(async move {
                 let response = app.get_me().await;
@@ -0,0 +1,94 @@
+use kicksapi::{
+    features::auth::PASSWORD_MIN_LENGTH,
+    middlewares::{IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER},
+};
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+
+use crate::e2e::testapp::{TestApp, setup};
+
+fn sign_up_data(email: &str) -> Value {
+    json!({
+        "email": email,
+        "password": "s".repeat(PASSWORD_MIN_LENGTH),
+    })
+}
+
+#[tokio::test]
+async fn retry_with_same_key_replays_the_first_response() {
+    setup(async |app: TestApp| {
+        let data = sign_up_data("test@gmail.com");
+        let headers = [(IDEMPOTENCY_KEY_HEADER, "sign-up-1")];
+
+        let first = app.sign_up_with_headers(&headers, &data).await;
+        assert_eq!(StatusCode::CREATED, first.status());
+        assert!(first.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+        let first_body = first.text().await.unwrap();
+
+        let retry = app.sign_up_with_headers(&headers, &data).await;
+        assert_eq!(StatusCode::CREATED, retry.status());
+        assert_eq!(retry.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+        assert_eq!(first_body, retry.text().await.unwrap());
+
+        // Without the key the duplicate reaches the handler again.
+        let duplicate = app.sign_up(&data).await;
+        assert_eq!(StatusCode::CONFLICT, duplicate.status());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn returns_422_when_key_is_reused_with_a_different_body() {
+    setup(async |app: TestApp| {
+        let headers = [(IDEMPOTENCY_KEY_HEADER, "sign-up-1")];
+
+        let response = app
+            .sign_up_with_headers(&headers, &sign_up_data("first@gmail.com"))
+            .await;
+        assert_eq!(StatusCode::CREATED, response.status());
+
+        let response = app
+            .sign_up_with_headers(&headers, &sign_up_data("second@gmail.com"))
+            .await;
+        assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+        assert_eq!(
+            response.json::<Value>().await.unwrap()["code"],
+            "idempotency_key_reused"
+        );
+    })
+    .await
+}
+
+#[tokio::test]
+async fn failed_requests_are_not_replayed() {
+    setup(async |app: TestApp| {
+        let headers = [(IDEMPOTENCY_KEY_HEADER, "sign-up-1")];
+        let data = sign_up_data("invalid email");
+
+        for _ in 0..2 {
+            let response = app.sign_up_with_headers(&headers, &data).await;
+            assert_eq!(StatusCode::BAD_REQUEST, response.status());
+            assert!(response.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+        }
+    })
+    .await
+}
+
+#[tokio::test]
+async fn returns_400_when_key_is_invalid() {
+    setup(async |app: TestApp| {
+        let response = app
+            .sign_up_with_headers(
+                &[(IDEMPOTENCY_KEY_HEADER, "has space")],
+                &sign_up_data("test@gmail.com"),
+            )
+            .await;
+
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        assert_eq!(
+            response.json::<Value>().await.unwrap()["code"],
+            "invalid_idempotency_key"
+        );
+    })
+    .await
+}
@@ -1,7 +1,14 @@
+mod api_keys;
+mod cookie_rotation;
+mod csrf;
 mod forgot_password;
 mod get_me;
+mod idempotency;
 mod logout;
+mod onboarding;
+mod reauthenticate;
 mod reset_password;
 mod sign_in;
 mod sign_up;
+mod token;
 mod verify_account;
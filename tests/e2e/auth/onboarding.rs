@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use kicksapi::{
+    ApiResponse,
+    features::auth::{OnboardingResponse, PASSWORD_MIN_LENGTH, UserResponse},
+};
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::e2e::testapp::{TestApp, setup};
+
+async fn onboarding(app: &TestApp) -> OnboardingResponse {
+    app.get_me()
+        .await
+        .json::<ApiResponse<UserResponse>>()
+        .await
+        .unwrap()
+        .data
+        .onboarding
+}
+
+#[tokio::test]
+async fn profile_given_at_sign_up_is_checked_off_after_verification() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+            "first_name": "Jordan",
+            "last_name": "Smith",
+            "gender": "other",
+        });
+        app.create_and_sign_in(&data).await;
+
+        // The checklist is started by a background job.
+        for _ in 0..50 {
+            if onboarding(&app).await.profile_completed {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        panic!("profile step was never checked off");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn steps_can_be_completed_until_the_checklist_is_done() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_sign_in(&data).await;
+        assert!(!onboarding(&app).await.shoe_size_set);
+
+        let response = app.complete_onboarding_step("shoe_size_set").await;
+        assert_eq!(StatusCode::OK, response.status());
+        let checklist = response
+            .json::<ApiResponse<OnboardingResponse>>()
+            .await
+            .unwrap()
+            .data;
+        assert!(checklist.shoe_size_set);
+        assert!(!checklist.completed);
+
+        // Completing a step twice is harmless.
+        for step in ["profile_completed", "preferences_chosen", "shoe_size_set"] {
+            let response = app.complete_onboarding_step(step).await;
+            assert_eq!(StatusCode::OK, response.status());
+        }
+
+        let checklist = onboarding(&app).await;
+        assert!(checklist.profile_completed);
+        assert!(checklist.preferences_chosen);
+        assert!(checklist.completed);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn unknown_steps_are_rejected() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_sign_in(&data).await;
+
+        let response = app.complete_onboarding_step("favourite_colour").await;
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn completing_a_step_requires_sign_in() {
+    setup(async |app: TestApp| {
+        let response = app.complete_onboarding_step("shoe_size_set").await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    })
+    .await;
+}
@@ -0,0 +1,77 @@
+use kicksapi::features::auth::PASSWORD_MIN_LENGTH;
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::e2e::testapp::{TestApp, setup};
+
+#[tokio::test]
+pub async fn returns_200_when_password_is_correct() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_sign_in(&data).await;
+
+        let response = app
+            .reauthenticate(&json!({ "password": data["password"] }))
+            .await;
+        assert_eq!(StatusCode::OK, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn returns_401_when_password_is_wrong() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_sign_in(&data).await;
+
+        let response = app
+            .reauthenticate(&json!({ "password": "p".repeat(PASSWORD_MIN_LENGTH) }))
+            .await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn returns_401_when_not_signed_in() {
+    setup(async |app: TestApp| {
+        let response = app
+            .reauthenticate(&json!({ "password": "s".repeat(PASSWORD_MIN_LENGTH) }))
+            .await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn sensitive_action_requires_recent_authentication() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_sign_in(&data).await;
+        app.age_session_authentication().await;
+
+        let response = app.create_api_key(&json!({ "name": "ci" })).await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+        let response = app.get_me().await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let response = app
+            .reauthenticate(&json!({ "password": data["password"] }))
+            .await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let response = app.create_api_key(&json!({ "name": "ci" })).await;
+        assert_eq!(StatusCode::CREATED, response.status());
+    })
+    .await
+}
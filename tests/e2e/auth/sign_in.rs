@@ -60,6 +60,57 @@ pub async fn successful_sign_in_returns_http_only_cookie() {
     .await
 }
 
+#[tokio::test]
+pub async fn sign_in_without_remember_me_sets_browser_session_cookie() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_verify(&data).await;
+
+        let response = app.sign_in(&data).await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let cookie = response
+            .cookies()
+            .find(|c| c.name() == app.application_config.session_cookie_name)
+            .unwrap();
+
+        assert!(cookie.expires().is_none());
+        assert!(cookie.max_age().is_none());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn sign_in_with_remember_me_sets_persistent_cookie() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_verify(&data).await;
+
+        let signin_data = json!({
+            "email": data["email"].as_str().unwrap(),
+            "password": data["password"].as_str().unwrap(),
+            "remember_me": true,
+        });
+
+        let response = app.sign_in(&signin_data).await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let cookie = response
+            .cookies()
+            .find(|c| c.name() == app.application_config.session_cookie_name)
+            .unwrap();
+
+        assert!(cookie.expires().is_some());
+    })
+    .await
+}
+
 #[tokio::test]
 async fn returns_400_when_request_is_invalid() {
     setup(async |app: TestApp| {
@@ -110,7 +161,7 @@ async fn returns_400_when_request_is_invalid() {
 }
 
 #[tokio::test]
-async fn returns_400_when_user_is_banned() {
+async fn returns_401_when_user_is_banned() {
     setup(async |mut app: TestApp| {
         let data = json!({
             "email": "test@gmail.com",
@@ -126,13 +177,13 @@ async fn returns_400_when_user_is_banned() {
         });
 
         let response = app.sign_in(&signin_data).await;
-        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
     })
     .await
 }
 
 #[tokio::test]
-async fn returns_400_when_user_not_verified() {
+async fn returns_401_when_user_not_verified() {
     setup(async |app: TestApp| {
         let data = json!({
             "email": "test@gmail.com",
@@ -148,7 +199,7 @@ async fn returns_400_when_user_not_verified() {
         });
 
         let response = app.sign_in(&signin_data).await;
-        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
     })
     .await
 }
@@ -159,7 +210,7 @@ async fn returns_429_when_too_many_requests() {
         let mut requests = JoinSet::new();
         let app = Arc::new(app);
 
-        for _ in 0..app.ratelimit_config.sign_in {
+        for _ in 0..app.ratelimit_config.sign_in.requests {
             let app = app.clone();
             requests.spawn(async move {
                 let data = json!({
@@ -167,7 +218,7 @@ async fn returns_429_when_too_many_requests() {
                     "password": "password"
                 });
                 let response = app.sign_in(&data).await;
-                assert_eq!(StatusCode::BAD_REQUEST, response.status());
+                assert_eq!(StatusCode::UNAUTHORIZED, response.status());
             });
         }
 
@@ -181,6 +232,34 @@ async fn returns_429_when_too_many_requests() {
             .await;
 
         assert_eq!(StatusCode::TOO_MANY_REQUESTS, last_response.status());
+        assert!(last_response.headers().contains_key("retry-after"));
+        assert_eq!(last_response.headers()["ratelimit-remaining"], "0");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn sign_in_rate_limit_is_keyed_by_email() {
+    setup(|app: TestApp| async move {
+        for _ in 0..app.ratelimit_config.sign_in.requests {
+            let response = app
+                .sign_in(&json!({
+                    "email": "invalid@gmail.com",
+                    "password": "password"
+                }))
+                .await;
+            assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+        }
+
+        let response = app
+            .sign_in(&json!({
+                "email": "other@gmail.com",
+                "password": "password"
+            }))
+            .await;
+
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+        assert!(response.headers().contains_key("ratelimit-limit"));
     })
     .await;
 }
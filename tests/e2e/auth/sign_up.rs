@@ -184,7 +184,7 @@ async fn returns_400_when_request_is_invalid() {
 }
 
 #[tokio::test]
-async fn returns_400_when_user_already_exists() {
+async fn returns_409_when_user_already_exists() {
     setup(async |app: TestApp| {
         let data = json!({
             "email": "test@gmail.com",
@@ -194,7 +194,7 @@ async fn returns_400_when_user_already_exists() {
         assert_eq!(StatusCode::CREATED, response.status());
 
         let second_response = app.sign_up(&data).await;
-        assert_eq!(StatusCode::BAD_REQUEST, second_response.status());
+        assert_eq!(StatusCode::CONFLICT, second_response.status());
     })
     .await;
 }
@@ -205,7 +205,7 @@ async fn returns_429_when_too_many_requests() {
         let mut requests = JoinSet::new();
         let app = Arc::new(app);
 
-        for _ in 0..app.ratelimit_config.sign_up {
+        for _ in 0..app.ratelimit_config.sign_up.requests {
             let app = app.clone();
             requests.spawn(async move {
                 let data = json!({
@@ -229,3 +229,63 @@ async fn returns_429_when_too_many_requests() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn rate_limit_is_tracked_per_forwarded_client() {
+    setup(|app: TestApp| async move {
+        let data = json!({
+            "email": "invalid email",
+            "password": "password",
+        });
+
+        for _ in 0..app.ratelimit_config.sign_up.requests {
+            let response = app.sign_up_forwarded_for("203.0.113.7", &data).await;
+            assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        }
+
+        let response = app.sign_up_forwarded_for("203.0.113.7", &data).await;
+        assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+
+        let response = app.sign_up_forwarded_for("203.0.113.8", &data).await;
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn client_sent_forwarded_header_does_not_pick_the_rate_limit_key() {
+    setup(|app: TestApp| async move {
+        let data = json!({
+            "email": "invalid email",
+            "password": "password",
+        });
+
+        // The trusted proxy appends the real address to X-Forwarded-For and
+        // passes the client's own Forwarded header through.
+        for n in 0..app.ratelimit_config.sign_up.requests {
+            let forwarded = format!("for=192.0.2.{n}");
+            let response = app
+                .sign_up_with_headers(
+                    &[
+                        ("Forwarded", &forwarded),
+                        ("X-Forwarded-For", "203.0.113.7"),
+                    ],
+                    &data,
+                )
+                .await;
+            assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        }
+
+        let response = app
+            .sign_up_with_headers(
+                &[
+                    ("Forwarded", "for=192.0.2.250"),
+                    ("X-Forwarded-For", "203.0.113.7"),
+                ],
+                &data,
+            )
+            .await;
+        assert_eq!(StatusCode::TOO_MANY_REQUESTS, response.status());
+    })
+    .await;
+}
@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use kicksapi::{
+    ApiResponse,
+    features::auth::{PASSWORD_MIN_LENGTH, TokenResponse, UserResponse},
+};
+use reqwest::StatusCode;
+use serde_json::json;
+use tokio::task::JoinSet;
+
+use crate::e2e::testapp::{TestApp, setup};
+
+async fn create_and_issue_tokens(app: &mut TestApp) -> TokenResponse {
+    let data = json!({
+        "email": "test@gmail.com",
+        "password": "s".repeat(PASSWORD_MIN_LENGTH),
+    });
+    app.create_and_verify(&data).await;
+
+    let response = app.token_sign_in(&data).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    response
+        .json::<ApiResponse<TokenResponse>>()
+        .await
+        .expect("Failed to parse token response")
+        .data
+}
+
+#[tokio::test]
+pub async fn returns_200_with_tokens_when_credentials_are_valid() {
+    setup(async |mut app: TestApp| {
+        let tokens = create_and_issue_tokens(&mut app).await;
+
+        assert_eq!(tokens.token_type, "Bearer");
+        assert!(!tokens.access_token.is_empty());
+        assert!(!tokens.refresh_token.is_empty());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn token_sign_in_does_not_set_session_cookie() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_verify(&data).await;
+
+        let response = app.token_sign_in(&data).await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let cookie = response
+            .cookies()
+            .find(|c| c.name() == app.application_config.session_cookie_name);
+
+        assert!(cookie.is_none());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn returns_401_when_credentials_are_invalid() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+        app.create_and_verify(&data).await;
+
+        let response = app
+            .token_sign_in(&json!({
+                "email": "test@gmail.com",
+                "password": "w".repeat(PASSWORD_MIN_LENGTH),
+            }))
+            .await;
+
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn access_token_authenticates_bearer_requests() {
+    setup(async |mut app: TestApp| {
+        let tokens = create_and_issue_tokens(&mut app).await;
+
+        let response = app.get_me_with_access_token(&tokens.access_token).await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let body = response.json::<ApiResponse<UserResponse>>().await;
+        assert!(body.is_ok());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn returns_401_when_access_token_is_invalid() {
+    setup(async |app: TestApp| {
+        let response = app.get_me_with_access_token("invalid.access.token").await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn refresh_rotates_refresh_token() {
+    setup(async |mut app: TestApp| {
+        let tokens = create_and_issue_tokens(&mut app).await;
+
+        let response = app.refresh_token(&tokens.refresh_token).await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let rotated = response
+            .json::<ApiResponse<TokenResponse>>()
+            .await
+            .unwrap()
+            .data;
+
+        assert_ne!(tokens.refresh_token, rotated.refresh_token);
+
+        let response = app.get_me_with_access_token(&rotated.access_token).await;
+        assert_eq!(StatusCode::OK, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn reusing_refresh_token_revokes_the_family() {
+    setup(async |mut app: TestApp| {
+        let tokens = create_and_issue_tokens(&mut app).await;
+
+        let response = app.refresh_token(&tokens.refresh_token).await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let rotated = response
+            .json::<ApiResponse<TokenResponse>>()
+            .await
+            .unwrap()
+            .data;
+
+        let reuse_response = app.refresh_token(&tokens.refresh_token).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, reuse_response.status());
+
+        let response = app.refresh_token(&rotated.refresh_token).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn revoked_refresh_token_cannot_be_used() {
+    setup(async |mut app: TestApp| {
+        let tokens = create_and_issue_tokens(&mut app).await;
+
+        let response = app.revoke_token(&tokens.refresh_token).await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let response = app.refresh_token(&tokens.refresh_token).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn returns_401_when_user_is_banned() {
+    setup(async |mut app: TestApp| {
+        let tokens = create_and_issue_tokens(&mut app).await;
+
+        app.ban_user("test@gmail.com").await;
+
+        let response = app.refresh_token(&tokens.refresh_token).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+        let response = app.get_me_with_access_token(&tokens.access_token).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn returns_429_when_too_many_requests() {
+    setup(|app: TestApp| async move {
+        let mut requests = JoinSet::new();
+        let app = Arc::new(app);
+
+        for _ in 0..app.ratelimit_config.refresh_token.requests {
+            let app = app.clone();
+            requests.spawn(async move {
+                let response = app.refresh_token("invalid").await;
+                assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+            });
+        }
+
+        requests.join_all().await;
+
+        let last_response = app.refresh_token("invalid").await;
+        assert_eq!(StatusCode::TOO_MANY_REQUESTS, last_response.status());
+    })
+    .await;
+}
@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use kicksapi::features::auth::PASSWORD_MIN_LENGTH;
+use kicksapi::{
+    ApiResponse,
+    features::auth::{PASSWORD_MIN_LENGTH, UserResponse},
+};
 use reqwest::StatusCode;
 use serde_json::json;
 use tokio::task::JoinSet;
@@ -63,6 +66,45 @@ async fn user_should_be_verified_when_request_is_valid() {
     .await;
 }
 
+#[tokio::test]
+async fn user_should_be_signed_in_when_request_is_valid() {
+    setup(async |mut app: TestApp| {
+        let data = json!({
+            "email": "test@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+
+        let response = app.sign_up(&data).await;
+        assert_eq!(StatusCode::CREATED, response.status());
+
+        let token = app.get_redis_value(RedisKeyType::AccountVerification).await;
+        let verify_account_data = json!({
+            "email": data["email"].as_str().unwrap(),
+            "token": token.unwrap(),
+        });
+
+        let verification_response = app.verify_account(&verify_account_data).await;
+        assert_eq!(StatusCode::OK, verification_response.status());
+        assert!(
+            verification_response
+                .cookies()
+                .any(|c| c.name() == app.application_config.session_cookie_name)
+        );
+
+        let user = verification_response
+            .json::<ApiResponse<UserResponse>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(user.email, "test@gmail.com");
+        assert!(!user.onboarding.completed);
+
+        let response = app.get_me().await;
+        assert_eq!(StatusCode::OK, response.status());
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn returns_400_when_request_is_invalid() {
     setup(async |app: TestApp| {
@@ -156,7 +198,7 @@ async fn returns_429_when_too_many_requests() {
         let mut requests = JoinSet::new();
         let app = Arc::new(app);
 
-        for _ in 0..app.ratelimit_config.verify_account {
+        for _ in 0..app.ratelimit_config.verify_account.requests {
             let app = app.clone();
             requests.spawn(async move {
                 let data = json!({
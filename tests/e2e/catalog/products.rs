@@ -0,0 +1,241 @@
+use kicksapi::{
+    ApiResponse,
+    features::{auth::PASSWORD_MIN_LENGTH, catalog::ProductResponse, shared::Page},
+};
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+
+use crate::e2e::testapp::{TestApp, setup};
+
+fn user(email: &str) -> Value {
+    json!({
+        "email": email,
+        "password": "s".repeat(PASSWORD_MIN_LENGTH),
+    })
+}
+
+async fn sign_in_as_admin(app: &mut TestApp) {
+    let admin = user("admin@gmail.com");
+    app.create_and_verify(&admin).await;
+    app.promote_to_admin("admin@gmail.com").await;
+
+    let response = app.sign_in(&admin).await;
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+fn product(sku: &str, brand: &str, release_date: &str, price_cents: i64) -> Value {
+    json!({
+        "sku": sku,
+        "brand": brand,
+        "model": "Air Max 1",
+        "colorway": "Sport Red",
+        "description": "The original visible Air.",
+        "gender": "unisex",
+        "release_date": release_date,
+        "price_cents": price_cents,
+        "currency": "usd",
+    })
+}
+
+async fn create_product(app: &TestApp, body: &Value) -> ProductResponse {
+    let response = app.create_product(body).await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    response
+        .json::<ApiResponse<ProductResponse>>()
+        .await
+        .unwrap()
+        .data
+}
+
+async fn list(app: &TestApp, query: &[(&str, &str)]) -> Page<ProductResponse> {
+    let response = app.list_products(query).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    response
+        .json::<ApiResponse<Page<ProductResponse>>>()
+        .await
+        .unwrap()
+        .data
+}
+
+fn skus(page: &Page<ProductResponse>) -> Vec<&str> {
+    page.items.iter().map(|p| p.sku.as_str()).collect()
+}
+
+#[tokio::test]
+async fn created_products_are_public_by_slug() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app).await;
+
+        let created =
+            create_product(&app, &product("fz5808-100", "Nike", "2025-03-26", 15000)).await;
+        assert_eq!(created.sku, "FZ5808-100");
+        assert_eq!(created.currency, "USD");
+        assert_eq!(created.slug, "nike-air-max-1-sport-red-fz5808-100");
+
+        let response = app.get_product(&created.slug).await;
+        assert_eq!(StatusCode::OK, response.status());
+        let product = response
+            .json::<ApiResponse<ProductResponse>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(product.id, created.id);
+        assert_eq!(product.release_date, "2025-03-26");
+        assert_eq!(product.price_cents, 15000);
+
+        assert_eq!(
+            StatusCode::NOT_FOUND,
+            app.get_product("no-such-sneaker").await.status()
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn products_are_listed_sorted_and_filtered() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app).await;
+        create_product(&app, &product("AAA-001", "Nike", "2024-01-10", 12000)).await;
+        create_product(&app, &product("BBB-002", "Adidas", "2025-06-01", 18000)).await;
+        create_product(&app, &product("CCC-003", "New Balance", "2023-09-15", 9000)).await;
+
+        let page = list(&app, &[]).await;
+        assert_eq!(skus(&page), ["BBB-002", "AAA-001", "CCC-003"]);
+
+        let page = list(&app, &[("sort", "price"), ("limit", "2")]).await;
+        assert_eq!(skus(&page), ["CCC-003", "AAA-001"]);
+        let cursor = page.next_cursor.expect("a second page");
+        let page = list(
+            &app,
+            &[("sort", "price"), ("limit", "2"), ("cursor", &cursor)],
+        )
+        .await;
+        assert_eq!(skus(&page), ["BBB-002"]);
+
+        let page = list(&app, &[("brand", "new")]).await;
+        assert_eq!(skus(&page), ["CCC-003"]);
+
+        let response = app.list_products(&[("gender", "adults")]).await;
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn updates_keep_the_slug() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app).await;
+        let created =
+            create_product(&app, &product("FZ5808-100", "Nike", "2025-03-26", 15000)).await;
+
+        let response = app
+            .update_product(
+                &created.id.to_string(),
+                &json!({ "colorway": "University Red", "price_cents": 16000 }),
+            )
+            .await;
+        assert_eq!(StatusCode::OK, response.status());
+        let updated = response
+            .json::<ApiResponse<ProductResponse>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(updated.colorway, "University Red");
+        assert_eq!(updated.price_cents, 16000);
+        assert_eq!(updated.model, "Air Max 1");
+        assert_eq!(updated.slug, created.slug);
+
+        let response = app
+            .update_product(&created.id.to_string(), &json!({ "price_cents": -1 }))
+            .await;
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+        let response = app
+            .update_product(&uuid::Uuid::new_v4().to_string(), &json!({}))
+            .await;
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn archived_products_leave_the_catalog() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app).await;
+        let created =
+            create_product(&app, &product("FZ5808-100", "Nike", "2025-03-26", 15000)).await;
+
+        let response = app.archive_product(&created.id.to_string()).await;
+        assert_eq!(StatusCode::OK, response.status());
+        let archived = response
+            .json::<ApiResponse<ProductResponse>>()
+            .await
+            .unwrap()
+            .data;
+        assert!(archived.archived_at.is_some());
+
+        assert_eq!(
+            StatusCode::NOT_FOUND,
+            app.get_product(&created.slug).await.status()
+        );
+        assert!(list(&app, &[]).await.items.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn invalid_and_duplicate_products_are_rejected() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app).await;
+        create_product(&app, &product("FZ5808-100", "Nike", "2025-03-26", 15000)).await;
+
+        let response = app
+            .create_product(&product("FZ5808-100", "Nike", "2025-03-26", 15000))
+            .await;
+        assert_eq!(StatusCode::CONFLICT, response.status());
+
+        let response = app
+            .create_product(&json!({
+                "sku": "no spaces",
+                "brand": "",
+                "model": "Air Max 1",
+                "colorway": "Sport Red",
+                "gender": "adults",
+                "release_date": "26/03/2025",
+                "price_cents": -100,
+                "currency": "dollars",
+            }))
+            .await;
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        let body: Value = response.json().await.unwrap();
+        for field in [
+            "sku",
+            "brand",
+            "gender",
+            "release_date",
+            "price_cents",
+            "currency",
+        ] {
+            assert!(body["errors"][field].is_array(), "{field}: {body}");
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn only_admins_manage_products() {
+    setup(async |mut app: TestApp| {
+        app.create_and_sign_in(&user("regular@gmail.com")).await;
+
+        let response = app
+            .create_product(&product("FZ5808-100", "Nike", "2025-03-26", 15000))
+            .await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+        let response = app.archive_product(&uuid::Uuid::new_v4().to_string()).await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    })
+    .await;
+}
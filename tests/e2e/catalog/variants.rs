@@ -0,0 +1,335 @@
+use kicksapi::{
+    ApiResponse,
+    features::{
+        auth::PASSWORD_MIN_LENGTH,
+        catalog::{
+            ProductResponse,
+            inventory::{ProductAvailabilityResponse, STOCK_MAX, ShoeWidth, VariantResponse},
+        },
+    },
+};
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+
+use crate::e2e::testapp::{TestApp, setup};
+
+fn user(email: &str) -> Value {
+    json!({
+        "email": email,
+        "password": "s".repeat(PASSWORD_MIN_LENGTH),
+    })
+}
+
+async fn sign_in_as_admin(app: &mut TestApp) {
+    let admin = user("admin@gmail.com");
+    app.create_and_verify(&admin).await;
+    app.promote_to_admin("admin@gmail.com").await;
+
+    let response = app.sign_in(&admin).await;
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+async fn create_product(app: &TestApp) -> ProductResponse {
+    let response = app
+        .create_product(&json!({
+            "sku": "FZ5808-100",
+            "brand": "Nike",
+            "model": "Air Max 1",
+            "colorway": "Sport Red",
+            "gender": "men",
+            "release_date": "2025-03-26",
+            "price_cents": 15000,
+            "currency": "USD",
+        }))
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    response
+        .json::<ApiResponse<ProductResponse>>()
+        .await
+        .unwrap()
+        .data
+}
+
+async fn create_variant(app: &TestApp, product: &ProductResponse, body: &Value) -> VariantResponse {
+    let response = app.create_variant(&product.id.to_string(), body).await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    response
+        .json::<ApiResponse<VariantResponse>>()
+        .await
+        .unwrap()
+        .data
+}
+
+async fn availability(app: &TestApp, slug: &str) -> ProductAvailabilityResponse {
+    let response = app.get_availability(slug).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    response
+        .json::<ApiResponse<ProductAvailabilityResponse>>()
+        .await
+        .unwrap()
+        .data
+}
+
+async fn adjust(app: &TestApp, variant: &VariantResponse, delta: i32) -> reqwest::Response {
+    app.adjust_stock(&variant.id.to_string(), &json!({ "delta": delta }))
+        .await
+}
+
+#[tokio::test]
+async fn variants_convert_missing_sizes() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app).await;
+        let product = create_product(&app).await;
+
+        let converted = create_variant(
+            &app,
+            &product,
+            &json!({ "sku": "fz5808-100-095", "size_us": 9.5 }),
+        )
+        .await;
+        assert_eq!(converted.sku, "FZ5808-100-095");
+        assert_eq!(converted.size_uk, 8.5);
+        assert_eq!(converted.size_eu, "42.5");
+        assert_eq!(converted.width, ShoeWidth::Standard);
+        assert_eq!(converted.stock_quantity, 0);
+
+        let explicit = create_variant(
+            &app,
+            &product,
+            &json!({
+                "sku": "FZ5808-100-100W",
+                "size_us": 10,
+                "size_uk": 9,
+                "size_eu": "44 2/3",
+                "width": "wide",
+                "stock_quantity": 3,
+            }),
+        )
+        .await;
+        assert_eq!(explicit.size_eu, "44 2/3");
+        assert_eq!(explicit.width, ShoeWidth::Wide);
+
+        let response = app.list_variants(&product.id.to_string()).await;
+        assert_eq!(StatusCode::OK, response.status());
+        let variants = response
+            .json::<ApiResponse<Vec<VariantResponse>>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(variants.len(), 2);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn invalid_and_duplicate_variants_are_rejected() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app).await;
+        let product = create_product(&app).await;
+        let id = product.id.to_string();
+        create_variant(&app, &product, &json!({ "sku": "FZ-095", "size_us": 9.5 })).await;
+
+        let response = app
+            .create_variant(&id, &json!({ "sku": "FZ-095", "size_us": 10 }))
+            .await;
+        assert_eq!(StatusCode::CONFLICT, response.status());
+
+        let response = app
+            .create_variant(&id, &json!({ "sku": "FZ-095-B", "size_us": 9.5 }))
+            .await;
+        assert_eq!(StatusCode::CONFLICT, response.status());
+
+        let response = app
+            .create_variant(
+                &id,
+                &json!({
+                    "sku": "FZ-X",
+                    "size_us": 9.25,
+                    "size_eu": "42,5",
+                    "width": "extra wide",
+                    "stock_quantity": -1,
+                    "price_cents_override": -1,
+                }),
+            )
+            .await;
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        let body: Value = response.json().await.unwrap();
+        for field in [
+            "size_us",
+            "size_eu",
+            "width",
+            "stock_quantity",
+            "price_cents_override",
+        ] {
+            assert!(body["errors"][field].is_array(), "{field}: {body}");
+        }
+
+        let response = app
+            .create_variant(
+                &uuid::Uuid::new_v4().to_string(),
+                &json!({ "sku": "FZ-100", "size_us": 10 }),
+            )
+            .await;
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn stock_never_goes_negative() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app).await;
+        let product = create_product(&app).await;
+        let variant = create_variant(
+            &app,
+            &product,
+            &json!({ "sku": "FZ-095", "size_us": 9.5, "stock_quantity": 2 }),
+        )
+        .await;
+
+        let response = adjust(&app, &variant, 3).await;
+        assert_eq!(StatusCode::OK, response.status());
+        let adjusted = response
+            .json::<ApiResponse<VariantResponse>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(adjusted.stock_quantity, 5);
+
+        assert_eq!(
+            StatusCode::CONFLICT,
+            adjust(&app, &variant, -6).await.status()
+        );
+        assert_eq!(StatusCode::OK, adjust(&app, &variant, -5).await.status());
+        assert_eq!(
+            StatusCode::CONFLICT,
+            adjust(&app, &variant, -1).await.status()
+        );
+        assert_eq!(
+            StatusCode::BAD_REQUEST,
+            adjust(&app, &variant, 0).await.status()
+        );
+
+        let response = app
+            .adjust_stock(&uuid::Uuid::new_v4().to_string(), &json!({ "delta": 1 }))
+            .await;
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn stock_stays_within_the_maximum() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app).await;
+        let product = create_product(&app).await;
+        let variant = create_variant(
+            &app,
+            &product,
+            &json!({ "sku": "FZ-095", "size_us": 9.5, "stock_quantity": STOCK_MAX - 1 }),
+        )
+        .await;
+
+        assert_eq!(
+            StatusCode::CONFLICT,
+            adjust(&app, &variant, 2).await.status()
+        );
+        assert_eq!(StatusCode::OK, adjust(&app, &variant, 1).await.status());
+        assert_eq!(
+            StatusCode::CONFLICT,
+            adjust(&app, &variant, STOCK_MAX).await.status()
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn availability_lists_in_stock_sizes_with_prices() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app).await;
+        let product = create_product(&app).await;
+        let wide = create_variant(
+            &app,
+            &product,
+            &json!({ "sku": "FZ-090W", "size_us": 9, "width": "wide", "stock_quantity": 1 }),
+        )
+        .await;
+        let ten = create_variant(
+            &app,
+            &product,
+            &json!({ "sku": "FZ-100", "size_us": 10, "stock_quantity": 4 }),
+        )
+        .await;
+        let nine = create_variant(
+            &app,
+            &product,
+            &json!({ "sku": "FZ-090", "size_us": 9, "stock_quantity": 1 }),
+        )
+        .await;
+        create_variant(&app, &product, &json!({ "sku": "FZ-110", "size_us": 11 })).await;
+
+        let response = app
+            .set_price_override(
+                &ten.id.to_string(),
+                &json!({ "price_cents_override": 17500 }),
+            )
+            .await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let available = availability(&app, &product.slug).await;
+        assert_eq!(available.product_id, product.id);
+        assert_eq!(available.currency, "USD");
+        let sizes: Vec<_> = available
+            .sizes
+            .iter()
+            .map(|s| (s.variant_id, s.price_cents))
+            .collect();
+        assert_eq!(sizes, [(nine.id, 15000), (ten.id, 17500), (wide.id, 15000)]);
+
+        let response = app
+            .set_price_override(
+                &ten.id.to_string(),
+                &json!({ "price_cents_override": null }),
+            )
+            .await;
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(StatusCode::OK, adjust(&app, &nine, -1).await.status());
+
+        let available = availability(&app, &product.slug).await;
+        let sizes: Vec<_> = available
+            .sizes
+            .iter()
+            .map(|s| (s.variant_id, s.price_cents))
+            .collect();
+        assert_eq!(sizes, [(ten.id, 15000), (wide.id, 15000)]);
+
+        app.archive_product(&product.id.to_string()).await;
+        assert_eq!(
+            StatusCode::NOT_FOUND,
+            app.get_availability(&product.slug).await.status()
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn only_admins_manage_variants() {
+    setup(async |mut app: TestApp| {
+        app.create_and_sign_in(&user("regular@gmail.com")).await;
+        let id = uuid::Uuid::new_v4().to_string();
+
+        let response = app
+            .create_variant(&id, &json!({ "sku": "FZ-095", "size_us": 9.5 }))
+            .await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+        assert_eq!(StatusCode::FORBIDDEN, app.list_variants(&id).await.status());
+
+        let response = app.adjust_stock(&id, &json!({ "delta": 1 })).await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    })
+    .await;
+}
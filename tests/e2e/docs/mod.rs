@@ -0,0 +1 @@
+mod openapi;
@@ -0,0 +1,33 @@
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::e2e::testapp::{TestApp, setup};
+
+#[tokio::test]
+async fn serves_openapi_document() {
+    setup(async |app: TestApp| {
+        let response = app.openapi().await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let doc = response.json::<Value>().await.unwrap();
+        assert_eq!(doc["openapi"], "3.1.0");
+        assert!(doc["paths"]["/api/v1/auth/sign-in"]["post"].is_object());
+        assert_eq!(
+            doc["components"]["securitySchemes"]["session_cookie"]["name"],
+            app.application_config.session_cookie_name.as_str()
+        );
+    })
+    .await
+}
+
+#[tokio::test]
+async fn serves_docs_ui() {
+    setup(async |app: TestApp| {
+        let response = app.docs().await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let html = response.text().await.unwrap();
+        assert!(html.contains("/api/v1/auth/sign-in"));
+    })
+    .await
+}
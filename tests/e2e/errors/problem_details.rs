@@ -0,0 +1,128 @@
+use kicksapi::configuration::app_config::ErrorFormat;
+use reqwest::{StatusCode, header};
+use serde_json::{Value, json};
+
+use crate::e2e::testapp::{TestApp, setup, setup_with};
+
+const PROBLEM_JSON: &str = "application/problem+json";
+
+fn invalid_sign_up() -> Value {
+    json!({
+        "email": "invalid email",
+        "password": "password",
+    })
+}
+
+#[tokio::test]
+async fn validation_errors_are_problem_details() {
+    setup(async |app: TestApp| {
+        let response = app
+            .sign_up_with_headers(&[("x-request-id", "req-123")], &invalid_sign_up())
+            .await;
+
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+
+        let body = response.json::<Value>().await.unwrap();
+        assert_eq!(body["type"], "about:blank");
+        assert_eq!(body["status"], 400);
+        assert_eq!(body["code"], "validation_failed");
+        assert_eq!(body["request_id"], "req-123");
+        assert!(
+            body["errors"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|e| e["field"] == "email")
+        );
+    })
+    .await
+}
+
+#[tokio::test]
+async fn generated_request_id_matches_header() {
+    setup(async |app: TestApp| {
+        let response = app.get_unknown_route().await;
+
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+        let request_id = response.headers()["x-request-id"]
+            .to_str()
+            .unwrap()
+            .to_owned();
+
+        let body = response.json::<Value>().await.unwrap();
+        assert_eq!(body["code"], "not_found");
+        assert_eq!(body["request_id"], request_id);
+    })
+    .await
+}
+
+#[tokio::test]
+async fn legacy_format_keeps_v1_shape() {
+    setup_with(
+        |config| config.application.error_format = ErrorFormat::Legacy,
+        async |app: TestApp| {
+            let response = app.sign_up(&invalid_sign_up()).await;
+
+            assert_eq!(StatusCode::BAD_REQUEST, response.status());
+            assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+
+            let body = response.json::<Value>().await.unwrap();
+            assert!(body["errors"]["email"].is_array());
+            assert!(body.get("code").is_none());
+
+            let response = app.get_unknown_route().await;
+            assert_eq!(
+                response.json::<Value>().await.unwrap(),
+                json!({ "error": "Not found" })
+            );
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn legacy_format_serves_problems_on_request() {
+    setup_with(
+        |config| config.application.error_format = ErrorFormat::Legacy,
+        async |app: TestApp| {
+            let response = app
+                .sign_up_with_headers(&[("accept", PROBLEM_JSON)], &invalid_sign_up())
+                .await;
+
+            assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+            assert_eq!(
+                response.json::<Value>().await.unwrap()["code"],
+                "validation_failed"
+            );
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn validation_errors_follow_accept_language() {
+    setup(async |app: TestApp| {
+        let response = app
+            .sign_up_with_headers(
+                &[("accept-language", "de-DE, en;q=0.5")],
+                &invalid_sign_up(),
+            )
+            .await;
+
+        assert_eq!(response.headers()[header::CONTENT_LANGUAGE], "de");
+
+        let body = response.json::<Value>().await.unwrap();
+        assert_eq!(body["detail"], "Validierung fehlgeschlagen");
+
+        let email = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|e| e["field"] == "email")
+            .unwrap();
+        assert_eq!(email["code"], "email.invalid");
+        assert_eq!(email["detail"], "Ungültige E-Mail-Adresse");
+    })
+    .await
+}
@@ -0,0 +1,52 @@
+use kicksapi::{
+    ApiResponse,
+    features::health::{HealthReport, HealthStatus},
+};
+use reqwest::StatusCode;
+
+use crate::e2e::testapp::{TestApp, setup};
+
+#[tokio::test]
+pub async fn live_returns_200() {
+    setup(async |app: TestApp| {
+        let response = app.health_live().await;
+        assert_eq!(StatusCode::OK, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn ready_reports_every_dependency() {
+    setup(async |app: TestApp| {
+        let response = app.health_ready().await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let report = response
+            .json::<ApiResponse<HealthReport>>()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(report.status, HealthStatus::Ok);
+        assert!(report.accepting_traffic);
+
+        let names: Vec<_> = report
+            .dependencies
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect();
+        assert_eq!(names, ["postgres", "redis", "email"]);
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn probes_are_not_rate_limited() {
+    setup(async |app: TestApp| {
+        for _ in 0..=app.ratelimit_config.global.requests {
+            let response = app.health_live().await;
+            assert_eq!(StatusCode::OK, response.status());
+        }
+    })
+    .await
+}
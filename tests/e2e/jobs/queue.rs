@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use kicksapi::features::auth::PASSWORD_MIN_LENGTH;
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+
+use crate::e2e::testapp::{TestApp, setup};
+
+fn user(email: &str) -> Value {
+    json!({
+        "email": email,
+        "password": "s".repeat(PASSWORD_MIN_LENGTH),
+    })
+}
+
+#[tokio::test]
+async fn cleanup_deletes_expired_unverified_accounts() {
+    setup(async |mut app: TestApp| {
+        for email in ["stale@gmail.com", "fresh@gmail.com"] {
+            assert_eq!(
+                StatusCode::CREATED,
+                app.sign_up(&user(email)).await.status()
+            );
+        }
+        app.create_and_verify(&user("verified@gmail.com")).await;
+        app.age_user("stale@gmail.com", 48).await;
+        app.age_user("verified@gmail.com", 48).await;
+
+        let id = app
+            .enqueue_job("auth.cleanup_unverified_accounts", Value::Null, 0.0)
+            .await;
+
+        assert_eq!(app.wait_for_job(id).await.0, "completed");
+        assert!(app.get_user_by_email("stale@gmail.com").await.is_none());
+        assert!(app.get_user_by_email("fresh@gmail.com").await.is_some());
+        assert!(app.get_user_by_email("verified@gmail.com").await.is_some());
+
+        let response = app.sign_up(&user("stale@gmail.com")).await;
+        assert_eq!(StatusCode::CREATED, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn job_without_handler_fails_permanently() {
+    setup(async |app: TestApp| {
+        let id = app.enqueue_job("does_not_exist", Value::Null, 0.0).await;
+
+        let (status, error) = app.wait_for_job(id).await;
+
+        assert_eq!(status, "failed");
+        assert!(error.unwrap().contains("no handler"));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn job_with_invalid_payload_fails_permanently() {
+    setup(async |app: TestApp| {
+        let id = app
+            .enqueue_job("jobs.prune", json!({ "retention_hours": "soon" }), 0.0)
+            .await;
+
+        let (status, error) = app.wait_for_job(id).await;
+
+        assert_eq!(status, "failed");
+        assert!(error.unwrap().contains("invalid payload"));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn scheduled_job_waits_until_due() {
+    setup(async |app: TestApp| {
+        let id = app
+            .enqueue_job("jobs.prune", json!({ "retention_hours": 1 }), 1.0)
+            .await;
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(app.job_status(id).await.0, "pending");
+
+        assert_eq!(app.wait_for_job(id).await.0, "completed");
+    })
+    .await
+}
@@ -0,0 +1,42 @@
+use kicksapi::features::auth::PASSWORD_MIN_LENGTH;
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::e2e::testapp::{TestApp, setup};
+
+#[tokio::test]
+pub async fn metrics_are_served_on_the_metrics_port_only() {
+    setup(async |app: TestApp| {
+        let response = app.metrics().await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let response = app.metrics_on_api_port().await;
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    })
+    .await
+}
+
+#[tokio::test]
+pub async fn failed_sign_in_is_recorded() {
+    setup(async |app: TestApp| {
+        let data = json!({
+            "email": "unknown@gmail.com",
+            "password": "s".repeat(PASSWORD_MIN_LENGTH),
+        });
+
+        let response = app.sign_in(&data).await;
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+        let body = app.metrics().await.text().await.unwrap();
+
+        assert!(body.contains(
+            r#"auth_failure_total{flow="sign_in",provider="password",reason="unknown_user"}"#
+        ));
+        assert!(body.contains(
+            r#"http_requests_total{method="POST",route="/api/v1/auth/sign-in",status="400"}"#
+        ));
+        assert!(body.contains("db_pool_connections"));
+        assert!(body.contains("redis_up 1"));
+    })
+    .await
+}
@@ -1,2 +1,10 @@
+mod admin;
 mod auth;
+mod catalog;
+mod docs;
+mod errors;
+mod health;
+mod jobs;
+mod metrics;
 mod testapp;
+mod webhooks;
@@ -0,0 +1,16 @@
+use reqwest::{Response, Url};
+
+use crate::e2e::testapp::TestApp;
+
+impl TestApp {
+    pub async fn list_users(&self, query: &[(&str, &str)]) -> Response {
+        let url = Url::parse_with_params(&format!("{}{}", self.address, "/admin/users"), query)
+            .expect("Invalid URL");
+
+        self.http_client
+            .get(url)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+}
@@ -1,4 +1,5 @@
-use reqwest::{Response, StatusCode};
+use kicksapi::{ApiResponse, features::auth::CsrfTokenResponse, middlewares::CSRF_HEADER_NAME};
+use reqwest::{Client, Response, StatusCode};
 use serde::Serialize;
 use serde_json::{Value, json};
 
@@ -17,6 +18,39 @@ impl TestApp {
             .expect("Request failed")
     }
 
+    pub async fn sign_up_forwarded_for<Body>(&self, client_ip: &str, body: &Body) -> Response
+    where
+        Body: Serialize,
+    {
+        self.http_client
+            .post(format!("{}{}", self.address, "/auth/sign-up"))
+            .header("X-Forwarded-For", client_ip)
+            .json(&body)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn sign_up_with_headers<Body>(
+        &self,
+        headers: &[(&str, &str)],
+        body: &Body,
+    ) -> Response
+    where
+        Body: Serialize,
+    {
+        let mut request = self
+            .http_client
+            .post(format!("{}{}", self.address, "/auth/sign-up"))
+            .json(&body);
+
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+
+        request.send().await.expect("Request failed")
+    }
+
     pub async fn verify_account<Body>(&self, body: &Body) -> Response
     where
         Body: Serialize,
@@ -65,9 +99,58 @@ impl TestApp {
             .expect("Request failed")
     }
 
+    pub async fn csrf_token(&self) -> String {
+        self.http_client
+            .get(format!("{}{}", self.address, "/auth/csrf-token"))
+            .send()
+            .await
+            .expect("Request failed")
+            .json::<ApiResponse<CsrfTokenResponse>>()
+            .await
+            .expect("Failed to parse csrf token response")
+            .data
+            .token
+    }
+
     pub async fn logout(&self) -> Response {
+        let csrf_token = self.csrf_token().await;
+        self.logout_with_csrf_token(Some(&csrf_token)).await
+    }
+
+    pub async fn logout_with_csrf_token(&self, csrf_token: Option<&str>) -> Response {
+        let mut request = self
+            .http_client
+            .post(format!("{}{}", self.address, "/auth/logout"));
+
+        if let Some(csrf_token) = csrf_token {
+            request = request.header(CSRF_HEADER_NAME, csrf_token);
+        }
+
+        request.send().await.expect("Request failed")
+    }
+
+    pub async fn logout_with_headers(&self, headers: &[(&str, &str)]) -> Response {
+        let mut request = self
+            .http_client
+            .post(format!("{}{}", self.address, "/auth/logout"));
+
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+
+        request.send().await.expect("Request failed")
+    }
+
+    pub async fn reauthenticate<Body>(&self, body: &Body) -> Response
+    where
+        Body: Serialize,
+    {
+        let csrf_token = self.csrf_token().await;
+
         self.http_client
-            .post(format!("{}{}", self.address, "/auth/logout"))
+            .post(format!("{}{}", self.address, "/auth/reauthenticate"))
+            .header(CSRF_HEADER_NAME, csrf_token)
+            .json(&body)
             .send()
             .await
             .expect("Request failed")
@@ -81,6 +164,121 @@ impl TestApp {
             .expect("Request failed")
     }
 
+    pub async fn token_sign_in<Body>(&self, body: &Body) -> Response
+    where
+        Body: Serialize,
+    {
+        self.http_client
+            .post(format!("{}{}", self.address, "/auth/token"))
+            .json(&body)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn refresh_token(&self, refresh_token: &str) -> Response {
+        self.http_client
+            .post(format!("{}{}", self.address, "/auth/token/refresh"))
+            .json(&json!({ "refresh_token": refresh_token }))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn revoke_token(&self, refresh_token: &str) -> Response {
+        self.http_client
+            .post(format!("{}{}", self.address, "/auth/token/revoke"))
+            .json(&json!({ "refresh_token": refresh_token }))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn complete_onboarding_step(&self, step: &str) -> Response {
+        let csrf_token = self.csrf_token().await;
+
+        self.http_client
+            .put(format!("{}/auth/me/onboarding/{}", self.address, step))
+            .header(CSRF_HEADER_NAME, csrf_token)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn get_me_with_access_token(&self, access_token: &str) -> Response {
+        self.http_client
+            .get(format!("{}{}", self.address, "/auth/me"))
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn create_api_key<Body>(&self, body: &Body) -> Response
+    where
+        Body: Serialize,
+    {
+        let csrf_token = self.csrf_token().await;
+
+        self.http_client
+            .post(format!("{}{}", self.address, "/auth/api-keys"))
+            .header(CSRF_HEADER_NAME, csrf_token)
+            .json(&body)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn list_api_keys(&self) -> Response {
+        self.http_client
+            .get(format!("{}{}", self.address, "/auth/api-keys"))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn revoke_api_key(&self, id: &str) -> Response {
+        let csrf_token = self.csrf_token().await;
+
+        self.http_client
+            .delete(format!("{}/auth/api-keys/{}", self.address, id))
+            .header(CSRF_HEADER_NAME, csrf_token)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn get_me_with_api_key(&self, api_key: &str) -> Response {
+        Client::new()
+            .get(format!("{}{}", self.address, "/auth/me"))
+            .header("Authorization", format!("ApiKey {}", api_key))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn get_me_with_cookie(&self, cookie: &str) -> Response {
+        Client::new()
+            .get(format!("{}{}", self.address, "/auth/me"))
+            .header("Cookie", cookie)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn create_api_key_with_api_key<Body>(&self, api_key: &str, body: &Body) -> Response
+    where
+        Body: Serialize,
+    {
+        Client::new()
+            .post(format!("{}{}", self.address, "/auth/api-keys"))
+            .header("Authorization", format!("ApiKey {}", api_key))
+            .json(&body)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
     pub async fn create_and_verify(&mut self, body: &Value) {
         let response = self.sign_up(body).await;
         assert_eq!(StatusCode::CREATED, response.status());
@@ -96,7 +294,14 @@ impl TestApp {
             "token": token.unwrap(),
         });
 
-        let verify_response = self.verify_account(&verify_account_data).await;
+        // Verifying signs the user in; keep that session out of the shared
+        // cookie jar so callers stay signed in as whoever they were.
+        let verify_response = Client::new()
+            .post(format!("{}{}", self.address, "/auth/verify-account"))
+            .json(&verify_account_data)
+            .send()
+            .await
+            .expect("Request failed");
         assert_eq!(StatusCode::OK, verify_response.status());
     }
 
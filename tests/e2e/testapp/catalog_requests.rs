@@ -0,0 +1,67 @@
+use kicksapi::middlewares::CSRF_HEADER_NAME;
+use reqwest::{Response, Url};
+use serde::Serialize;
+
+use crate::e2e::testapp::TestApp;
+
+impl TestApp {
+    pub async fn list_products(&self, query: &[(&str, &str)]) -> Response {
+        let url = Url::parse_with_params(&format!("{}/products", self.address), query)
+            .expect("Invalid URL");
+
+        self.http_client
+            .get(url)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn get_product(&self, slug: &str) -> Response {
+        self.http_client
+            .get(format!("{}/products/{}", self.address, slug))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn create_product<Body>(&self, body: &Body) -> Response
+    where
+        Body: Serialize,
+    {
+        let csrf_token = self.csrf_token().await;
+
+        self.http_client
+            .post(format!("{}{}", self.address, "/admin/products"))
+            .header(CSRF_HEADER_NAME, csrf_token)
+            .json(&body)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn update_product<Body>(&self, id: &str, body: &Body) -> Response
+    where
+        Body: Serialize,
+    {
+        let csrf_token = self.csrf_token().await;
+
+        self.http_client
+            .patch(format!("{}/admin/products/{}", self.address, id))
+            .header(CSRF_HEADER_NAME, csrf_token)
+            .json(&body)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn archive_product(&self, id: &str) -> Response {
+        let csrf_token = self.csrf_token().await;
+
+        self.http_client
+            .post(format!("{}/admin/products/{}/archive", self.address, id))
+            .header(CSRF_HEADER_NAME, csrf_token)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+}
@@ -1,10 +1,14 @@
 use kicksapi::features::auth::{
     EmailAddress, FacebookID, FirstName, GoogleID, HashedPassword, LastName,
-    REDIS_ACCOUNT_VERIFICATION_PREFIX, REDIS_RESET_PASSWORD_PREFIX, User, UserGender, UserID,
-    UserRole,
+    REDIS_ACCOUNT_VERIFICATION_PREFIX, REDIS_RESET_PASSWORD_PREFIX, REDIS_SESSION_PREFIX, User,
+    UserGender, UserID, UserRole,
 };
+use std::time::Duration;
+
 use redis::AsyncTypedCommands;
+use serde_json::Value;
 use sqlx::query;
+use uuid::Uuid;
 
 use crate::e2e::testapp::TestApp;
 
@@ -84,6 +88,98 @@ impl TestApp {
         .expect("Failed to ban user");
     }
 
+    pub async fn promote_to_admin(&self, email: &str) {
+        query!("UPDATE users SET role = 'admin' WHERE email = $1", email)
+            .execute(&self.pool)
+            .await
+            .expect("Failed to promote user");
+    }
+
+    pub async fn age_user(&self, email: &str, hours: i32) {
+        query!(
+            "UPDATE users SET created_at = created_at - make_interval(hours => $2) WHERE email = $1",
+            email,
+            hours
+        )
+        .execute(&self.pool)
+        .await
+        .expect("Failed to age user");
+    }
+
+    /// Queues a job directly, as another instance would.
+    pub async fn enqueue_job(&self, kind: &str, payload: Value, run_in_seconds: f64) -> Uuid {
+        query!(
+            r#"
+                INSERT INTO jobs (kind, payload, max_attempts, run_at)
+                VALUES ($1, $2, 3, NOW() + make_interval(secs => $3))
+                RETURNING id
+            "#,
+            kind,
+            payload,
+            run_in_seconds
+        )
+        .fetch_one(&self.pool)
+        .await
+        .expect("Failed to enqueue job")
+        .id
+    }
+
+    /// Polls until the job is completed or failed, returning its status and last error.
+    pub async fn wait_for_job(&self, id: Uuid) -> (String, Option<String>) {
+        for _ in 0..50 {
+            let (status, error) = self.job_status(id).await;
+            if status == "completed" || status == "failed" {
+                return (status, error);
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        panic!("job {id} did not finish");
+    }
+
+    pub async fn job_status(&self, id: Uuid) -> (String, Option<String>) {
+        let record = query!(
+            r#"SELECT status::text AS "status!", last_error FROM jobs WHERE id = $1"#,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .expect("Failed to get job");
+
+        (record.status, record.last_error)
+    }
+
+    /// Moves the last credential check of every session an hour into the past.
+    pub async fn age_session_authentication(&mut self) {
+        const HOUR: i64 = 60 * 60;
+
+        query!("UPDATE sessions SET authenticated_at = authenticated_at - INTERVAL '1 hour'")
+            .execute(&self.pool)
+            .await
+            .expect("Failed to age sessions");
+
+        let keys = self
+            .redis
+            .keys(format!("{}*", REDIS_SESSION_PREFIX))
+            .await
+            .expect("Failed to get redis keys");
+
+        for key in keys {
+            let Some(value) = self.redis.get(&key).await.expect("Failed to get session") else {
+                continue;
+            };
+
+            let mut session: Value = serde_json::from_str(&value).expect("Invalid session");
+            session["authenticated_at"] =
+                (session["authenticated_at"].as_i64().unwrap() - HOUR).into();
+
+            self.redis
+                .set(&key, session.to_string())
+                .await
+                .expect("Failed to update session");
+        }
+    }
+
     pub async fn get_redis_value(&mut self, key_type: RedisKeyType) -> Option<String> {
         let (pattern, prefix) = match key_type {
             RedisKeyType::AccountVerification => (
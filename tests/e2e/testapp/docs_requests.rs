@@ -0,0 +1,21 @@
+use reqwest::Response;
+
+use crate::e2e::testapp::TestApp;
+
+impl TestApp {
+    pub async fn openapi(&self) -> Response {
+        self.http_client
+            .get(format!("{}{}", self.base_address(), "/api/openapi.json"))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn docs(&self) -> Response {
+        self.http_client
+            .get(format!("{}{}", self.base_address(), "/api/docs"))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+}
@@ -0,0 +1,13 @@
+use reqwest::Response;
+
+use crate::e2e::testapp::TestApp;
+
+impl TestApp {
+    pub async fn get_unknown_route(&self) -> Response {
+        self.http_client
+            .get(format!("{}{}", self.address, "/does-not-exist"))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+}
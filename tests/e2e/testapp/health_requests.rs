@@ -0,0 +1,25 @@
+use reqwest::Response;
+
+use crate::e2e::testapp::TestApp;
+
+impl TestApp {
+    pub(super) fn base_address(&self) -> &str {
+        self.address.trim_end_matches("/api/v1")
+    }
+
+    pub async fn health_live(&self) -> Response {
+        self.http_client
+            .get(format!("{}{}", self.base_address(), "/health/live"))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn health_ready(&self) -> Response {
+        self.http_client
+            .get(format!("{}{}", self.base_address(), "/health/ready"))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+}
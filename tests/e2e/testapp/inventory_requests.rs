@@ -0,0 +1,80 @@
+use kicksapi::middlewares::CSRF_HEADER_NAME;
+use reqwest::Response;
+use serde::Serialize;
+
+use crate::e2e::testapp::TestApp;
+
+impl TestApp {
+    pub async fn get_availability(&self, slug: &str) -> Response {
+        self.http_client
+            .get(format!("{}/products/{}/availability", self.address, slug))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn create_variant<Body>(&self, product_id: &str, body: &Body) -> Response
+    where
+        Body: Serialize,
+    {
+        let csrf_token = self.csrf_token().await;
+
+        self.http_client
+            .post(format!(
+                "{}/admin/products/{}/variants",
+                self.address, product_id
+            ))
+            .header(CSRF_HEADER_NAME, csrf_token)
+            .json(&body)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn list_variants(&self, product_id: &str) -> Response {
+        self.http_client
+            .get(format!(
+                "{}/admin/products/{}/variants",
+                self.address, product_id
+            ))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn adjust_stock<Body>(&self, variant_id: &str, body: &Body) -> Response
+    where
+        Body: Serialize,
+    {
+        let csrf_token = self.csrf_token().await;
+
+        self.http_client
+            .post(format!(
+                "{}/admin/variants/{}/stock",
+                self.address, variant_id
+            ))
+            .header(CSRF_HEADER_NAME, csrf_token)
+            .json(&body)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn set_price_override<Body>(&self, variant_id: &str, body: &Body) -> Response
+    where
+        Body: Serialize,
+    {
+        let csrf_token = self.csrf_token().await;
+
+        self.http_client
+            .put(format!(
+                "{}/admin/variants/{}/price",
+                self.address, variant_id
+            ))
+            .header(CSRF_HEADER_NAME, csrf_token)
+            .json(&body)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+}
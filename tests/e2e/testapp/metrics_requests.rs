@@ -0,0 +1,21 @@
+use reqwest::Response;
+
+use crate::e2e::testapp::TestApp;
+
+impl TestApp {
+    pub async fn metrics(&self) -> Response {
+        self.http_client
+            .get(format!("{}{}", self.metrics_address, "/metrics"))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn metrics_on_api_port(&self) -> Response {
+        self.http_client
+            .get(format!("{}{}", self.base_address(), "/metrics"))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+}
@@ -19,11 +19,25 @@ use uuid::Uuid;
 
 use crate::e2e::testapp::setup_database::{setup_postgres, setup_redis};
 
+mod admin_requests;
 mod auth_requests;
+mod catalog_requests;
 mod database;
+mod docs_requests;
+mod errors_requests;
+mod health_requests;
+mod inventory_requests;
+mod metrics_requests;
 mod setup_database;
+mod webhook_receiver;
+mod webhooks_requests;
 
 pub use database::RedisKeyType;
+pub use webhook_receiver::{ReceivedWebhook, WebhookReceiver};
+
+/// Stands in for a secret retired by a cookie key rotation.
+pub const PREVIOUS_COOKIE_SECRET: &str =
+    "0f3a9c1e7b5d2f8a4c6e0b9d3f7a1c5e8b2d6f0a4c9e3b7d1f5a8c2e6b0d4f9a3c7e1b5d8f2a6c0e4b9d";
 
 static TRACING: LazyLock<()> = LazyLock::new(|| {
     let env_filter = EnvFilter::new("info");
@@ -41,6 +55,7 @@ static TRACING: LazyLock<()> = LazyLock::new(|| {
 
 pub struct TestApp {
     address: String,
+    metrics_address: String,
     pool: PgPool,
     redis: MultiplexedConnection,
     http_client: Client,
@@ -49,6 +64,14 @@ pub struct TestApp {
 }
 
 pub async fn setup<T>(func: T)
+where
+    T: AsyncFnOnce(TestApp),
+{
+    setup_with(|_| {}, func).await
+}
+
+/// Like [`setup`], with a hook to adjust the configuration before the app is built.
+pub async fn setup_with<T>(configure: impl FnOnce(&mut Configuration), func: T)
 where
     T: AsyncFnOnce(TestApp),
 {
@@ -62,11 +85,15 @@ where
         }
     }
 
-    let mut config = Configuration::new();
+    let mut config = Configuration::load().expect("Failed to load configuration");
     config.application.port = 0;
+    config.metrics.port = 0;
     config.database.name = format!("test-{}", Uuid::new_v4());
-    config.ratelimit.sign_up = 15;
-    config.ratelimit.reset_password = 15;
+    config.ratelimit.sign_up.requests = 15;
+    config.ratelimit.reset_password.requests = 15;
+    config.application.shutdown_drain_seconds = 0;
+    config.application.previous_cookie_secrets = vec![PREVIOUS_COOKIE_SECRET.into()];
+    configure(&mut config);
 
     let (redis, host, port, cleanup_redis) = setup_redis().await;
     let (pool, cleanup_postgres) = setup_postgres(&config.database).await;
@@ -79,6 +106,7 @@ where
         .expect("Failed to build app");
 
     let app_port = app.port();
+    let metrics_port = app.metrics_port();
 
     let token = CancellationToken::new();
     let server_handle = tokio::spawn(app.run(token.clone()));
@@ -91,6 +119,7 @@ where
 
     let test_app = TestApp {
         address: format!("http://localhost:{}/api/v1", app_port),
+        metrics_address: format!("http://localhost:{}", metrics_port),
         pool: pool.clone(),
         redis,
         http_client: client,
@@ -1,4 +1,6 @@
-use kicksapi::configuration::database_config::DatabaseConfig;
+use kicksapi::{
+    clients::database_client::MIGRATOR, configuration::database_config::DatabaseConfig,
+};
 use redis::aio::MultiplexedConnection;
 use sqlx::postgres::PgConnectOptions;
 use sqlx::{ConnectOptions, Connection, Executor, PgPool};
@@ -25,7 +27,7 @@ pub async fn setup_postgres(config: &DatabaseConfig) -> (PgPool, impl AsyncFnOnc
         .await
         .expect("Failed to connect to Postgres.");
 
-    sqlx::migrate!("./migrations")
+    MIGRATOR
         .run(&pool)
         .await
         .expect("Failed to migrate the database");
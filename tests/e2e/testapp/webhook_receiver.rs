@@ -0,0 +1,81 @@
+use std::{
+    net::SocketAddr,
+    sync::{
+        Arc,
+        atomic::{AtomicU16, Ordering},
+    },
+    time::Duration,
+};
+
+use axum::{
+    Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+};
+use tokio::{net::TcpListener, sync::mpsc};
+
+pub struct ReceivedWebhook {
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl ReceivedWebhook {
+    pub fn header(&self, name: &str) -> &str {
+        self.headers[name].to_str().unwrap()
+    }
+}
+
+type ReceiverState = (mpsc::UnboundedSender<ReceivedWebhook>, Arc<AtomicU16>);
+
+/// A local endpoint that records every webhook it receives and answers
+/// with a configurable status.
+pub struct WebhookReceiver {
+    pub url: String,
+    requests: mpsc::UnboundedReceiver<ReceivedWebhook>,
+    status: Arc<AtomicU16>,
+}
+
+impl WebhookReceiver {
+    pub async fn start() -> Self {
+        let (tx, requests) = mpsc::unbounded_channel();
+        let status = Arc::new(AtomicU16::new(StatusCode::OK.as_u16()));
+
+        let router = Router::new()
+            .route("/hooks", post(receive))
+            .with_state((tx, status.clone()));
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .expect("Failed to bind webhook receiver");
+        let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        Self {
+            url,
+            requests,
+            status,
+        }
+    }
+
+    pub fn respond_with(&self, status: StatusCode) {
+        self.status.store(status.as_u16(), Ordering::SeqCst);
+    }
+
+    pub async fn next(&mut self) -> ReceivedWebhook {
+        tokio::time::timeout(Duration::from_secs(10), self.requests.recv())
+            .await
+            .expect("No webhook received")
+            .expect("Webhook receiver stopped")
+    }
+}
+
+async fn receive(
+    State((tx, status)): State<ReceiverState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    let _ = tx.send(ReceivedWebhook { headers, body });
+
+    StatusCode::from_u16(status.load(Ordering::SeqCst)).unwrap()
+}
@@ -0,0 +1,69 @@
+use kicksapi::middlewares::CSRF_HEADER_NAME;
+use reqwest::{Response, Url};
+use serde::Serialize;
+
+use crate::e2e::testapp::TestApp;
+
+impl TestApp {
+    pub async fn create_webhook<Body>(&self, body: &Body) -> Response
+    where
+        Body: Serialize,
+    {
+        let csrf_token = self.csrf_token().await;
+
+        self.http_client
+            .post(format!("{}{}", self.address, "/admin/webhooks"))
+            .header(CSRF_HEADER_NAME, csrf_token)
+            .json(&body)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn list_webhooks(&self) -> Response {
+        self.http_client
+            .get(format!("{}{}", self.address, "/admin/webhooks"))
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn delete_webhook(&self, id: &str) -> Response {
+        let csrf_token = self.csrf_token().await;
+
+        self.http_client
+            .delete(format!("{}/admin/webhooks/{}", self.address, id))
+            .header(CSRF_HEADER_NAME, csrf_token)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn list_webhook_deliveries(&self, id: &str, query: &[(&str, &str)]) -> Response {
+        let url = Url::parse_with_params(
+            &format!("{}/admin/webhooks/{}/deliveries", self.address, id),
+            query,
+        )
+        .expect("Invalid URL");
+
+        self.http_client
+            .get(url)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+
+    pub async fn replay_webhook_delivery(&self, id: &str, delivery_id: &str) -> Response {
+        let csrf_token = self.csrf_token().await;
+
+        self.http_client
+            .post(format!(
+                "{}/admin/webhooks/{}/deliveries/{}/replay",
+                self.address, id, delivery_id
+            ))
+            .header(CSRF_HEADER_NAME, csrf_token)
+            .send()
+            .await
+            .expect("Request failed")
+    }
+}
@@ -0,0 +1,314 @@
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use kicksapi::{
+    ApiResponse,
+    features::{
+        auth::PASSWORD_MIN_LENGTH,
+        shared::Page,
+        webhooks::{
+            CreatedWebhookEndpointResponse, WEBHOOK_SIGNATURE_HEADER, WebhookDeliveryResponse,
+            WebhookDeliveryStatus, WebhookEndpointResponse,
+        },
+    },
+};
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+use sha2::Sha256;
+
+use crate::e2e::testapp::{ReceivedWebhook, TestApp, WebhookReceiver, setup, setup_with};
+
+fn user(email: &str) -> Value {
+    json!({
+        "email": email,
+        "password": "s".repeat(PASSWORD_MIN_LENGTH),
+    })
+}
+
+async fn sign_in_as_admin(app: &mut TestApp) {
+    let admin = user("admin@gmail.com");
+    app.create_and_verify(&admin).await;
+    app.promote_to_admin("admin@gmail.com").await;
+
+    let response = app.sign_in(&admin).await;
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+async fn create_webhook(app: &TestApp, url: &str, event_types: &[&str]) -> (String, String) {
+    let response = app
+        .create_webhook(&json!({ "url": url, "event_types": event_types }))
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    let created = response
+        .json::<ApiResponse<CreatedWebhookEndpointResponse>>()
+        .await
+        .unwrap()
+        .data;
+
+    (created.endpoint.id.to_string(), created.secret)
+}
+
+/// Polls the delivery log until the latest delivery has left `pending`.
+async fn latest_delivery(app: &TestApp, endpoint_id: &str) -> WebhookDeliveryResponse {
+    for _ in 0..50 {
+        let mut page = app
+            .list_webhook_deliveries(endpoint_id, &[("limit", "1")])
+            .await
+            .json::<ApiResponse<Page<WebhookDeliveryResponse>>>()
+            .await
+            .unwrap()
+            .data;
+
+        if let Some(delivery) = page.items.pop()
+            && delivery.status != WebhookDeliveryStatus::Pending
+        {
+            return delivery;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    panic!("no delivery for {endpoint_id} was attempted");
+}
+
+fn assert_signed(webhook: &ReceivedWebhook, secret: &str) {
+    let signature = webhook.header(WEBHOOK_SIGNATURE_HEADER);
+    let (timestamp, digest) = signature
+        .strip_prefix("t=")
+        .and_then(|rest| rest.split_once(",v1="))
+        .expect("Malformed signature header");
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(format!("{timestamp}.").as_bytes());
+    mac.update(&webhook.body);
+    mac.verify_slice(&hex::decode(digest).unwrap())
+        .expect("Signature does not match the body");
+}
+
+#[tokio::test]
+async fn sign_up_is_delivered_signed_and_logged() {
+    setup(async |mut app: TestApp| {
+        let mut receiver = WebhookReceiver::start().await;
+        sign_in_as_admin(&mut app).await;
+        let (endpoint_id, secret) = create_webhook(&app, &receiver.url, &["user.signed_up"]).await;
+
+        let response = app.sign_up(&user("new@gmail.com")).await;
+        assert_eq!(StatusCode::CREATED, response.status());
+
+        let webhook = receiver.next().await;
+        assert_signed(&webhook, &secret);
+        assert_eq!(webhook.header("x-kicks-event"), "user.signed_up");
+        let body: Value = serde_json::from_slice(&webhook.body).unwrap();
+        assert_eq!(body["type"], "user.signed_up");
+        assert_eq!(body["data"]["email"], "new@gmail.com");
+
+        let delivery = latest_delivery(&app, &endpoint_id).await;
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Succeeded);
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.response_status, Some(200));
+        assert_eq!(delivery.id.to_string(), webhook.header("x-kicks-delivery"));
+        assert_eq!(delivery.payload, body);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn failed_delivery_is_retried_and_can_be_replayed() {
+    setup(async |mut app: TestApp| {
+        let mut receiver = WebhookReceiver::start().await;
+        receiver.respond_with(StatusCode::SERVICE_UNAVAILABLE);
+        sign_in_as_admin(&mut app).await;
+        let (endpoint_id, secret) = create_webhook(&app, &receiver.url, &["user.verified"]).await;
+
+        app.create_and_verify(&user("new@gmail.com")).await;
+        receiver.next().await;
+
+        let failed = latest_delivery(&app, &endpoint_id).await;
+        assert_eq!(failed.status, WebhookDeliveryStatus::Retrying);
+        assert_eq!(failed.response_status, Some(503));
+        assert!(failed.last_error.is_some());
+
+        receiver.respond_with(StatusCode::NO_CONTENT);
+        let response = app
+            .replay_webhook_delivery(&endpoint_id, &failed.id.to_string())
+            .await;
+        assert_eq!(StatusCode::ACCEPTED, response.status());
+        let replay = response
+            .json::<ApiResponse<WebhookDeliveryResponse>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(replay.replay_of, Some(failed.id));
+        assert_eq!(replay.event_id, failed.event_id);
+
+        let webhook = receiver.next().await;
+        assert_signed(&webhook, &secret);
+        assert_eq!(webhook.header("x-kicks-delivery"), replay.id.to_string());
+
+        let delivered = latest_delivery(&app, &endpoint_id).await;
+        assert_eq!(delivered.status, WebhookDeliveryStatus::Succeeded);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn bans_queued_by_kicksctl_reach_subscribed_endpoints() {
+    setup(async |mut app: TestApp| {
+        let mut receiver = WebhookReceiver::start().await;
+        sign_in_as_admin(&mut app).await;
+        let (endpoint_id, _) = create_webhook(&app, &receiver.url, &["user.banned"]).await;
+
+        // What `kicksctl ban` leaves in the queue for the server's workers.
+        let event_id = uuid::Uuid::new_v4();
+        let delivery = json!({
+            "id": event_id,
+            "event": "admin.user_banned",
+            "subscriber": "webhooks",
+            "payload": { "user_id": uuid::Uuid::new_v4(), "email": "banned@gmail.com" },
+        });
+        let id = app
+            .enqueue_job("events.deliver", delivery.clone(), 0.0)
+            .await;
+        assert_eq!(app.wait_for_job(id).await.0, "completed");
+
+        let webhook = receiver.next().await;
+        let body: Value = serde_json::from_slice(&webhook.body).unwrap();
+        assert_eq!(body["id"], event_id.to_string());
+        assert_eq!(body["type"], "user.banned");
+        assert_eq!(body["data"]["email"], "banned@gmail.com");
+
+        // A retried dispatch of the same event doesn't deliver it again.
+        let id = app.enqueue_job("events.deliver", delivery, 0.0).await;
+        assert_eq!(app.wait_for_job(id).await.0, "completed");
+        let deliveries = app
+            .list_webhook_deliveries(&endpoint_id, &[])
+            .await
+            .json::<ApiResponse<Page<WebhookDeliveryResponse>>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(deliveries.items.len(), 1);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn endpoints_can_be_listed_and_deleted() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app).await;
+        let (endpoint_id, _) = create_webhook(
+            &app,
+            "https://crm.example.com/hooks",
+            &["user.signed_up", "user.banned"],
+        )
+        .await;
+
+        let endpoints = app
+            .list_webhooks()
+            .await
+            .json::<ApiResponse<Vec<WebhookEndpointResponse>>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].url, "https://crm.example.com/hooks");
+
+        assert_eq!(
+            StatusCode::OK,
+            app.delete_webhook(&endpoint_id).await.status()
+        );
+        assert_eq!(
+            StatusCode::NOT_FOUND,
+            app.delete_webhook(&endpoint_id).await.status()
+        );
+        assert_eq!(
+            StatusCode::NOT_FOUND,
+            app.list_webhook_deliveries(&endpoint_id, &[])
+                .await
+                .status()
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn invalid_endpoints_are_rejected() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app).await;
+
+        let response = app
+            .create_webhook(&json!({ "url": "ftp://example.com", "event_types": ["user.deleted"] }))
+            .await;
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        let body: Value = response.json().await.unwrap();
+        assert!(body["errors"]["url"].is_array(), "{body}");
+        assert!(body["errors"]["event_types"].is_array(), "{body}");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn only_admins_manage_webhooks() {
+    setup(async |mut app: TestApp| {
+        app.create_and_sign_in(&user("regular@gmail.com")).await;
+
+        assert_eq!(StatusCode::FORBIDDEN, app.list_webhooks().await.status());
+        let response = app
+            .create_webhook(
+                &json!({ "url": "https://example.com", "event_types": ["user.banned"] }),
+            )
+            .await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn private_receivers_are_rejected_unless_allowed() {
+    setup_with(
+        |config| config.application.allow_private_webhook_targets = false,
+        async |mut app: TestApp| {
+            sign_in_as_admin(&mut app).await;
+
+            for url in [
+                "http://127.0.0.1:8080/hooks",
+                "http://localhost/hooks",
+                "http://169.254.169.254/latest/meta-data",
+                "http://[::1]/hooks",
+            ] {
+                let response = app
+                    .create_webhook(&json!({ "url": url, "event_types": ["user.banned"] }))
+                    .await;
+
+                assert_eq!(StatusCode::BAD_REQUEST, response.status(), "{url}");
+                let body: Value = response.json().await.unwrap();
+                assert!(body["errors"]["url"].is_array(), "{body}");
+            }
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn creating_an_endpoint_requires_recent_authentication() {
+    setup(async |mut app: TestApp| {
+        sign_in_as_admin(&mut app).await;
+        app.age_session_authentication().await;
+        let data =
+            json!({ "url": "https://crm.example.com/hooks", "event_types": ["user.banned"] });
+
+        let response = app.create_webhook(&data).await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["code"], "reauthentication_required");
+
+        let response = app
+            .reauthenticate(&json!({ "password": "s".repeat(PASSWORD_MIN_LENGTH) }))
+            .await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let response = app.create_webhook(&data).await;
+        assert_eq!(StatusCode::CREATED, response.status());
+    })
+    .await;
+}